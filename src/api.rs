@@ -0,0 +1,333 @@
+//! Public API for embedding stax in other tools.
+//!
+//! The CLI remains the primary consumer of the engine; this module wraps the
+//! most common flows (status, restack, submit) behind typed results and never
+//! prints or prompts. Operations that rewrite history are recorded like their
+//! CLI counterparts, so `stax undo` works on them too. Failures callers may
+//! want to handle (an untracked branch, missing auth) carry a [`StaxError`].
+//!
+//! ```no_run
+//! let repo = stax::api::open(std::path::Path::new("."))?;
+//! let status = stax::api::status(&repo)?;
+//! for branch in status.branches.iter().filter(|b| b.needs_restack) {
+//!     println!("{} needs restack", branch.name);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::config::Config;
+use crate::ops::receipt::{OpKind, PlanSummary};
+use crate::ops::tx::Transaction;
+use crate::remote::RemoteInfo;
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+pub use crate::engine::stack::StackBranch;
+pub use crate::engine::{BranchMetadata, PrInfo, Stack};
+pub use crate::error::StaxError;
+pub use crate::git::{GitRepo, RebaseResult};
+
+/// Open the repository containing `path`. Metadata is signed and verified
+/// like the CLI does when `auth.sign_metadata` is set.
+pub fn open(path: &Path) -> Result<GitRepo> {
+    if let Some(key) = Config::load()
+        .ok()
+        .and_then(|config| config.metadata_signing_key().ok().flatten())
+    {
+        crate::engine::metadata::set_signing_key(key);
+    }
+    GitRepo::open_at(path)
+}
+
+/// One tracked branch (or trunk) as reported by [`status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchStatus {
+    pub name: String,
+    pub parent: Option<String>,
+    pub children: Vec<String>,
+    pub is_current: bool,
+    pub is_trunk: bool,
+    pub needs_restack: bool,
+    pub pr_number: Option<u64>,
+    pub pr_state: Option<String>,
+    /// Commits on the branch that are not on its parent
+    pub ahead: usize,
+    /// Commits on the parent that are not on the branch
+    pub behind: usize,
+}
+
+/// Snapshot of every tracked branch, parents before children
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackStatus {
+    pub trunk: String,
+    pub current: String,
+    pub branches: Vec<BranchStatus>,
+}
+
+impl StackStatus {
+    pub fn branch(&self, name: &str) -> Option<&BranchStatus> {
+        self.branches.iter().find(|b| b.name == name)
+    }
+}
+
+/// Load the stack and compute per-branch status. Local only; no network.
+pub fn status(repo: &GitRepo) -> Result<StackStatus> {
+    let stack = Stack::load(repo)?;
+    let current = repo.current_branch()?;
+
+    let mut branches = Vec::new();
+    for name in parent_first(&stack) {
+        let info = &stack.branches[&name];
+        let (ahead, behind) = info
+            .parent
+            .as_deref()
+            .and_then(|p| repo.commits_ahead_behind(p, &name).ok())
+            .unwrap_or((0, 0));
+        let mut children = info.children.clone();
+        children.sort();
+        branches.push(BranchStatus {
+            is_current: name == current,
+            is_trunk: name == stack.trunk,
+            parent: info.parent.clone(),
+            children,
+            needs_restack: info.needs_restack,
+            pr_number: info.pr_number,
+            pr_state: info.pr_state.clone(),
+            ahead,
+            behind,
+            name,
+        });
+    }
+
+    Ok(StackStatus {
+        trunk: stack.trunk,
+        current,
+        branches,
+    })
+}
+
+/// Result of [`restack`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestackOutcome {
+    /// Nothing in scope needed restacking
+    UpToDate,
+    /// Every branch that needed it was rebased onto its parent
+    Restacked(Vec<String>),
+    /// A rebase stopped on conflicts; resolve them and run `stax continue`
+    Conflict {
+        restacked: Vec<String>,
+        branch: String,
+    },
+}
+
+/// Restack the stack containing `branch` (ancestors, the branch, descendants)
+/// parent-first. Requires a clean working tree.
+pub fn restack(repo: &GitRepo, branch: &str) -> Result<RestackOutcome> {
+    let stack = Stack::load(repo)?;
+    stack.require(branch)?;
+    if repo.is_dirty()? {
+        anyhow::bail!("Working tree is dirty. Please stash or commit changes first.");
+    }
+
+    let scope: Vec<String> = stack
+        .current_stack(branch)
+        .into_iter()
+        .filter(|b| b != &stack.trunk)
+        .collect();
+    if !scope.iter().any(|b| stack.branches[b].needs_restack) {
+        return Ok(RestackOutcome::UpToDate);
+    }
+
+    let original = repo.current_branch()?;
+    let mut tx = Transaction::begin(OpKind::Restack, repo, true)?;
+    tx.plan_branches(repo, &scope)?;
+    tx.set_plan_summary(PlanSummary {
+        branches_to_rebase: scope.len(),
+        branches_to_push: 0,
+        description: vec![format!("Restack up to {} branches", scope.len())],
+    });
+    tx.snapshot()?;
+
+    let mut restacked = Vec::new();
+    for name in &scope {
+        // Parents move as we go, so re-check against the live stack
+        let live = Stack::load(repo)?;
+        if !live.branches.get(name).is_some_and(|b| b.needs_restack) {
+            continue;
+        }
+        let Some(meta) = BranchMetadata::read(repo.inner(), name)? else {
+            continue;
+        };
+
+        match repo.rebase_branch_onto(name, &meta.parent_branch_name, false)? {
+            RebaseResult::Success => {
+                let parent_rev = repo.parent_commit(&meta.parent_branch_name)?;
+                meta.restacked_onto(parent_rev).write(repo.inner(), name)?;
+                tx.record_after(repo, name)?;
+                restacked.push(name.clone());
+            }
+            RebaseResult::Conflict => {
+                tx.finish_err("Rebase conflict", Some("rebase"), Some(name))?;
+                return Ok(RestackOutcome::Conflict {
+                    restacked,
+                    branch: name.clone(),
+                });
+            }
+        }
+    }
+
+    if repo.current_branch()? != original {
+        repo.checkout(&original)?;
+    }
+    tx.finish_ok()?;
+    Ok(RestackOutcome::Restacked(restacked))
+}
+
+/// Options for [`submit`]
+#[derive(Debug, Clone, Default)]
+pub struct SubmitOptions {
+    /// Push only; don't create or update PRs
+    pub no_pr: bool,
+    /// Open new PRs as drafts
+    pub draft: bool,
+}
+
+/// What [`submit`] did for one branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmittedBranch {
+    pub branch: String,
+    pub pushed: bool,
+    pub pr_number: Option<u64>,
+    pub pr_created: bool,
+}
+
+/// Push `branches` (parent-first) and create or retarget their PRs.
+/// New PRs use the branch's oldest commit subject as the title and an empty body.
+pub fn submit(
+    repo: &GitRepo,
+    branches: &[String],
+    options: &SubmitOptions,
+) -> Result<Vec<SubmittedBranch>> {
+    let config = Config::load()?;
+    let stack = Stack::load(repo)?;
+    let remote_info = RemoteInfo::from_repo(repo, &config)?;
+    let workdir = repo.workdir()?;
+
+    let order: Vec<String> = parent_first(&stack)
+        .into_iter()
+        .filter(|b| branches.contains(b))
+        .collect();
+    for branch in branches {
+        if !order.contains(branch) || branch == &stack.trunk {
+            return Err(StaxError::NotTracked(branch.clone()).into());
+        }
+    }
+
+    let mut tx = Transaction::begin(OpKind::Submit, repo, true)?;
+    for branch in &order {
+        tx.plan_remote_branch(repo, &remote_info.name, branch)?;
+    }
+    tx.snapshot()?;
+
+    let mut results = Vec::new();
+    for branch in &order {
+        let status = Command::new("git")
+            .args(["push", "-f", "-u", &remote_info.name, branch])
+            .current_dir(workdir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .context("Failed to push branch")?;
+        if !status.success() {
+            tx.finish_err("Push failed", Some("push"), Some(branch))?;
+            anyhow::bail!("Failed to push branch {}", branch);
+        }
+
+        let oid = repo.branch_commit(branch)?;
+        tx.record_remote_after(&remote_info.name, branch, &oid);
+        if let Some(meta) = BranchMetadata::read(repo.inner(), branch)? {
+            BranchMetadata {
+                last_submitted_revision: Some(oid),
+                ..meta
+            }
+            .write(repo.inner(), branch)?;
+        }
+        results.push(SubmittedBranch {
+            branch: branch.clone(),
+            pushed: true,
+            pr_number: stack.branches[branch].pr_number,
+            pr_created: false,
+        });
+    }
+
+    if !options.no_pr {
+        let rt = tokio::runtime::Runtime::new()?;
+        let outcome = rt.block_on(async {
+            let client = remote_info.provider()?;
+            let open_prs = client.list_open_prs_by_head().await?;
+
+            for result in &mut results {
+                let meta = BranchMetadata::read(repo.inner(), &result.branch)?
+                    .context(format!("No metadata for branch {}", result.branch))?;
+                let parent = meta.parent_branch_name.clone();
+
+                let pr = match open_prs.get(&result.branch) {
+                    Some(existing) => {
+                        if existing.info.base != parent {
+                            client.update_base(existing.info.number, &parent).await?;
+                        }
+                        existing.info.clone()
+                    }
+                    None => {
+                        let title = repo
+                            .commits_between(&parent, &result.branch)?
+                            .last()
+                            .cloned()
+                            .unwrap_or_else(|| result.branch.clone());
+                        result.pr_created = true;
+                        client
+                            .create_pr(&result.branch, &parent, &title, "", options.draft)
+                            .await?
+                    }
+                };
+
+                result.pr_number = Some(pr.number);
+                BranchMetadata {
+                    pr_info: Some(PrInfo {
+                        number: pr.number,
+                        state: pr.state.clone(),
+                        is_draft: Some(pr.is_draft),
+                    }),
+                    ..meta
+                }
+                .write(repo.inner(), &result.branch)?;
+            }
+            Ok::<(), anyhow::Error>(())
+        });
+
+        if let Err(err) = outcome {
+            tx.finish_err(&err.to_string(), Some("pr"), None)?;
+            return Err(err);
+        }
+    }
+
+    tx.finish_ok()?;
+    Ok(results)
+}
+
+/// Tracked branches ordered parent-first from trunk, siblings sorted by name
+fn parent_first(stack: &Stack) -> Vec<String> {
+    let mut order = Vec::new();
+    let mut queue = vec![stack.trunk.clone()];
+    while let Some(name) = queue.pop() {
+        let Some(branch) = stack.branches.get(&name) else {
+            continue;
+        };
+        order.push(name);
+        let mut children = branch.children.clone();
+        children.sort_by(|a, b| b.cmp(a));
+        queue.extend(children);
+    }
+    order
+}
@@ -1,8 +1,14 @@
 use crate::config::Config;
+use crate::git::GitRepo;
+use crate::github::{GitHubClient, TokenIdentity};
+use crate::remote::RemoteInfo;
 use anyhow::Result;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Password};
 
+/// Classic token scopes stax relies on
+const REQUIRED_SCOPES: &[&str] = &["repo", "read:org"];
+
 pub fn run(token: Option<String>, from_gh: bool) -> Result<()> {
     let token = if from_gh {
         Config::gh_cli_token_for_import()?
@@ -48,7 +54,7 @@ pub fn run(token: Option<String>, from_gh: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn status() -> Result<()> {
+pub fn status(verify: bool) -> Result<()> {
     let status = Config::github_auth_status();
 
     println!("{}", "GitHub Auth Status".bold());
@@ -95,6 +101,9 @@ pub fn status() -> Result<()> {
             "{}",
             "Run `stax auth`, `stax auth --from-gh`, or `gh auth login`.".dimmed()
         );
+    } else if verify {
+        println!();
+        verify_token()?;
     }
 
     Ok(())
@@ -120,3 +129,93 @@ fn print_source_line(label: &str, available: bool, enabled: bool, note: &str) {
         if note.is_empty() { "" } else { note }
     );
 }
+
+/// Ask GitHub who the resolved token belongs to and what it may do
+fn verify_token() -> Result<()> {
+    let config = Config::load()?;
+    // Outside a repo (or without a GitHub remote) fall back to github.com
+    let remote = GitRepo::open()
+        .ok()
+        .and_then(|repo| RemoteInfo::from_repo(&repo, &config).ok());
+    let (owner, repo, api_base_url) = match &remote {
+        Some(remote) => (
+            remote.owner(),
+            remote.repo.as_str(),
+            remote.api_base_url.clone(),
+        ),
+        None => ("", "", None),
+    };
+
+    let client = GitHubClient::new(owner, repo, api_base_url)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let identity = runtime.block_on(client.token_identity())?;
+    print_identity(&identity);
+    Ok(())
+}
+
+fn print_identity(identity: &TokenIdentity) {
+    println!(
+        "{} {}",
+        "✓ Authenticated as:".green(),
+        identity.login.cyan()
+    );
+
+    let Some(scopes) = &identity.scopes else {
+        println!(
+            "{}",
+            "  Scopes: not reported (fine-grained or app token; check its repository permissions)"
+                .dimmed()
+        );
+        return;
+    };
+
+    let shown = if scopes.is_empty() {
+        "(none)".to_string()
+    } else {
+        scopes.join(", ")
+    };
+    println!("  Scopes: {}", shown.dimmed());
+
+    let missing = missing_scopes(scopes);
+    if missing.is_empty() {
+        println!("{}", "  ✓ Token has the scopes stax needs".green());
+    } else {
+        println!(
+            "{}",
+            format!("  ⚠ Missing scopes: {}", missing.join(", ")).yellow()
+        );
+    }
+}
+
+/// Required scopes the token lacks; broader org scopes imply `read:org`
+fn missing_scopes(scopes: &[String]) -> Vec<&'static str> {
+    REQUIRED_SCOPES
+        .iter()
+        .copied()
+        .filter(|required| !scopes.iter().any(|scope| grants(scope, required)))
+        .collect()
+}
+
+fn grants(scope: &str, required: &str) -> bool {
+    scope == required || (required == "read:org" && matches!(scope, "write:org" | "admin:org"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scopes(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn missing_scopes_reports_absent_required_scopes() {
+        assert_eq!(missing_scopes(&scopes(&["gist"])), vec!["repo", "read:org"]);
+        assert!(missing_scopes(&scopes(&["repo", "read:org"])).is_empty());
+    }
+
+    #[test]
+    fn missing_scopes_accepts_broader_org_scopes() {
+        assert!(missing_scopes(&scopes(&["repo", "admin:org"])).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::config::Config;
-use crate::engine::BranchMetadata;
+use crate::engine::{BranchMetadata, Stack};
 use crate::git::GitRepo;
 use crate::remote;
 use anyhow::{bail, Result};
@@ -9,12 +9,20 @@ use dialoguer::{theme::ColorfulTheme, Input, Select};
 use std::path::Path;
 use std::process::Command;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     name: Option<String>,
     message: Option<String>,
     from: Option<String>,
     prefix: Option<String>,
+    ticket: Option<String>,
     all: bool,
+    empty: bool,
+    commit: bool,
+    require_staged: bool,
+    auto_track: bool,
+    dry_run: bool,
+    track_only: bool,
 ) -> Result<()> {
     let repo = GitRepo::open()?;
     let config = Config::load()?;
@@ -46,14 +54,48 @@ pub fn run(
         }
     };
 
+    // --empty records an empty commit and --commit uses the existing index,
+    // so neither stages anything itself
+    let should_stage = should_stage && !empty && !commit;
+
+    // --commit carries the staged changes over to the new branch
+    let has_staged = commit && has_staged_changes(repo.workdir()?)?;
+    if commit && !has_staged && require_staged {
+        bail!("No staged changes to commit. Stage changes with `git add` first.");
+    }
+
     // Format the branch name according to config
-    let branch_name = match prefix.as_deref() {
-        Some(_) => config.format_branch_name_with_prefix_override(&input, prefix.as_deref()),
-        None => config.format_branch_name(&input),
-    };
+    let branch_name =
+        config.format_branch_name_with_ticket(&input, prefix.as_deref(), ticket.as_deref());
+
+    ensure_not_reserved(&repo, &branch_name)?;
 
-    // Check for branch name conflicts (Git doesn't allow both "foo" and "foo/bar")
     let existing_branches = repo.list_branches().unwrap_or_default();
+
+    if track_only {
+        // Match the name as typed first; the formatted one may carry a prefix
+        let branch = [&input, &branch_name]
+            .into_iter()
+            .find(|b| existing_branches.contains(*b))
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Branch '{}' does not exist. Drop --track-only to create it.",
+                    input
+                )
+            })?;
+        return track_existing(&repo, &branch, &parent_branch, dry_run);
+    }
+
+    if existing_branches.contains(&branch_name) && !dry_run {
+        bail!(
+            "Branch '{}' already exists. Use --track-only to stack it on '{}' instead.",
+            branch_name,
+            parent_branch
+        );
+    }
+
+    // Check for branch name conflicts (Git doesn't allow both "foo" and "foo/bar")
     for existing in &existing_branches {
         // Check if new branch would be a child path of existing (e.g., creating "foo/bar" when "foo" exists)
         if branch_name.starts_with(&format!("{}/", existing)) {
@@ -80,6 +122,25 @@ pub fn run(
         }
     }
 
+    if dry_run {
+        println!(
+            "Would create branch '{}' (stacked on {})",
+            branch_name.green(),
+            parent_branch.blue()
+        );
+        if existing_branches.contains(&branch_name) {
+            println!(
+                "{}",
+                format!("Branch '{}' already exists.", branch_name).yellow()
+            );
+        }
+        return Ok(());
+    }
+
+    if auto_track {
+        auto_track_trunk_child(&repo, &parent_branch)?;
+    }
+
     // Create the branch
     if parent_branch == current {
         repo.create_branch(&branch_name)?;
@@ -89,7 +150,10 @@ pub fn run(
 
     // Track it with current branch as parent
     let parent_rev = repo.branch_commit(&parent_branch)?;
-    let meta = BranchMetadata::new(&parent_branch, &parent_rev);
+    let meta = BranchMetadata {
+        fork_point: Some(parent_rev.clone()),
+        ..BranchMetadata::new(&parent_branch, &parent_rev)
+    };
     meta.write(repo.inner(), &branch_name)?;
 
     // Checkout the new branch
@@ -115,6 +179,48 @@ pub fn run(
         branch_name.green(),
         parent_branch.blue()
     );
+    if branch_name != input {
+        println!(
+            "{}",
+            format!("Branch name formatted from '{}'", input).dimmed()
+        );
+    }
+
+    if empty {
+        let msg = commit_message
+            .clone()
+            .unwrap_or_else(|| empty_commit_message(&input));
+        let commit_status = Command::new("git")
+            .args(["commit", "--allow-empty", "-m", &msg])
+            .current_dir(repo.workdir()?)
+            .status()?;
+
+        if !commit_status.success() {
+            bail!("Failed to create empty commit");
+        }
+
+        println!("Committed: {}", msg.cyan());
+    }
+
+    if commit {
+        if has_staged {
+            let msg = message
+                .clone()
+                .unwrap_or_else(|| empty_commit_message(&input));
+            let commit_status = Command::new("git")
+                .args(["commit", "-m", &msg])
+                .current_dir(repo.workdir()?)
+                .status()?;
+
+            if !commit_status.success() {
+                bail!("Failed to commit staged changes");
+            }
+
+            println!("Committed staged changes: {}", msg.cyan());
+        } else {
+            println!("{}", "No staged changes to commit".dimmed());
+        }
+    }
 
     // Stage changes if -a or -m was used or wizard selected it
     if should_stage {
@@ -161,6 +267,121 @@ pub fn run(
     Ok(())
 }
 
+/// Stack an existing branch on `parent` and check it out, like `stax branch track`
+/// with an explicit parent
+fn track_existing(repo: &GitRepo, branch: &str, parent: &str, dry_run: bool) -> Result<()> {
+    if branch == parent {
+        bail!("Branch '{}' cannot be stacked on itself.", branch);
+    }
+    if BranchMetadata::read(repo.inner(), branch)?.is_some() {
+        bail!(
+            "Branch '{}' is already tracked. Use `stax branch reparent` to change its parent.",
+            branch
+        );
+    }
+    if dry_run {
+        println!(
+            "Would track existing branch '{}' (stacked on {})",
+            branch.green(),
+            parent.blue()
+        );
+        return Ok(());
+    }
+
+    let parent_rev = repo.branch_commit(parent)?;
+    let meta = BranchMetadata {
+        fork_point: repo.merge_base(parent, branch).ok(),
+        ..BranchMetadata::new(parent, &parent_rev)
+    };
+    meta.write(repo.inner(), branch)?;
+    repo.checkout(branch)?;
+
+    println!(
+        "Tracked existing branch '{}' and switched to it (stacked on {})",
+        branch.green(),
+        parent.blue()
+    );
+    Ok(())
+}
+
+/// Derive a commit message from a branch name, e.g. "auth/add-login" -> "add login"
+/// Track `branch` on trunk when it was branched off trunk with plain git, so
+/// stacking on it doesn't leave a half-tracked stack. Branches that build on
+/// another tracked branch are left alone; their parent is ambiguous.
+fn auto_track_trunk_child(repo: &GitRepo, branch: &str) -> Result<()> {
+    let trunk = repo.trunk_branch()?;
+    if branch == trunk || BranchMetadata::read(repo.inner(), branch)?.is_some() {
+        return Ok(());
+    }
+    let Ok(fork_point) = repo.merge_base(&trunk, branch) else {
+        return Ok(());
+    };
+
+    let trunk_oid = git2::Oid::from_str(&repo.branch_commit(&trunk)?)?;
+    let stack = Stack::load(repo)?;
+    for tracked in stack.branches.keys().filter(|b| **b != trunk) {
+        let Ok(shared) = repo.merge_base(tracked, branch) else {
+            continue;
+        };
+        let shared = git2::Oid::from_str(&shared)?;
+        // Sharing commits that aren't on trunk means `branch` sits on `tracked`
+        if shared != trunk_oid && !repo.inner().graph_descendant_of(trunk_oid, shared)? {
+            return Ok(());
+        }
+    }
+
+    let trunk_rev = repo.branch_commit(&trunk)?;
+    let meta = BranchMetadata {
+        fork_point: Some(fork_point),
+        ..BranchMetadata::new(&trunk, &trunk_rev)
+    };
+    meta.write(repo.inner(), branch)?;
+
+    println!(
+        "{}",
+        format!(
+            "Tracked '{}' on '{}' so the new branch has a complete stack (use --no-auto-track to skip).",
+            branch, trunk
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+fn empty_commit_message(input: &str) -> String {
+    let last = input.rsplit('/').next().unwrap_or(input);
+    let message = last.replace(['-', '_'], " ").trim().to_string();
+    if message.is_empty() {
+        input.to_string()
+    } else {
+        message
+    }
+}
+
+/// Names git treats specially, which would be ambiguous as branch names
+const RESERVED_BRANCH_NAMES: &[&str] = &["HEAD", "FETCH_HEAD", "ORIG_HEAD", "MERGE_HEAD"];
+
+/// Refuse names that would collide with trunk or a git-reserved ref
+fn ensure_not_reserved(repo: &GitRepo, branch_name: &str) -> Result<()> {
+    let trunk = repo.trunk_branch()?;
+    if branch_name == trunk {
+        bail!(
+            "Cannot create '{}': it is the trunk branch. Pick a different name.",
+            branch_name
+        );
+    }
+    if RESERVED_BRANCH_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(branch_name))
+    {
+        bail!(
+            "Cannot create '{}': the name is reserved by git. Pick a different name.",
+            branch_name
+        );
+    }
+    Ok(())
+}
+
 /// Interactive wizard for branch creation when no arguments provided
 fn run_wizard(workdir: &Path, parent_branch: &str) -> Result<(String, Option<String>, bool)> {
     // Show header
@@ -231,6 +452,15 @@ fn run_wizard(workdir: &Path, parent_branch: &str) -> Result<(String, Option<Str
     Ok((name, commit_message, should_stage))
 }
 
+/// Check if the index has changes relative to HEAD
+fn has_staged_changes(workdir: &Path) -> Result<bool> {
+    let status = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
+        .current_dir(workdir)
+        .status()?;
+    Ok(!status.success())
+}
+
 /// Check if there are uncommitted changes in the working directory
 fn has_uncommitted_changes(workdir: &Path) -> bool {
     Command::new("git")
@@ -1,20 +1,32 @@
+use crate::config::Config;
 use crate::engine::BranchMetadata;
+use crate::git::repo::CommitInfo;
 use crate::git::GitRepo;
+use crate::remote::{self, RemoteInfo};
 use anyhow::Result;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect};
+use std::io::IsTerminal;
+use std::process::Command;
 
-pub fn run(branch: Option<String>, force: bool) -> Result<()> {
+pub fn run(
+    branch: Option<String>,
+    force: bool,
+    really_mean_it: bool,
+    remote: bool,
+    close_pr: bool,
+) -> Result<()> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
     let trunk = repo.trunk_branch()?;
+    let config = Config::load()?;
 
     let target = match branch {
         Some(b) => b,
         None => {
             // Interactive selection
             let mut branches = repo.list_branches()?;
-            branches.retain(|b| b != &trunk && b != &current);
+            branches.retain(|b| b != &trunk && b != &current && !config.is_protected_branch(b));
             branches.sort();
 
             if branches.is_empty() {
@@ -39,10 +51,53 @@ pub fn run(branch: Option<String>, force: bool) -> Result<()> {
         anyhow::bail!("Cannot delete current branch. Checkout a different branch first.");
     }
 
-    // Confirm if not forced
+    if config.is_protected_branch(&target) && !(force && really_mean_it) {
+        anyhow::bail!(
+            "Branch '{}' is protected (branch.protected). Pass --force --i-really-mean-it to delete it anyway.",
+            target
+        );
+    }
+
+    let remote_name = config.remote_name().to_string();
+    let parent = BranchMetadata::read(repo.inner(), &target)?.map(|meta| meta.parent_branch_name);
+
+    // Refuse to drop work that exists nowhere else, unless forced
     if !force {
+        let at_risk = unsaved_commits(&repo, &target, parent.as_deref(), &trunk, &remote_name)?;
+        if !at_risk.is_empty() {
+            eprintln!(
+                "{}",
+                format!(
+                    "Branch '{}' has {} commit(s) that are neither merged into {} nor pushed to '{}':",
+                    target,
+                    at_risk.len(),
+                    parent.as_deref().unwrap_or(&trunk),
+                    remote_name
+                )
+                .yellow()
+            );
+            for commit in &at_risk {
+                eprintln!("  {} {}", commit.short_hash.dimmed(), commit.message);
+            }
+            anyhow::bail!(
+                "Refusing to delete '{}' with unmerged, unpushed work. Use --force to delete anyway.",
+                target
+            );
+        }
+    }
+
+    // Confirm if not forced and someone is there to answer
+    if !force && std::io::stdin().is_terminal() {
+        let prompt = if remote {
+            format!(
+                "Delete branch '{}' locally and on '{}'?",
+                target, remote_name
+            )
+        } else {
+            format!("Delete branch '{}'?", target)
+        };
         let confirm = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt(format!("Delete branch '{}'?", target))
+            .with_prompt(prompt)
             .default(false)
             .interact()?;
 
@@ -52,13 +107,113 @@ pub fn run(branch: Option<String>, force: bool) -> Result<()> {
         }
     }
 
-    // Delete git branch
-    repo.delete_branch(&target, force)?;
+    // Capture PR info before metadata goes away
+    let pr_number = BranchMetadata::read(repo.inner(), &target)?
+        .and_then(|meta| meta.pr_info)
+        .filter(|pr| pr.number > 0)
+        .map(|pr| pr.number);
+
+    // Delete git branch; the check above already covers what `git branch -d` would
+    repo.delete_branch(&target, true)?;
 
     // Delete metadata
     BranchMetadata::delete(repo.inner(), &target)?;
 
     println!("Deleted branch '{}'", target.red());
 
+    if close_pr {
+        close_open_pr(&repo, &config, &target, pr_number)?;
+    }
+
+    if remote {
+        delete_remote_branch(&repo, &remote_name, &target)?;
+    }
+
+    Ok(())
+}
+
+/// Commits on `branch` not reachable from its parent, trunk, or either one's remote copy
+fn unsaved_commits(
+    repo: &GitRepo,
+    branch: &str,
+    parent: Option<&str>,
+    trunk: &str,
+    remote_name: &str,
+) -> Result<Vec<CommitInfo>> {
+    let mut bases = vec![
+        trunk.to_string(),
+        format!("{}/{}", remote_name, trunk),
+        format!("{}/{}", remote_name, branch),
+    ];
+    if let Some(parent) = parent.filter(|p| *p != trunk && *p != branch) {
+        bases.push(parent.to_string());
+    }
+    repo.commits_not_reachable_from(branch, &bases)
+}
+
+/// Close the branch's open PR, looking it up by head when metadata has no PR number
+fn close_open_pr(
+    repo: &GitRepo,
+    config: &Config,
+    branch: &str,
+    pr_number: Option<u64>,
+) -> Result<()> {
+    let remote_info = RemoteInfo::from_repo(repo, config)?;
+    let rt = tokio::runtime::Runtime::new()?;
+    let client = rt.block_on(async { remote_info.provider() })?;
+
+    let open_pr = match pr_number {
+        Some(number) => rt
+            .block_on(async { client.pr_state(number).await })
+            .ok()
+            .filter(|state| state.eq_ignore_ascii_case("open"))
+            .map(|_| number),
+        None => rt
+            .block_on(async { client.find_pr(branch).await })?
+            .map(|pr| pr.number),
+    };
+
+    match open_pr {
+        Some(number) => {
+            rt.block_on(async { client.close_pr(number).await })?;
+            println!("Closed PR {}", format!("#{}", number).cyan());
+        }
+        None => println!("{}", "No open PR to close".dimmed()),
+    }
+
+    Ok(())
+}
+
+/// Delete the branch on the remote if it exists there
+fn delete_remote_branch(repo: &GitRepo, remote_name: &str, branch: &str) -> Result<()> {
+    let workdir = repo.workdir()?;
+    let remote_branches = remote::get_remote_branches(workdir, remote_name).unwrap_or_default();
+    if !remote_branches.iter().any(|b| b == branch) {
+        println!(
+            "{}",
+            format!("No remote branch '{}/{}' to delete", remote_name, branch).dimmed()
+        );
+        return Ok(());
+    }
+
+    let status = Command::new("git")
+        .args(["push", remote_name, "--delete", branch])
+        .current_dir(workdir)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Failed to delete remote branch '{}/{}'",
+            remote_name,
+            branch
+        );
+    }
+
+    println!(
+        "Deleted remote branch '{}'",
+        format!("{}/{}", remote_name, branch).red()
+    );
     Ok(())
 }
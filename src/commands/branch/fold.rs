@@ -1,12 +1,20 @@
+use super::squash::commit_with_message;
 use crate::engine::{BranchMetadata, Stack};
-use crate::git::GitRepo;
-use anyhow::{Context, Result};
+use crate::git::{GitRepo, RebaseOptions, RebaseResult};
+use crate::ops::receipt::{FoldResume, OpKind, PlanSummary, ResumeState};
+use crate::ops::tx::Transaction;
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::collections::HashMap;
 use std::process::Command;
 
-/// Fold the current branch into its parent (merge commits into parent)
-pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
+/// Fold the current branch into its parent (merge commits into parent).
+/// Unless the branch is kept, its children move onto the parent.
+///
+/// `edit` is `Some(true)` for `--edit`, `Some(false)` for `--no-edit`; by
+/// default the editor only opens after conflicts were resolved.
+pub fn run(keep_branch: bool, skip_confirm: bool, force: bool, edit: Option<bool>) -> Result<()> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
     let stack = Stack::load(&repo)?;
@@ -27,16 +35,29 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
         return Ok(());
     }
 
-    // Check if current branch has children
-    if let Some(branch_info) = stack.branches.get(&current) {
-        if !branch_info.children.is_empty() {
-            println!("{}", "Cannot fold: this branch has children.".red());
-            println!("Children: {}", branch_info.children.join(", ").cyan());
-            println!();
-            println!("Please fold or delete child branches first.");
-            return Ok(());
+    let info = stack.branches.get(&current);
+    if let Some(pr) = info
+        .filter(|b| {
+            b.pr_state
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case("open"))
+        })
+        .and_then(|b| b.pr_number)
+    {
+        if !force {
+            bail!(
+                "'{}' has open PR #{}. Merge or close it first, or pass --force to fold anyway.",
+                current,
+                pr
+            );
         }
     }
+    let children = info.map(|b| b.children.clone()).unwrap_or_default();
+    let descendants = if keep_branch {
+        Vec::new()
+    } else {
+        stack.descendants(&current)
+    };
 
     // Count commits to fold
     let output = Command::new("git")
@@ -75,6 +96,14 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
         println!("  {}", line.dimmed());
     }
     println!();
+    if !keep_branch && !children.is_empty() {
+        println!(
+            "Children moving onto '{}': {}",
+            parent.green(),
+            children.join(", ").cyan()
+        );
+        println!();
+    }
 
     // Confirm (unless --yes flag)
     let action = if keep_branch {
@@ -94,6 +123,17 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
         }
     }
 
+    let mut tx = Transaction::begin(OpKind::Fold, &repo, true)?;
+    let mut planned = vec![current.clone(), parent.clone()];
+    planned.extend(descendants.iter().cloned());
+    tx.plan_branches(&repo, &planned)?;
+    tx.set_plan_summary(PlanSummary {
+        branches_to_rebase: descendants.len(),
+        branches_to_push: 0,
+        description: vec![format!("Fold '{}' into '{}'", current, parent)],
+    });
+    tx.snapshot()?;
+
     // Checkout parent
     print!("Checking out {}... ", parent.cyan());
     let checkout_status = Command::new("git")
@@ -120,9 +160,38 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
         .status()
         .context("Failed to merge")?;
 
+    let mut message = format!("Fold {} into {}", current, parent);
+    if edit == Some(true) {
+        let subjects = String::from_utf8_lossy(&log_output.stdout)
+            .lines()
+            .map(|line| format!("* {}", line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        message = format!("{}\n\n{}", message, subjects);
+    }
+    let pending = FoldResume {
+        branch: current.clone(),
+        parent: parent.clone(),
+        keep_branch,
+        edit,
+        message,
+    };
+
     if !merge_status.success() {
         println!("{}", "failed".red());
 
+        if !unmerged_paths(&repo)?.is_empty() {
+            tx.pause(ResumeState::Fold(pending), parent)?;
+            println!();
+            println!("{}", "Resolve conflicts, stage them, and run:".yellow());
+            println!("  {}", "stax continue".cyan());
+            println!(
+                "{}",
+                format!("To give up: git reset --hard && git checkout {}", current).dimmed()
+            );
+            return Ok(());
+        }
+
         // Abort merge and reset working tree
         let _ = Command::new("git")
             .args(["merge", "--abort"])
@@ -142,36 +211,81 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
             .status();
 
         anyhow::bail!(
-            "Failed to merge branch. There may be conflicts.\n\
+            "Failed to merge branch.\n\
              Restored to branch '{}'.",
             current
         );
     }
     println!("{}", "done".green());
 
-    // Commit the merge
-    print!("Committing... ");
-    let commit_msg = format!("Fold {} into {}", current, parent);
-    let commit_status = Command::new("git")
-        .args(["commit", "-m", &commit_msg])
-        .current_dir(workdir)
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .status()
-        .context("Failed to commit")?;
+    if let Some(conflict) = finish(&repo, &pending, false)? {
+        tx.finish_err("Rebase conflict", Some("rebase"), Some(&conflict))?;
+        return Ok(());
+    }
+    tx.record_after(&repo, parent)?;
+    for branch in &descendants {
+        tx.record_after(&repo, branch)?;
+    }
+    tx.finish_ok()
+}
 
-    if !commit_status.success() {
+/// Finish a fold paused on conflicts. Returns false while conflicts remain.
+pub fn resume(repo: &GitRepo, pending: &FoldResume) -> Result<bool> {
+    let unmerged = unmerged_paths(repo)?;
+    if !unmerged.is_empty() {
+        println!("{}", "Conflicts still unresolved:".yellow());
+        for path in unmerged {
+            println!("  {}", path.red());
+        }
+        println!();
+        println!(
+            "Resolve and stage them, then run {} again.",
+            "stax continue".cyan()
+        );
+        return Ok(false);
+    }
+
+    println!(
+        "Continuing fold of '{}' into '{}'...",
+        pending.branch.cyan(),
+        pending.parent.green()
+    );
+    finish(repo, pending, true)?;
+    Ok(true)
+}
+
+/// Commit the squash-merged changes and clean up the folded branch.
+/// Returns the child that stopped on a rebase conflict, if any.
+fn finish(repo: &GitRepo, pending: &FoldResume, after_conflict: bool) -> Result<Option<String>> {
+    let workdir = repo.workdir()?;
+    let current = &pending.branch;
+    let parent = &pending.parent;
+
+    // Commit the merge, reviewing the message after conflicts unless --no-edit
+    print!("Committing... ");
+    let edit = pending.edit.unwrap_or(after_conflict);
+    if !commit_with_message(repo, &pending.message, edit)? {
         // Maybe nothing to commit
         println!("{}", "no changes".yellow());
     } else {
         println!("{}", "done".green());
     }
 
+    // Tips from before the fold delimit each child's own commits
+    let stack = Stack::load(repo)?;
+    let descendants = stack.descendants(current);
+    let old_tips: HashMap<String, String> = descendants
+        .iter()
+        .chain(std::iter::once(current))
+        .filter_map(|b| repo.branch_commit(b).ok().map(|oid| (b.clone(), oid)))
+        .collect();
+
     // Delete the old branch unless --keep
-    if !keep_branch {
+    let mut deleted = false;
+    if !pending.keep_branch {
         print!("Deleting {}... ", current.cyan());
         let delete_status = Command::new("git")
-            .args(["branch", "-D", &current])
+            .args(["branch", "-D", current])
             .current_dir(workdir)
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
@@ -180,8 +294,9 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
 
         if delete_status.success() {
             // Also delete metadata
-            let _ = BranchMetadata::delete(repo.inner(), &current);
+            let _ = BranchMetadata::delete(repo.inner(), current);
             println!("{}", "done".green());
+            deleted = true;
         } else {
             println!("{}", "failed".yellow());
         }
@@ -189,7 +304,7 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
 
     // Update parent's metadata (it may need restack now)
     if let Some(parent_meta) = BranchMetadata::read(repo.inner(), parent)? {
-        let parent_commit = repo.branch_commit(&parent_meta.parent_branch_name)?;
+        let parent_commit = repo.parent_commit(&parent_meta.parent_branch_name)?;
         let updated_parent = BranchMetadata {
             parent_branch_revision: parent_commit,
             ..parent_meta
@@ -197,8 +312,88 @@ pub fn run(keep_branch: bool, skip_confirm: bool) -> Result<()> {
         updated_parent.write(repo.inner(), parent)?;
     }
 
+    let conflict = if deleted {
+        restack_children(repo, &stack, &descendants, &old_tips, current, parent)?
+    } else {
+        None
+    };
+    if conflict.is_some() {
+        return Ok(conflict);
+    }
+
     println!();
     println!("{} Folded '{}' into '{}'", "✓".green(), current, parent);
 
-    Ok(())
+    Ok(None)
+}
+
+/// Rebase the folded branch's descendants parent-first, moving its children onto
+/// `parent` and replaying only each branch's own commits
+fn restack_children(
+    repo: &GitRepo,
+    stack: &Stack,
+    descendants: &[String],
+    old_tips: &HashMap<String, String>,
+    folded: &str,
+    parent: &str,
+) -> Result<Option<String>> {
+    for branch in descendants {
+        let Some(old_parent) = stack.branches.get(branch).and_then(|b| b.parent.clone()) else {
+            continue;
+        };
+        let new_parent = if old_parent == folded {
+            parent.to_string()
+        } else {
+            old_parent.clone()
+        };
+        let upstream = old_tips.get(&old_parent).map(String::as_str);
+
+        println!("  {} onto {}", branch.white(), new_parent.blue());
+        let result = repo.rebase_branch_onto_from(
+            branch,
+            &new_parent,
+            upstream,
+            false,
+            &RebaseOptions::default(),
+        )?;
+
+        // Point metadata at the new parent either way so `stax continue` finishes the branch
+        let new_parent_rev = repo.branch_commit(&new_parent)?;
+        let updated = match BranchMetadata::read(repo.inner(), branch)? {
+            Some(meta) => BranchMetadata {
+                parent_branch_name: new_parent.clone(),
+                parent_branch_revision: new_parent_rev,
+                ..meta
+            },
+            None => BranchMetadata::new(&new_parent, &new_parent_rev),
+        };
+        updated.write(repo.inner(), branch)?;
+
+        if result == RebaseResult::Conflict {
+            println!("    {}", "✗ conflict".red());
+            println!();
+            println!("{}", "Resolve conflicts and run:".yellow());
+            println!("  {}", "stax continue".cyan());
+            return Ok(Some(branch.clone()));
+        }
+        println!("    {}", "✓ done".green());
+    }
+
+    if !descendants.is_empty() {
+        repo.checkout(parent)?;
+    }
+    Ok(None)
+}
+
+/// Paths left unmerged by a conflicted merge
+fn unmerged_paths(repo: &GitRepo) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .current_dir(repo.workdir()?)
+        .output()
+        .context("Failed to list unmerged paths")?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
 }
@@ -1,6 +1,7 @@
 pub mod create;
 pub mod delete;
 pub mod fold;
+pub mod move_cmd;
 pub mod rename;
 pub mod reparent;
 pub mod squash;
@@ -0,0 +1,305 @@
+use super::reparent;
+use crate::engine::Stack;
+use crate::git::{GitRepo, RebaseResult};
+use crate::ops::receipt::{OpKind, PlanSummary};
+use crate::ops::tx::{self, Transaction};
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoveTarget {
+    Top,
+    Bottom,
+}
+
+impl MoveTarget {
+    fn label(self) -> &'static str {
+        match self {
+            MoveTarget::Top => "top",
+            MoveTarget::Bottom => "bottom",
+        }
+    }
+}
+
+/// Move a branch to the top or bottom of its stack
+pub fn run(branch: Option<String>, to_top: bool, to_bottom: bool) -> Result<()> {
+    let target = match (to_top, to_bottom) {
+        (true, false) => MoveTarget::Top,
+        (false, true) => MoveTarget::Bottom,
+        _ => bail!("Specify where to move the branch: --to-top or --to-bottom"),
+    };
+
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+    let branch = branch.unwrap_or_else(|| current.clone());
+
+    if branch == stack.trunk {
+        bail!("Cannot move trunk branch '{}'", branch);
+    }
+    if !stack.branches.contains_key(&branch) {
+        bail!(
+            "Branch '{}' is not tracked. Run `stax branch track` first.",
+            branch
+        );
+    }
+
+    let reparents = plan_move(&stack, &branch, target)?;
+    if reparents.is_empty() {
+        println!(
+            "'{}' is already at the {} of its stack.",
+            branch.cyan(),
+            target.label()
+        );
+        return Ok(());
+    }
+
+    if repo.is_dirty()? {
+        bail!("Working tree is dirty. Please stash or commit changes first.");
+    }
+
+    let order = rebase_order(&stack, &reparents);
+
+    // Old tips delimit each branch's own commits once parents start moving
+    let old_tips: HashMap<String, String> = stack
+        .branches
+        .keys()
+        .filter_map(|b| repo.branch_commit(b).ok().map(|oid| (b.clone(), oid)))
+        .collect();
+
+    let mut tx = Transaction::begin(OpKind::Reorder, &repo, false)?;
+    tx.plan_branches(&repo, &order)?;
+    let summary = PlanSummary {
+        branches_to_rebase: order.len(),
+        branches_to_push: 0,
+        description: vec![format!(
+            "Move '{}' to the {} of its stack",
+            branch,
+            target.label()
+        )],
+    };
+    tx::print_plan(tx.kind(), &summary, false);
+    tx.set_plan_summary(summary);
+    tx.snapshot()?;
+
+    for b in &order {
+        let old_parent = match stack.branches.get(b).and_then(|br| br.parent.clone()) {
+            Some(p) => p,
+            None => continue,
+        };
+        let new_parent = reparents.get(b).cloned().unwrap_or(old_parent.clone());
+        let upstream = old_tips.get(&old_parent).map(String::as_str);
+
+        println!("  {} onto {}", b.white(), new_parent.blue());
+
+        match reparent::rebase_onto_new_parent(&repo, b, &new_parent, upstream)? {
+            RebaseResult::Success => {
+                tx.record_after(&repo, b)?;
+                println!("    {}", "✓ done".green());
+            }
+            RebaseResult::Conflict => {
+                println!("    {}", "✗ conflict".red());
+                println!();
+                println!("{}", "Resolve conflicts and run:".yellow());
+                println!("  {}", "stax continue".cyan());
+                tx.finish_err("Rebase conflict", Some("rebase"), Some(b))?;
+                return Ok(());
+            }
+        }
+    }
+
+    repo.checkout(&current)?;
+    tx.finish_ok()?;
+
+    println!(
+        "✓ Moved '{}' to the {} of its stack",
+        branch.green(),
+        target.label()
+    );
+
+    Ok(())
+}
+
+/// Compute the new parent for every branch whose parent changes. Empty means no-op.
+fn plan_move(stack: &Stack, branch: &str, target: MoveTarget) -> Result<HashMap<String, String>> {
+    let info = &stack.branches[branch];
+    let parent = info
+        .parent
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Branch '{}' has no parent", branch))?;
+    let mut reparents = HashMap::new();
+
+    match target {
+        MoveTarget::Bottom => {
+            if parent == stack.trunk {
+                return Ok(reparents);
+            }
+
+            // The current bottom is the ancestor sitting directly on trunk
+            let bottom = stack
+                .ancestors(branch)
+                .into_iter()
+                .find(|a| {
+                    stack.branches.get(a).and_then(|b| b.parent.as_deref())
+                        == Some(stack.trunk.as_str())
+                })
+                .ok_or_else(|| anyhow::anyhow!("Could not find the bottom of '{}'", branch))?;
+
+            for child in &info.children {
+                reparents.insert(child.clone(), parent.clone());
+            }
+            reparents.insert(branch.to_string(), stack.trunk.clone());
+            reparents.insert(bottom, branch.to_string());
+        }
+        MoveTarget::Top => {
+            if info.children.is_empty() {
+                return Ok(reparents);
+            }
+            if info.children.len() > 1 {
+                bail!(
+                    "'{}' has multiple children; the top of its stack is ambiguous.",
+                    branch
+                );
+            }
+
+            let child = info.children[0].clone();
+            let mut tip = child.clone();
+            loop {
+                let children = &stack.branches[&tip].children;
+                match children.len() {
+                    0 => break,
+                    1 => tip = children[0].clone(),
+                    _ => bail!(
+                        "'{}' has multiple children; the top of the stack is ambiguous.",
+                        tip
+                    ),
+                }
+            }
+
+            reparents.insert(child, parent);
+            reparents.insert(branch.to_string(), tip);
+        }
+    }
+
+    Ok(reparents)
+}
+
+/// Parent-first order of every branch that must be rebased: the reparented
+/// branches plus everything stacked above them in the new topology.
+fn rebase_order(stack: &Stack, reparents: &HashMap<String, String>) -> Vec<String> {
+    let mut children: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, info) in &stack.branches {
+        let parent = reparents.get(name).or(info.parent.as_ref());
+        if let Some(parent) = parent {
+            children
+                .entry(parent.clone())
+                .or_default()
+                .push(name.clone());
+        }
+    }
+    for kids in children.values_mut() {
+        kids.sort();
+    }
+
+    let mut order = Vec::new();
+    let mut affected: HashSet<String> = HashSet::new();
+    let mut to_visit = vec![stack.trunk.clone()];
+    while let Some(name) = to_visit.pop() {
+        let parent_affected = reparents
+            .get(&name)
+            .or(stack.branches.get(&name).and_then(|b| b.parent.as_ref()))
+            .is_some_and(|p| affected.contains(p));
+        if reparents.contains_key(&name) || parent_affected {
+            affected.insert(name.clone());
+            order.push(name.clone());
+        }
+        if let Some(kids) = children.get(&name) {
+            to_visit.extend(kids.iter().rev().cloned());
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::stack::StackBranch;
+
+    fn linear_stack(names: &[&str]) -> Stack {
+        let mut branches = HashMap::new();
+        let mut parent = "main".to_string();
+        let mut all = vec!["main"];
+        all.extend_from_slice(names);
+        for (i, name) in all.iter().enumerate() {
+            let children = all
+                .get(i + 1)
+                .map(|c| vec![c.to_string()])
+                .unwrap_or_default();
+            branches.insert(
+                name.to_string(),
+                StackBranch {
+                    name: name.to_string(),
+                    parent: if i == 0 { None } else { Some(parent.clone()) },
+                    children,
+                    needs_restack: false,
+                    pr_number: None,
+                    pr_state: None,
+                    pr_is_draft: None,
+                },
+            );
+            parent = name.to_string();
+        }
+        Stack {
+            branches,
+            trunk: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_plan_move_to_bottom() {
+        let stack = linear_stack(&["a", "b", "c", "d"]);
+        let plan = plan_move(&stack, "c", MoveTarget::Bottom).unwrap();
+        assert_eq!(plan.get("c").map(String::as_str), Some("main"));
+        assert_eq!(plan.get("a").map(String::as_str), Some("c"));
+        assert_eq!(plan.get("d").map(String::as_str), Some("b"));
+        assert_eq!(plan.len(), 3);
+
+        let order = rebase_order(&stack, &plan);
+        assert_eq!(order, vec!["c", "a", "b", "d"]);
+    }
+
+    #[test]
+    fn test_plan_move_to_top() {
+        let stack = linear_stack(&["a", "b", "c", "d"]);
+        let plan = plan_move(&stack, "b", MoveTarget::Top).unwrap();
+        assert_eq!(plan.get("c").map(String::as_str), Some("a"));
+        assert_eq!(plan.get("b").map(String::as_str), Some("d"));
+        assert_eq!(plan.len(), 2);
+
+        let order = rebase_order(&stack, &plan);
+        assert_eq!(order, vec!["c", "d", "b"]);
+    }
+
+    #[test]
+    fn test_plan_move_noop_at_ends() {
+        let stack = linear_stack(&["a", "b"]);
+        assert!(plan_move(&stack, "a", MoveTarget::Bottom)
+            .unwrap()
+            .is_empty());
+        assert!(plan_move(&stack, "b", MoveTarget::Top).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plan_move_to_top_rejects_branching_stack() {
+        let mut stack = linear_stack(&["a", "b"]);
+        stack
+            .branches
+            .get_mut("a")
+            .unwrap()
+            .children
+            .push("x".to_string());
+        assert!(plan_move(&stack, "a", MoveTarget::Top).is_err());
+    }
+}
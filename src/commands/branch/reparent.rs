@@ -1,5 +1,5 @@
 use crate::engine::{BranchMetadata, Stack};
-use crate::git::GitRepo;
+use crate::git::{GitRepo, RebaseOptions, RebaseResult};
 use crate::remote;
 use anyhow::Result;
 use colored::Colorize;
@@ -87,18 +87,7 @@ pub fn run(branch: Option<String>, parent: Option<String>) -> Result<()> {
         .merge_base(&parent_branch, &target)
         .unwrap_or(parent_rev.clone());
 
-    let existing = BranchMetadata::read(repo.inner(), &target)?;
-    let updated = if let Some(meta) = existing {
-        BranchMetadata {
-            parent_branch_name: parent_branch.clone(),
-            parent_branch_revision: merge_base.clone(),
-            ..meta
-        }
-    } else {
-        BranchMetadata::new(&parent_branch, &merge_base)
-    };
-
-    updated.write(repo.inner(), &target)?;
+    write_parent(&repo, &target, &parent_branch, &merge_base)?;
 
     let config = crate::config::Config::load()?;
     if let Ok(remote_branches) = remote::get_remote_branches(repo.workdir()?, config.remote_name())
@@ -131,3 +120,38 @@ pub fn run(branch: Option<String>, parent: Option<String>) -> Result<()> {
 
     Ok(())
 }
+
+/// Rebase `branch` onto `new_parent`, replaying only its commits after `upstream`
+/// (its old parent's tip), and reparent it there. Metadata points at the new
+/// parent even on conflict so `stax continue` finishes the branch.
+pub(crate) fn rebase_onto_new_parent(
+    repo: &GitRepo,
+    branch: &str,
+    new_parent: &str,
+    upstream: Option<&str>,
+) -> Result<RebaseResult> {
+    let result = repo.rebase_branch_onto_from(
+        branch,
+        new_parent,
+        upstream,
+        false,
+        &RebaseOptions::default(),
+    )?;
+    let new_parent_rev = repo.branch_commit(new_parent)?;
+    write_parent(repo, branch, new_parent, &new_parent_rev)?;
+    Ok(result)
+}
+
+/// Record `parent` at `parent_rev` as the parent of `branch`, keeping the rest of
+/// its metadata
+fn write_parent(repo: &GitRepo, branch: &str, parent: &str, parent_rev: &str) -> Result<()> {
+    let updated = match BranchMetadata::read(repo.inner(), branch)? {
+        Some(meta) => BranchMetadata {
+            parent_branch_name: parent.to_string(),
+            parent_branch_revision: parent_rev.to_string(),
+            ..meta
+        },
+        None => BranchMetadata::new(parent, parent_rev),
+    };
+    updated.write(repo.inner(), branch)
+}
@@ -1,12 +1,16 @@
 use crate::engine::BranchMetadata;
-use crate::git::GitRepo;
+use crate::git::{GitRepo, RebaseOptions, RebaseResult};
+use crate::ops::receipt::{OpKind, PlanSummary, ResumeState, SquashResume};
+use crate::ops::tx::Transaction;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input};
-use std::process::Command;
+use std::process::{Command, Stdio};
 
-/// Squash all commits on the current branch into a single commit
-pub fn run(message: Option<String>, skip_confirm: bool) -> Result<()> {
+/// Squash all commits on the current branch into a single commit.
+///
+/// `edit` is `Some(true)` for `--edit`, `Some(false)` for `--no-edit`.
+pub fn run(message: Option<String>, skip_confirm: bool, edit: Option<bool>) -> Result<()> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
     let workdir = repo.workdir()?;
@@ -66,7 +70,23 @@ pub fn run(message: Option<String>, skip_confirm: bool) -> Result<()> {
     // Get commit message
     let squash_message = if let Some(msg) = message {
         msg
-    } else if skip_confirm {
+    } else if edit == Some(true) {
+        // Seed the editor with every commit message, oldest first
+        let log_output = Command::new("git")
+            .args([
+                "log",
+                "--reverse",
+                "--format=%B",
+                &format!("{}..HEAD", parent),
+            ])
+            .current_dir(workdir)
+            .output()
+            .context("Failed to get commit messages")?;
+
+        String::from_utf8_lossy(&log_output.stdout)
+            .trim()
+            .to_string()
+    } else if skip_confirm || edit == Some(false) {
         // In non-interactive mode, use first commit's message as default
         let first_msg_output = Command::new("git")
             .args(["log", "-1", "--format=%s", &format!("{}..HEAD", parent)])
@@ -108,11 +128,27 @@ pub fn run(message: Option<String>, skip_confirm: bool) -> Result<()> {
         }
     }
 
-    // Perform soft reset to parent
+    // Squash onto the fork point: if the parent moved on, resetting to its tip
+    // would revert its new commits, so the squashed commit is rebased instead
+    let parent_tip = repo.branch_commit(parent)?;
+    let base = repo
+        .merge_base(parent, &current)
+        .unwrap_or_else(|_| parent_tip.clone());
+
+    let mut tx = Transaction::begin(OpKind::Squash, &repo, true)?;
+    tx.plan_branch(&repo, &current)?;
+    tx.set_plan_summary(PlanSummary {
+        branches_to_rebase: usize::from(base != parent_tip),
+        branches_to_push: 0,
+        description: vec![format!("Squash {} commits on '{}'", commit_count, current)],
+    });
+    tx.snapshot()?;
+
+    // Perform soft reset to the fork point
     print!("Squashing commits... ");
 
     let reset_status = Command::new("git")
-        .args(["reset", "--soft", parent])
+        .args(["reset", "--soft", &base])
         .current_dir(workdir)
         .status()
         .context("Failed to reset")?;
@@ -123,35 +159,92 @@ pub fn run(message: Option<String>, skip_confirm: bool) -> Result<()> {
     }
 
     // Create new squashed commit
-    let commit_status = Command::new("git")
-        .args(["commit", "-m", &squash_message])
-        .current_dir(workdir)
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .status()
-        .context("Failed to commit")?;
-
-    if !commit_status.success() {
+    if !commit_with_message(&repo, &squash_message, edit == Some(true))? {
         println!("{}", "failed".red());
         anyhow::bail!("Failed to create squashed commit");
     }
 
     println!("{}", "done".green());
 
-    // Update metadata with new parent revision
-    let parent_commit = repo.branch_commit(parent)?;
-    let updated_meta = BranchMetadata {
-        parent_branch_revision: parent_commit,
-        ..meta
+    let pending = SquashResume {
+        branch: current.clone(),
+        parent: parent.clone(),
+        commit_count,
     };
-    updated_meta.write(repo.inner(), &current)?;
+
+    if base != parent_tip {
+        print!("Rebasing onto {}... ", parent.cyan());
+        let result =
+            repo.rebase_branch_onto_with(&current, parent, false, &RebaseOptions::default())?;
+        if result == RebaseResult::Conflict {
+            println!("{}", "conflict".red());
+            println!();
+            println!("{}", "Resolve conflicts, stage them, and run:".yellow());
+            println!("  {}", "stax continue".cyan());
+            tx.pause(ResumeState::Squash(pending), &current)?;
+            return Ok(());
+        }
+        println!("{}", "done".green());
+    }
+
+    finish(&repo, &pending)?;
+    tx.record_after(&repo, &current)?;
+    tx.finish_ok()
+}
+
+/// Finish a squash whose rebase stopped on conflicts. Returns false while the rebase is still going.
+pub fn resume(repo: &GitRepo, pending: &SquashResume) -> Result<bool> {
+    if repo.rebase_in_progress()? {
+        return Ok(false);
+    }
+    finish(repo, pending)?;
+    Ok(true)
+}
+
+/// Point the branch's metadata at its parent's tip and report
+fn finish(repo: &GitRepo, pending: &SquashResume) -> Result<()> {
+    if let Some(meta) = BranchMetadata::read(repo.inner(), &pending.branch)? {
+        let updated_meta = BranchMetadata {
+            parent_branch_revision: repo.branch_commit(&pending.parent)?,
+            ..meta
+        };
+        updated_meta.write(repo.inner(), &pending.branch)?;
+    }
 
     println!();
     println!(
         "{} Squashed {} commits into one.",
         "✓".green(),
-        commit_count
+        pending.commit_count
     );
 
     Ok(())
 }
+
+/// Commit the staged changes with `message`, opening git's editor on it when
+/// `edit` is set. The editor is resolved by git itself, so `GIT_EDITOR`,
+/// `core.editor`, `VISUAL` and `EDITOR` are honored in that order.
+pub(crate) fn commit_with_message(repo: &GitRepo, message: &str, edit: bool) -> Result<bool> {
+    let workdir = repo.workdir()?;
+    if !edit {
+        let status = Command::new("git")
+            .args(["commit", "-m", message])
+            .current_dir(workdir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .context("Failed to commit")?;
+        return Ok(status.success());
+    }
+
+    let msg_path = repo.git_dir()?.join("STAX_EDITMSG");
+    std::fs::write(&msg_path, format!("{}\n", message))?;
+    let status = Command::new("git")
+        .args(["commit", "--edit", "-F"])
+        .arg(&msg_path)
+        .current_dir(workdir)
+        .status()
+        .context("Failed to commit")?;
+    let _ = std::fs::remove_file(&msg_path);
+    Ok(status.success())
+}
@@ -6,12 +6,54 @@ use crate::remote::{self, RemoteInfo};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Select};
+use serde::Serialize;
 use std::process::Command;
 
-pub fn run(parent: Option<String>, all_prs: bool) -> Result<()> {
+/// Structured result of `branch track --json` and `branch untrack --json`
+#[derive(Serialize)]
+pub(crate) struct TrackJson {
+    pub branch: String,
+    pub parent: Option<String>,
+    pub parent_revision: Option<String>,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Print a track result as JSON; failures become a `"status": "error"` object and still fail
+pub(crate) fn print_json(branch: &str, result: Result<TrackJson>) -> Result<()> {
+    let (output, err) = match result {
+        Ok(output) => (output, None),
+        Err(e) => (
+            TrackJson {
+                branch: branch.to_string(),
+                parent: None,
+                parent_revision: None,
+                status: "error",
+                error: Some(e.to_string()),
+            },
+            Some(e),
+        ),
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    err.map_or(Ok(()), Err)
+}
+
+pub fn run(parent: Option<String>, all_prs: bool, force: bool, json: bool) -> Result<()> {
     if all_prs {
         return run_track_all_prs();
     }
+    if json {
+        let branch = GitRepo::open()
+            .and_then(|repo| repo.current_branch())
+            .unwrap_or_default();
+        return print_json(&branch, track_current(parent, force, true));
+    }
+    track_current(parent, force, false).map(|_| ())
+}
+
+/// Track the current branch; `json` silences the human-readable output
+fn track_current(parent: Option<String>, force: bool, json: bool) -> Result<TrackJson> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
     let config = Config::load()?;
@@ -19,39 +61,118 @@ pub fn run(parent: Option<String>, all_prs: bool) -> Result<()> {
 
     // Can't track trunk
     if current == trunk {
-        println!(
-            "{} is the trunk branch and cannot be tracked.",
-            current.yellow()
-        );
-        return Ok(());
+        if !json {
+            println!(
+                "{} is the trunk branch and cannot be tracked.",
+                current.yellow()
+            );
+        }
+        return Ok(TrackJson {
+            branch: current,
+            parent: None,
+            parent_revision: None,
+            status: "trunk",
+            error: None,
+        });
     }
 
     // Check if already tracked
-    if let Some(existing) = BranchMetadata::read(repo.inner(), &current)? {
-        println!(
-            "Branch '{}' is already tracked with parent '{}'.",
-            current.yellow(),
-            existing.parent_branch_name.blue()
-        );
-        println!("Use {} to update.", "stax branch reparent".cyan());
-        return Ok(());
+    let existing = BranchMetadata::read(repo.inner(), &current)?;
+    if let Some(existing) = &existing {
+        if !force {
+            if !json {
+                println!(
+                    "Branch '{}' is already tracked with parent '{}'.",
+                    current.yellow(),
+                    existing.parent_branch_name.blue()
+                );
+                println!(
+                    "Use {} to update, or {} to overwrite.",
+                    "stax branch reparent".cyan(),
+                    "--force".cyan()
+                );
+            }
+            return Ok(TrackJson {
+                branch: current,
+                parent: Some(existing.parent_branch_name.clone()),
+                parent_revision: Some(existing.parent_branch_revision.clone()),
+                status: "already_tracked",
+                error: None,
+            });
+        }
     }
 
+    // Teams that record parentage in the branch description get it as the default
+    let parent = parent.or_else(|| {
+        let key = config.branch.parent_description_key.as_deref()?;
+        let description = repo
+            .inner()
+            .config()
+            .ok()?
+            .get_string(&format!("branch.{}.description", current))
+            .ok()?;
+        let described = parent_from_description(&description, key)?;
+        if repo.parent_commit(&described).is_ok() {
+            if !json {
+                println!(
+                    "{}",
+                    format!("Using parent '{}' from the branch description.", described).dimmed()
+                );
+            }
+            Some(described)
+        } else {
+            if !json {
+                println!(
+                    "{}",
+                    format!(
+                        "Branch description names parent '{}', which does not exist; pick one instead.",
+                        described
+                    )
+                    .yellow()
+                );
+            }
+            None
+        }
+    });
+
     // Determine parent
     let parent_branch = match parent {
         Some(p) => {
-            // Validate the branch exists
-            if repo.branch_commit(&p).is_err() {
-                anyhow::bail!("Branch '{}' does not exist", p);
+            // Validate the branch exists, locally or as a remote-tracking branch
+            if repo.parent_commit(&p).is_err() {
+                anyhow::bail!(
+                    "Branch '{}' does not exist. For a branch you have not checked out, \
+                     fetch it and pass '{}/<branch>'.",
+                    p,
+                    config.remote_name()
+                );
             }
             p
         }
+        None if json => {
+            anyhow::bail!("No parent given. Pass --parent when using --json.");
+        }
         None => {
             // Build list of potential parents
             let mut branches = repo.list_branches()?;
             branches.retain(|b| b != &current);
             branches.sort();
 
+            // Remote-only branches can be parents too, tracked by their remote-tracking name
+            if let Ok(remote_branches) =
+                remote::get_remote_branches(repo.workdir()?, config.remote_name())
+            {
+                let mut remote_only: Vec<String> = remote_branches
+                    .into_iter()
+                    .filter(|b| b != "HEAD" && b != config.remote_name())
+                    .filter(|b| !branches.contains(b) && b != &current)
+                    .map(|b| format!("{}/{}", config.remote_name(), b))
+                    .filter(|b| repo.is_remote_only_branch(b))
+                    .collect();
+                remote_only.sort();
+                branches.extend(remote_only);
+            }
+
             // Put trunk first as the recommended default
             if let Some(pos) = branches.iter().position(|b| b == &trunk) {
                 branches.remove(pos);
@@ -85,34 +206,74 @@ pub fn run(parent: Option<String>, all_prs: bool) -> Result<()> {
         }
     };
 
-    let parent_rev = repo.branch_commit(&parent_branch)?;
+    let parent_rev = repo.parent_commit(&parent_branch)?;
+
+    if let Some(existing) = existing.as_ref().filter(|_| !json) {
+        println!(
+            "{}",
+            format!(
+                "Warning: overwriting recorded parent '{}' with '{}'.",
+                existing.parent_branch_name, parent_branch
+            )
+            .yellow()
+        );
+        println!(
+            "{}",
+            "Note: no commits are rebased; run `stax restack` to move them.".yellow()
+        );
+    }
 
     // Create metadata
-    let meta = BranchMetadata::new(&parent_branch, &parent_rev);
+    let meta = BranchMetadata {
+        fork_point: repo.merge_base(&parent_branch, &current).ok(),
+        ..BranchMetadata::new(&parent_branch, &parent_rev)
+    };
     meta.write(repo.inner(), &current)?;
 
-    if let Ok(remote_branches) = remote::get_remote_branches(repo.workdir()?, config.remote_name())
-    {
-        if !remote_branches.contains(&parent_branch) {
+    if !json {
+        if repo.is_remote_only_branch(&parent_branch) {
             println!(
                 "{}",
                 format!(
-                    "Warning: parent '{}' is not on remote '{}'.",
-                    parent_branch,
-                    config.remote_name()
+                    "Note: '{}' is a remote-tracking branch; restack rebases onto it as of your last fetch.",
+                    parent_branch
                 )
-                .yellow()
+                .dimmed()
             );
+        } else if let Ok(remote_branches) =
+            remote::get_remote_branches(repo.workdir()?, config.remote_name())
+        {
+            if !remote_branches.contains(&parent_branch) {
+                println!(
+                    "{}",
+                    format!(
+                        "Warning: parent '{}' is not on remote '{}'.",
+                        parent_branch,
+                        config.remote_name()
+                    )
+                    .yellow()
+                );
+            }
         }
-    }
 
-    println!(
-        "✓ Tracking '{}' with parent '{}'",
-        current.green(),
-        parent_branch.blue()
-    );
+        println!(
+            "✓ Tracking '{}' with parent '{}'",
+            current.green(),
+            parent_branch.blue()
+        );
+    }
 
-    Ok(())
+    Ok(TrackJson {
+        branch: current,
+        parent: Some(parent_branch),
+        parent_revision: Some(parent_rev),
+        status: if existing.is_some() {
+            "retracked"
+        } else {
+            "tracked"
+        },
+        error: None,
+    })
 }
 
 /// Track all open PRs authored by the current user
@@ -232,6 +393,9 @@ fn run_track_all_prs() -> Result<()> {
                 state: pr.state.to_uppercase(),
                 is_draft: Some(pr.is_draft),
             }),
+            last_submitted_revision: None,
+            fork_point: repo.merge_base(&parent_branch, &pr.head_branch).ok(),
+            signature: None,
         };
 
         meta.write(repo.inner(), &pr.head_branch)?;
@@ -279,3 +443,46 @@ fn fetch_branch_from_remote(workdir: &std::path::Path, remote: &str, branch: &st
 
     Ok(())
 }
+
+/// Find a `<key>: <parent>` line in a branch description
+fn parent_from_description(description: &str, key: &str) -> Option<String> {
+    description.lines().find_map(|line| {
+        let value = line
+            .trim()
+            .strip_prefix(key)?
+            .trim_start()
+            .strip_prefix(':')?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parent_from_description_finds_key_line() {
+        let description = "Adds the login page\n\nstax-parent: feature/auth\n";
+        assert_eq!(
+            parent_from_description(description, "stax-parent"),
+            Some("feature/auth".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parent_from_description_ignores_other_keys_and_blank_values() {
+        assert_eq!(
+            parent_from_description("stax-parents: main", "stax-parent"),
+            None
+        );
+        assert_eq!(
+            parent_from_description("stax-parent:   ", "stax-parent"),
+            None
+        );
+        assert_eq!(
+            parent_from_description("no parent here", "stax-parent"),
+            None
+        );
+    }
+}
@@ -1,22 +1,120 @@
+use super::track::{print_json, TrackJson};
 use crate::engine::BranchMetadata;
-use crate::git::GitRepo;
-use anyhow::Result;
+use crate::git::{refs, GitRepo};
+use anyhow::{bail, Result};
 use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::io::IsTerminal;
 
 /// Remove stax metadata for a branch, but keep the git branch intact.
-pub fn run(branch: Option<String>) -> Result<()> {
+pub fn run(
+    branch: Option<String>,
+    json: bool,
+    all: bool,
+    dry_run: bool,
+    skip_confirm: bool,
+) -> Result<()> {
+    if all {
+        return untrack_all(dry_run, skip_confirm);
+    }
+    if json {
+        let target = match &branch {
+            Some(b) => b.clone(),
+            None => GitRepo::open()
+                .and_then(|repo| repo.current_branch())
+                .unwrap_or_default(),
+        };
+        return print_json(&target, untrack(branch, true));
+    }
+    untrack(branch, false).map(|_| ())
+}
+
+fn untrack(branch: Option<String>, json: bool) -> Result<TrackJson> {
     let repo = GitRepo::open()?;
     let target = branch.unwrap_or(repo.current_branch()?);
 
-    if BranchMetadata::read(repo.inner(), &target)?.is_none() {
-        println!("Branch '{}' is not tracked.", target.yellow());
+    let Some(meta) = BranchMetadata::read(repo.inner(), &target)? else {
+        if !json {
+            println!("Branch '{}' is not tracked.", target.yellow());
+        }
+        return Ok(TrackJson {
+            branch: target,
+            parent: None,
+            parent_revision: None,
+            status: "not_tracked",
+            error: None,
+        });
+    };
+
+    BranchMetadata::delete(repo.inner(), &target)?;
+    if !json {
+        println!(
+            "✓ Untracked '{}' (removed stax metadata, kept git branch)",
+            target.green()
+        );
+    }
+
+    Ok(TrackJson {
+        branch: target,
+        parent: Some(meta.parent_branch_name),
+        parent_revision: Some(meta.parent_branch_revision),
+        status: "untracked",
+        error: None,
+    })
+}
+
+/// Remove every branch's stax metadata, leaving all git branches in place
+fn untrack_all(dry_run: bool, skip_confirm: bool) -> Result<()> {
+    let repo = GitRepo::open()?;
+    let mut branches = refs::list_metadata_branches(repo.inner())?;
+    branches.sort();
+
+    if branches.is_empty() {
+        println!("{}", "No stax metadata to remove.".dimmed());
         return Ok(());
     }
 
-    BranchMetadata::delete(repo.inner(), &target)?;
     println!(
-        "✓ Untracked '{}' (removed stax metadata, kept git branch)",
-        target.green()
+        "{}",
+        if dry_run {
+            "Would remove stax metadata for:"
+        } else {
+            "Removing stax metadata for:"
+        }
+        .bold()
+    );
+    for branch in &branches {
+        println!("  {}", branch);
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    if !skip_confirm {
+        if !std::io::stdin().is_terminal() {
+            bail!("Refusing to untrack every branch without confirmation. Pass --yes to proceed.");
+        }
+        let confirm = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Untrack all {} branches? Git branches are kept.",
+                branches.len()
+            ))
+            .default(false)
+            .interact()?;
+
+        if !confirm {
+            println!("{}", "Aborted.".red());
+            return Ok(());
+        }
+    }
+
+    for branch in &branches {
+        BranchMetadata::delete(repo.inner(), branch)?;
+    }
+    println!(
+        "✓ Untracked {} branches (removed stax metadata, kept git branches)",
+        branches.len().to_string().green()
     );
 
     Ok(())
@@ -1,13 +1,15 @@
 use crate::commands;
 use crate::config::Config;
 use crate::engine::Stack;
-use crate::git::GitRepo;
+use crate::error::StaxError;
+use crate::git::{GitRepo, RebaseOptions};
 use anyhow::Result;
 use colored::Colorize;
 use std::process::Command;
 
 pub fn run(no_pr: bool, no_submit: bool, auto_stash_pop: bool) -> Result<()> {
     let repo = GitRepo::open()?;
+    commands::restack::ensure_no_rebase_in_progress(&repo)?;
     let original = repo.current_branch()?;
 
     println!("{}", "Cascading stack...".bold());
@@ -18,17 +20,21 @@ pub fn run(no_pr: bool, no_submit: bool, auto_stash_pop: bool) -> Result<()> {
     warn_if_trunk_stale(&repo);
 
     commands::navigate::bottom()?;
-    commands::restack::run(false, false, true, auto_stash_pop)?;
+    commands::restack::run(
+        false,
+        false,
+        true,
+        false,
+        1,
+        false,
+        auto_stash_pop,
+        false,
+        RebaseOptions::default(),
+    )?;
+    stop_on_conflict(&repo, &original)?;
 
-    if repo.rebase_in_progress()? {
-        return Ok(());
-    }
-
-    commands::upstack::restack::run(auto_stash_pop)?;
-
-    if repo.rebase_in_progress()? {
-        return Ok(());
-    }
+    commands::upstack::restack::run(auto_stash_pop, false)?;
+    stop_on_conflict(&repo, &original)?;
 
     if no_submit {
         println!("{}", "Skipping push and PRs (--no-submit)".dimmed());
@@ -37,6 +43,7 @@ pub fn run(no_pr: bool, no_submit: bool, auto_stash_pop: bool) -> Result<()> {
             commands::submit::SubmitScope::Stack,
             false,  // draft
             no_pr,  // no_pr (push but skip PR creation/updates)
+            false,  // no_push
             false,  // force
             true,   // yes
             true,   // no_prompt
@@ -49,6 +56,13 @@ pub fn run(no_pr: bool, no_submit: bool, auto_stash_pop: bool) -> Result<()> {
             false,  // no_template
             false,  // edit
             false,  // ai_body
+            false,  // no_verify
+            false,  // restart
+            false,  // assign_me
+            false,  // comment_only
+            None,   // template_dir
+            false,  // no_track_pr
+            false,  // auto_merge
         )?;
     }
 
@@ -72,7 +86,11 @@ fn warn_if_trunk_stale(repo: &GitRepo) {
     // Count commits on remote that aren't in local trunk.
     // git rev-list --count <local>..<remote> — uses only local git objects.
     let output = Command::new("git")
-        .args(["rev-list", "--count", &format!("{}..{}", stack.trunk, remote_ref)])
+        .args([
+            "rev-list",
+            "--count",
+            &format!("{}..{}", stack.trunk, remote_ref),
+        ])
         .current_dir(workdir)
         .output();
 
@@ -95,3 +113,14 @@ fn warn_if_trunk_stale(repo: &GitRepo) {
         // If rev-list fails (e.g. remote ref doesn't exist yet), silently skip.
     }
 }
+
+/// Stop the cascade (before anything is pushed) if a restack left a rebase paused on conflicts
+fn stop_on_conflict(repo: &GitRepo, fallback: &str) -> Result<()> {
+    if repo.rebase_in_progress()? {
+        let branch = repo
+            .rebasing_branch()
+            .unwrap_or_else(|| fallback.to_string());
+        return Err(StaxError::RebaseConflict(branch).into());
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::config::Config;
-use crate::engine::Stack;
+use crate::engine::{BranchSort, Stack};
 use crate::git::{refs, GitRepo};
 use crate::remote;
 use anyhow::Result;
@@ -32,7 +32,13 @@ struct CheckoutRow {
     display: String,
 }
 
-pub fn run(branch: Option<String>, trunk: bool, parent: bool, child: Option<usize>) -> Result<()> {
+pub fn run(
+    branch: Option<String>,
+    trunk: bool,
+    parent: bool,
+    child: Option<usize>,
+    create: bool,
+) -> Result<()> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
 
@@ -40,17 +46,37 @@ pub fn run(branch: Option<String>, trunk: bool, parent: bool, child: Option<usiz
         anyhow::bail!("Cannot combine explicit branch with --trunk/--parent/--child");
     }
 
+    // Like `git switch -c`: a name that exists nowhere becomes a new branch on this one
+    if let Some(name) = branch.as_deref().filter(|_| create) {
+        let remote_name = Config::load()?.remote_name().to_string();
+        let exists = repo.branch_commit(name).is_ok()
+            || repo
+                .parent_commit(&format!("{}/{}", remote_name, name))
+                .is_ok();
+        if !exists {
+            return super::branch::create::run(
+                Some(name.to_string()),
+                None,  // message
+                None,  // from (current branch)
+                None,  // prefix
+                None,  // ticket
+                false, // all
+                false, // empty
+                false, // commit
+                false, // require_staged
+                true,  // auto_track
+                false, // dry_run
+                false, // track_only
+            );
+        }
+    }
+
     let target = if trunk || parent || child.is_some() {
         let stack = Stack::load(&repo)?;
         if trunk {
             stack.trunk.clone()
         } else if parent {
-            let parent_branch = stack
-                .branches
-                .get(&current)
-                .and_then(|b| b.parent.clone())
-                .ok_or_else(|| anyhow::anyhow!("Branch '{}' has no tracked parent.", current))?;
-            parent_branch
+            stack.require_parent(&current)?
         } else {
             let children: Vec<String> = stack
                 .branches
@@ -72,11 +98,16 @@ pub fn run(branch: Option<String>, trunk: bool, parent: bool, child: Option<usiz
         match branch {
             Some(b) => b,
             None => {
-                let stack = Stack::load(&repo)?;
+                let mut stack = Stack::load(&repo)?;
                 let _workdir = repo.workdir()?;
-
-                if stack.branches.is_empty() {
-                    println!("No branches found.");
+                // Same order `stax status` shows by default
+                stack.sort_children(BranchSort::default(), &current, |b| {
+                    repo.branch_commit_time(b).unwrap_or(0)
+                });
+
+                if !stack.has_tracked_branches() {
+                    println!("{}", "No tracked branches yet.".dimmed());
+                    super::print_empty_stack_hint();
                     return Ok(());
                 }
 
@@ -100,15 +131,16 @@ pub fn run(branch: Option<String>, trunk: bool, parent: bool, child: Option<usiz
                 let items: Vec<String> = rows.iter().map(|r| r.display.clone()).collect();
                 let branch_names: Vec<String> = rows.iter().map(|r| r.branch.clone()).collect();
 
-                let mut theme = ColorfulTheme::default();
-                theme.active_item_style =
-                    console::Style::new().for_stderr().black().on_white().bold();
-                theme.active_item_prefix = console::style("▶".to_string())
-                    .for_stderr()
-                    .black()
-                    .on_white()
-                    .bold();
-                theme.inactive_item_prefix = console::style(" ".to_string()).for_stderr();
+                let theme = ColorfulTheme {
+                    active_item_style: console::Style::new().for_stderr().black().on_white().bold(),
+                    active_item_prefix: console::style("▶".to_string())
+                        .for_stderr()
+                        .black()
+                        .on_white()
+                        .bold(),
+                    inactive_item_prefix: console::style(" ".to_string()).for_stderr(),
+                    ..ColorfulTheme::default()
+                };
 
                 let term = console::Term::stderr();
                 if term.is_term() {
@@ -168,8 +200,7 @@ fn build_checkout_rows(stack: &Stack, repo: &GitRepo, current: &str) -> Result<V
 
     let mut display_branches: Vec<DisplayBranch> = Vec::new();
     let mut max_column = 0;
-    let mut sorted_trunk_children = trunk_children;
-    sorted_trunk_children.sort();
+    let sorted_trunk_children = trunk_children;
 
     for (i, root) in sorted_trunk_children.iter().enumerate() {
         collect_display_branches_with_nesting(
@@ -345,7 +376,7 @@ fn terminal_width() -> usize {
 }
 
 /// Collect branches with proper nesting for branches that have multiple children
-/// fp-style: children sorted alphabetically, each child gets column + index
+/// fp-style: each child (in the stack's sort order) gets column + index
 fn collect_display_branches_with_nesting(
     stack: &Stack,
     branch: &str,
@@ -366,10 +397,9 @@ fn collect_recursive(
     *max_column = (*max_column).max(column);
 
     if let Some(info) = stack.branches.get(branch) {
-        let mut children: Vec<&String> = info.children.iter().collect();
+        let children: Vec<&String> = info.children.iter().collect();
 
         if !children.is_empty() {
-            children.sort();
             for (i, child) in children.iter().enumerate() {
                 collect_recursive(stack, child, column + i, result, max_column);
             }
@@ -472,7 +502,7 @@ mod tests {
         let stack = test_stack();
         let mut display_branches = Vec::new();
         let mut max_column = 0;
-        let mut roots = vec!["auth".to_string(), "hotfix".to_string()];
+        let mut roots = ["auth".to_string(), "hotfix".to_string()];
         roots.sort();
         for (i, root) in roots.iter().enumerate() {
             collect_display_branches_with_nesting(
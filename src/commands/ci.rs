@@ -2,6 +2,7 @@ use crate::ci::history;
 use crate::config::Config;
 use crate::engine::Stack;
 use crate::git::GitRepo;
+use crate::github::client::MAX_PAGINATED_ITEMS;
 use crate::github::GitHubClient;
 use crate::remote::RemoteInfo;
 use anyhow::Result;
@@ -407,16 +408,14 @@ pub fn record_ci_history(repo: &GitRepo, statuses: &[BranchCiStatus]) {
     for status in statuses {
         for check in &status.check_runs {
             // Only record successful completions with valid timing data
-            if check.status == "completed"
-                && check.conclusion.as_deref() == Some("success")
-                && check.elapsed_secs.is_some()
-                && check.completed_at.is_some()
+            if check.status != "completed" || check.conclusion.as_deref() != Some("success") {
+                continue;
+            }
+            if let (Some(elapsed), Some(completed_at)) =
+                (check.elapsed_secs, check.completed_at.as_ref())
             {
-                let elapsed = check.elapsed_secs.unwrap();
-                let completed_at = check.completed_at.as_ref().unwrap().clone();
-
                 // Silently ignore errors - don't fail the command if history update fails
-                let _ = history::add_completion(repo, &check.name, elapsed, completed_at);
+                let _ = history::add_completion(repo, &check.name, elapsed, completed_at.clone());
             }
         }
 
@@ -557,7 +556,7 @@ fn format_duration(secs: u64) -> String {
 }
 
 /// Fetch all checks (both check runs and commit statuses)
-async fn fetch_all_checks(
+pub async fn fetch_all_checks(
     repo: &GitRepo,
     client: &GitHubClient,
     commit_sha: &str,
@@ -595,11 +594,11 @@ async fn fetch_commit_statuses(
     commit_sha: &str,
 ) -> Result<(Option<String>, Vec<CheckRunInfo>)> {
     let url = format!(
-        "/repos/{}/{}/commits/{}/statuses",
+        "/repos/{}/{}/commits/{}/statuses?per_page=100",
         client.owner, client.repo, commit_sha
     );
 
-    let statuses: Vec<CommitStatus> = match client.octocrab.get(&url, None::<&()>).await {
+    let statuses: Vec<CommitStatus> = match client.get_all_pages(&url, MAX_PAGINATED_ITEMS).await {
         Ok(s) => s,
         Err(_) => return Ok((None, Vec::new())),
     };
@@ -650,12 +649,9 @@ async fn fetch_commit_statuses(
         // Calculate completion percentage (only for in_progress checks)
         let completion_percent = if status_str == "in_progress" {
             if let (Some(elapsed), Some(avg)) = (elapsed_secs, average_secs) {
-                if avg > 0 {
-                    let pct: u64 = ((elapsed * 100) / avg).min(99);
-                    Some(pct as u8)
-                } else {
-                    None
-                }
+                (elapsed * 100)
+                    .checked_div(avg)
+                    .map(|pct: u64| pct.min(99) as u8)
             } else {
                 None
             }
@@ -722,7 +718,7 @@ async fn fetch_check_runs(
     commit_sha: &str,
 ) -> Result<(Option<String>, Vec<CheckRunInfo>)> {
     let url = format!(
-        "/repos/{}/{}/commits/{}/check-runs",
+        "/repos/{}/{}/commits/{}/check-runs?per_page=100",
         client.owner, client.repo, commit_sha
     );
 
@@ -782,12 +778,9 @@ async fn fetch_check_runs(
         // Calculate completion percentage (only for in_progress checks)
         let completion_percent = if r.status == "in_progress" {
             if let (Some(elapsed), Some(avg)) = (elapsed_secs, average_secs) {
-                if avg > 0 {
-                    let pct: u64 = ((elapsed * 100) / avg).min(99);
-                    Some(pct as u8)
-                } else {
-                    None
-                }
+                (elapsed * 100)
+                    .checked_div(avg)
+                    .map(|pct: u64| pct.min(99) as u8)
             } else {
                 None
             }
@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::engine::Stack;
+use crate::error::StaxError;
 use crate::git::GitRepo;
 use crate::github::pr::PrComment;
 use crate::github::GitHubClient;
@@ -15,27 +16,10 @@ pub fn run(plain: bool) -> Result<()> {
     let stack = Stack::load(&repo)?;
     let config = Config::load()?;
 
-    // Check if branch is tracked
-    let branch_info = stack.branches.get(&current);
-    if branch_info.is_none() {
-        anyhow::bail!(
-            "Branch '{}' is not tracked. Use {} to track it first.",
-            current,
-            "stax branch track".cyan()
-        );
-    }
-
-    // Check if branch has a PR
-    let pr_number = branch_info.and_then(|b| b.pr_number);
-    if pr_number.is_none() {
-        anyhow::bail!(
-            "No PR found for branch '{}'. Use {} to create one.",
-            current,
-            "stax submit".cyan()
-        );
-    }
-
-    let pr_number = pr_number.unwrap();
+    let pr_number = stack
+        .require(&current)?
+        .pr_number
+        .ok_or_else(|| StaxError::NoPr(current.clone()))?;
     let remote_info = RemoteInfo::from_repo(&repo, &config)?;
 
     // Create GitHub client and fetch comments
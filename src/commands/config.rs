@@ -1,6 +1,7 @@
 use crate::config::Config;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
+use dialoguer::Editor;
 use std::fs;
 
 pub fn run() -> Result<()> {
@@ -20,3 +21,336 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
+
+/// Print where stax reads its config and stores credentials
+pub fn path() -> Result<()> {
+    println!("{} {}", "Config:".blue().bold(), Config::path()?.display());
+    println!(
+        "{} {}",
+        "Credentials:".blue().bold(),
+        Config::credentials_path()?.display()
+    );
+    Ok(())
+}
+
+/// Edit the config in `$EDITOR`, only replacing the file once the result parses
+pub fn edit() -> Result<()> {
+    Config::ensure_exists()?;
+    let path = Config::path()?;
+    let original = fs::read_to_string(&path)?;
+
+    let edited = match Editor::new().extension(".toml").edit(&original)? {
+        Some(edited) if edited != original => edited,
+        _ => {
+            println!("{}", "Config unchanged.".dimmed());
+            return Ok(());
+        }
+    };
+
+    if let Err(err) = validate(&edited) {
+        anyhow::bail!(
+            "Edited config is invalid; {} was left unchanged.\n{}",
+            path.display(),
+            err
+        );
+    }
+
+    // Write next to the real file and rename so a crash never leaves it half-written
+    let tmp = path.with_extension("toml.tmp");
+    fs::write(&tmp, &edited)?;
+    fs::rename(&tmp, &path)?;
+
+    println!("{} {}", "✓ Saved".green(), path.display());
+    Ok(())
+}
+
+/// Replace the deprecated `branch.prefix`/`branch.date` settings with an equivalent
+/// `branch.format` template, keeping a backup of the old file
+pub fn migrate() -> Result<()> {
+    let path = Config::path()?;
+    if !path.exists() {
+        println!(
+            "{}",
+            "Config file does not exist yet; nothing to migrate.".dimmed()
+        );
+        return Ok(());
+    }
+
+    let original = fs::read_to_string(&path)?;
+    let before: Config = toml::from_str(&original)?;
+    if let Some(format) = &before.branch.format {
+        if before.branch.prefix.is_some() || before.branch.date {
+            // Both styles present: format already wins, so leave the file for the user to tidy
+            println!(
+                "{} branch.format = \"{}\" is set, so branch.prefix/branch.date are ignored. \
+                 Remove them from {} by hand.",
+                "⚠".yellow(),
+                format,
+                path.display()
+            );
+        } else {
+            println!(
+                "{} (format = \"{}\"); nothing to migrate.",
+                "branch.format is already set".dimmed(),
+                format
+            );
+        }
+        return Ok(());
+    }
+
+    let mut table: toml::Table = original.parse()?;
+    let old_branch = branch_table(&table);
+    if migrate_branch_table(&mut table).is_none() {
+        println!(
+            "{}",
+            "No legacy branch naming settings to migrate.".dimmed()
+        );
+        return Ok(());
+    }
+    let migrated = toml::to_string_pretty(&table)?;
+    let after: Config = toml::from_str(&migrated)?;
+
+    println!("{}", "[branch]".blue().bold());
+    for line in branch_diff(&old_branch, &branch_table(&table)) {
+        if line.starts_with('-') {
+            println!("{}", line.red());
+        } else {
+            println!("{}", line.green());
+        }
+    }
+    println!(
+        "Example: {} → {}",
+        before.format_branch_name("my feature").cyan(),
+        after.format_branch_name("my feature").cyan()
+    );
+
+    let backup = path.with_extension("toml.bak");
+    fs::copy(&path, &backup)?;
+    let tmp = path.with_extension("toml.tmp");
+    fs::write(&tmp, &migrated)?;
+    fs::rename(&tmp, &path)?;
+
+    println!(
+        "{} {} (backup at {})",
+        "✓ Migrated".green(),
+        path.display(),
+        backup.display()
+    );
+    Ok(())
+}
+
+fn branch_table(table: &toml::Table) -> toml::Table {
+    table
+        .get("branch")
+        .and_then(|b| b.as_table())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// `-`/`+` lines for keys removed, changed, or added between two tables
+fn branch_diff(before: &toml::Table, after: &toml::Table) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (key, value) in before {
+        if after.get(key) != Some(value) {
+            lines.push(format!("- {} = {}", key, value));
+        }
+    }
+    for (key, value) in after {
+        if before.get(key) != Some(value) {
+            lines.push(format!("+ {} = {}", key, value));
+        }
+    }
+    lines
+}
+
+/// Rewrite the `[branch]` table in place, returning the new template.
+/// Legacy names are `<prefix><date><replacement><message>` with a `%Y-%m-%d` date.
+fn migrate_branch_table(table: &mut toml::Table) -> Option<String> {
+    let branch = table.get_mut("branch")?.as_table_mut()?;
+    if branch.contains_key("format") {
+        return None;
+    }
+
+    let prefix = branch
+        .remove("prefix")
+        .and_then(|v| v.as_str().map(String::from));
+    let date = branch
+        .remove("date")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if prefix.is_none() && !date {
+        return None;
+    }
+
+    let mut template = prefix.unwrap_or_default();
+    if date {
+        let replacement = branch
+            .get("replacement")
+            .and_then(|v| v.as_str())
+            .unwrap_or("-");
+        template.push_str(&format!("{{date}}{}", replacement));
+        branch.insert("date_format".into(), "%Y-%m-%d".into());
+    }
+    template.push_str("{message}");
+    branch.insert("format".into(), template.clone().into());
+    Some(template)
+}
+
+/// Print the value at a dotted key such as `branch.format`
+pub fn get(key: &str) -> Result<()> {
+    let root = toml::Value::try_from(Config::load()?)?;
+    match lookup(&root, key) {
+        Some(toml::Value::String(value)) => println!("{}", value),
+        Some(toml::Value::Table(_)) => {
+            anyhow::bail!("'{}' is a section. Use `stax config` to see its keys.", key)
+        }
+        Some(value) => println!("{}", value),
+        None => anyhow::bail!("'{}' is not set.", key),
+    }
+    Ok(())
+}
+
+/// Set the value at a dotted key, checking it against the config's types before saving
+pub fn set(key: &str, value: &str) -> Result<()> {
+    let config = with_value(Config::load()?, key, value)?;
+    config.save()?;
+    println!("{} {} = {}", "✓ Set".green(), key.bold(), value);
+    Ok(())
+}
+
+fn lookup<'a>(root: &'a toml::Value, key: &str) -> Option<&'a toml::Value> {
+    key.split('.')
+        .try_fold(root, |value, part| value.as_table()?.get(part))
+}
+
+fn with_value(config: Config, key: &str, raw: &str) -> Result<Config> {
+    let mut root = toml::Value::try_from(&config)?;
+    let (section_path, field) = match key.rsplit_once('.') {
+        Some((section, field)) => (Some(section), field),
+        None => (None, key),
+    };
+
+    let mut section = root
+        .as_table_mut()
+        .context("Config did not serialize to a table")?;
+    for part in section_path.into_iter().flat_map(|s| s.split('.')) {
+        section = section
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .with_context(|| format!("'{}' is not a config section.", part))?;
+    }
+
+    // Match the existing type where there is one; unset optional keys take a TOML literal or text
+    let value = match section.get(field) {
+        Some(toml::Value::Table(_)) => {
+            anyhow::bail!("'{}' is a section. Set one of its keys instead.", key)
+        }
+        Some(toml::Value::Boolean(_)) => {
+            toml::Value::Boolean(raw.parse().map_err(|_| {
+                anyhow::anyhow!("Invalid value for {}: expected true or false.", key)
+            })?)
+        }
+        Some(toml::Value::Integer(_)) => toml::Value::Integer(
+            raw.parse()
+                .map_err(|_| anyhow::anyhow!("Invalid value for {}: expected a number.", key))?,
+        ),
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        _ => toml::from_str::<toml::Table>(&format!("value = {}", raw))
+            .ok()
+            .and_then(|mut t| t.remove("value"))
+            .unwrap_or_else(|| toml::Value::String(raw.to_string())),
+    };
+    section.insert(field.to_string(), value);
+
+    let updated: Config = root
+        .try_into()
+        .map_err(|err| anyhow::anyhow!("Invalid value for {}: {}", key, err))?;
+
+    // Unknown keys deserialize fine but are dropped; catch them here
+    if lookup(&toml::Value::try_from(&updated)?, key).is_none() {
+        anyhow::bail!("Unknown config key '{}'.", key);
+    }
+    Ok(updated)
+}
+
+fn validate(content: &str) -> Result<()> {
+    toml::from_str::<Config>(content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_branch_table_builds_equivalent_template() {
+        let mut table: toml::Table = "[branch]\nprefix = \"cesar/\"\ndate = true\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            migrate_branch_table(&mut table).as_deref(),
+            Some("cesar/{date}-{message}")
+        );
+
+        let branch = table["branch"].as_table().unwrap();
+        assert!(!branch.contains_key("prefix"));
+        assert!(!branch.contains_key("date"));
+        assert_eq!(branch["date_format"].as_str(), Some("%Y-%m-%d"));
+
+        // Already migrated: nothing left to do
+        assert_eq!(migrate_branch_table(&mut table), None);
+    }
+
+    #[test]
+    fn test_branch_diff_lists_removed_and_added_keys() {
+        let mut table: toml::Table = "[branch]\nprefix = \"cesar/\"\nreplacement = \"-\"\n"
+            .parse()
+            .unwrap();
+        let before = branch_table(&table);
+        migrate_branch_table(&mut table).unwrap();
+
+        assert_eq!(
+            branch_diff(&before, &branch_table(&table)),
+            vec![
+                "- prefix = \"cesar/\"".to_string(),
+                "+ format = \"cesar/{message}\"".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migrate_branch_table_without_legacy_fields() {
+        let mut table: toml::Table = "[branch]\nreplacement = \"_\"\n".parse().unwrap();
+        assert_eq!(migrate_branch_table(&mut table), None);
+        assert!(!table["branch"].as_table().unwrap().contains_key("format"));
+    }
+
+    #[test]
+    fn test_with_value_sets_nested_keys() {
+        let config = with_value(Config::default(), "branch.format", "{user}/{message}").unwrap();
+        assert_eq!(config.branch.format.as_deref(), Some("{user}/{message}"));
+
+        let config = with_value(config, "ui.tips", "false").unwrap();
+        assert!(!config.ui.tips);
+    }
+
+    #[test]
+    fn test_with_value_rejects_bad_types_and_unknown_keys() {
+        assert!(with_value(Config::default(), "ui.tips", "notabool").is_err());
+        assert!(with_value(Config::default(), "ui.not_a_key", "true").is_err());
+        assert!(with_value(Config::default(), "branch", "x").is_err());
+    }
+
+    #[test]
+    fn test_validate_accepts_default_config() {
+        let content = toml::to_string_pretty(&Config::default()).unwrap();
+        assert!(validate(&content).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_toml_and_types() {
+        assert!(validate("[branch\nprefix = ").is_err());
+        assert!(validate("[ui]\ntips = \"yes please\"\n").is_err());
+    }
+}
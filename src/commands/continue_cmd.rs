@@ -1,13 +1,20 @@
+use crate::commands::branch::{fold, squash};
 use crate::config::Config;
-use crate::engine::BranchMetadata;
+use crate::engine::Stack;
 use crate::git::{GitRepo, RebaseResult};
+use crate::ops::receipt::{OpReceipt, ResumeState};
+use crate::ops::tx;
 use anyhow::Result;
 use colored::Colorize;
 
 pub fn run() -> Result<()> {
     let repo = GitRepo::open()?;
+    let paused = tx::load_paused(&repo)?;
 
     if !repo.rebase_in_progress()? {
+        if let Some(receipt) = paused {
+            return resume(&repo, receipt);
+        }
         println!("{}", "No rebase in progress.".yellow());
         return Ok(());
     }
@@ -16,18 +23,19 @@ pub fn run() -> Result<()> {
 
     match repo.rebase_continue()? {
         RebaseResult::Success => {
-            // Update metadata for current branch
+            // Update metadata for the current branch and anything below it
+            // (an interactive restack moves the whole stack in one rebase)
             let current = repo.current_branch()?;
-            if let Some(meta) = BranchMetadata::read(repo.inner(), &current)? {
-                let new_parent_rev = repo.branch_commit(&meta.parent_branch_name)?;
-                let updated_meta = BranchMetadata {
-                    parent_branch_revision: new_parent_rev,
-                    ..meta
-                };
-                updated_meta.write(repo.inner(), &current)?;
-            }
+            let stack = Stack::load(&repo)?;
+            let mut branches = stack.ancestors(&current);
+            branches.retain(|b| b != &stack.trunk);
+            branches.push(current);
+            crate::commands::restack::refresh_parent_revisions(&repo, &branches)?;
 
             println!("{}", "✓ Rebase completed successfully!".green());
+            if let Some(receipt) = paused {
+                return resume(&repo, receipt);
+            }
             let config = Config::load().unwrap_or_default();
             if config.ui.tips {
                 println!();
@@ -52,3 +60,16 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
+
+/// Run the steps an operation had left when it stopped on conflicts
+fn resume(repo: &GitRepo, receipt: OpReceipt) -> Result<()> {
+    let done = match receipt.paused() {
+        Some(ResumeState::Fold(pending)) => fold::resume(repo, pending)?,
+        Some(ResumeState::Squash(pending)) => squash::resume(repo, pending)?,
+        None => return Ok(()),
+    };
+    if done {
+        tx::finish_resumed(repo, receipt)?;
+    }
+    Ok(())
+}
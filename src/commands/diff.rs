@@ -2,14 +2,82 @@ use crate::engine::Stack;
 use crate::git::GitRepo;
 use anyhow::Result;
 use colored::Colorize;
+use std::io::IsTerminal;
+use std::path::Path;
 use std::process::Command;
 
-pub fn run(stack_filter: Option<String>, all: bool) -> Result<()> {
+/// How much of each diff to print
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffMode {
+    /// Per-file change counts (`git diff --stat`)
+    #[default]
+    Stat,
+    /// Changed file paths only (`git diff --name-only`)
+    NameOnly,
+    /// Full patch with changed words marked inline (`git diff --word-diff`)
+    WordDiff,
+    /// Full patch with changed words highlighted by color (`git diff --color-words`)
+    ColorWords,
+    /// Full line-based patch (`git diff --patch`)
+    Patch,
+}
+
+impl DiffMode {
+    fn git_flag(self) -> &'static str {
+        match self {
+            DiffMode::Stat => "--stat",
+            DiffMode::NameOnly => "--name-only",
+            DiffMode::WordDiff => "--word-diff",
+            DiffMode::ColorWords => "--color-words",
+            DiffMode::Patch => "--patch",
+        }
+    }
+
+    /// Patches carry their highlighting in color, which git drops when piped
+    fn wants_color(self) -> bool {
+        matches!(
+            self,
+            DiffMode::WordDiff | DiffMode::ColorWords | DiffMode::Patch
+        ) && std::io::stdout().is_terminal()
+    }
+}
+
+pub fn run(
+    stack_filter: Option<String>,
+    all: bool,
+    mode: DiffMode,
+    range: Option<String>,
+    between: Option<(String, String)>,
+) -> Result<()> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
     let stack = Stack::load(&repo)?;
     let workdir = repo.workdir()?;
 
+    if let Some(range) = range {
+        if !range.contains("..") {
+            anyhow::bail!("Invalid range '{}'. Expected <a>..<b>.", range);
+        }
+        println!("\n{} {}", "Diff".cyan(), range.bold());
+        return print_diff(workdir, &range, mode);
+    }
+
+    if let Some((a, b)) = between {
+        for refspec in [&a, &b] {
+            if repo.resolve_ref(refspec).is_err() {
+                anyhow::bail!("'{}' does not name a branch or commit.", refspec);
+            }
+        }
+        let (ahead, behind) = repo.commits_ahead_behind(&a, &b)?;
+        println!(
+            "\n{} {} {}",
+            "Diff".cyan(),
+            format!("{} → {}", a, b).bold(),
+            format!("({} ahead, {} behind)", ahead, behind).dimmed()
+        );
+        return print_diff(workdir, &format!("{}..{}", a, b), mode);
+    }
+
     let target = stack_filter.clone().unwrap_or_else(|| current.clone());
     let show_all = if all {
         true
@@ -60,21 +128,7 @@ pub fn run(stack_filter: Option<String>, all: bool) -> Result<()> {
             restack_marker.yellow()
         );
 
-        let output = Command::new("git")
-            .args(["diff", "--stat", &format!("{}..{}", parent, branch)])
-            .current_dir(workdir)
-            .output()?;
-
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if stdout.trim().is_empty() {
-                println!("{}", "  (no changes)".dimmed());
-            } else {
-                for line in stdout.lines() {
-                    println!("  {}", line);
-                }
-            }
-        }
+        print_diff(workdir, &format!("{}..{}", parent, branch), mode)?;
     }
 
     let aggregate_stack = if show_all {
@@ -91,22 +145,50 @@ pub fn run(stack_filter: Option<String>, all: bool) -> Result<()> {
 
     if let Some(top) = top {
         println!("\n{}", "Aggregate stack diff".cyan());
-        let output = Command::new("git")
-            .args(["diff", "--stat", &format!("{}..{}", stack.trunk, top)])
-            .current_dir(workdir)
-            .output()?;
-
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if stdout.trim().is_empty() {
-                println!("{}", "  (no changes)".dimmed());
-            } else {
-                for line in stdout.lines() {
-                    println!("  {}", line);
-                }
-            }
+        print_diff(workdir, &format!("{}..{}", stack.trunk, top), mode)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn print_diff(workdir: &Path, range: &str, mode: DiffMode) -> Result<()> {
+    let mut cmd = Command::new("git");
+    cmd.arg("diff").arg(mode.git_flag());
+    if mode.wants_color() {
+        cmd.arg("--color=always");
+    }
+    let output = cmd.arg(range).current_dir(workdir).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff {} failed: {}",
+            range,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        println!("{}", "  (no changes)".dimmed());
+    } else {
+        for line in stdout.lines() {
+            println!("  {}", line);
         }
     }
+    Ok(())
+}
 
+/// Run `git diff` straight to the terminal so git's pager and colors apply
+pub(crate) fn page_diff(workdir: &Path, range: &str, mode: DiffMode) -> Result<()> {
+    let status = Command::new("git")
+        .arg("diff")
+        .arg(mode.git_flag())
+        .arg(range)
+        .current_dir(workdir)
+        .status()?;
+
+    if !status.success() {
+        anyhow::bail!("git diff {} failed", range);
+    }
     Ok(())
 }
@@ -1,5 +1,5 @@
 use crate::config::Config;
-use crate::engine::Stack;
+use crate::engine::{BranchMetadata, RepairReason, Stack};
 use crate::git::GitRepo;
 use crate::remote;
 use anyhow::Result;
@@ -81,7 +81,7 @@ pub fn run() -> Result<()> {
         let mut orphaned = Vec::new();
         for (name, info) in &stack.branches {
             if let Some(parent) = &info.parent {
-                if repo.branch_commit(parent).is_err() {
+                if repo.parent_commit(parent).is_err() {
                     orphaned.push((name.clone(), parent.clone()));
                 }
             }
@@ -99,6 +99,30 @@ pub fn run() -> Result<()> {
             }
         }
 
+        let mut invalid_revisions: Vec<&String> = stack
+            .branches
+            .keys()
+            .filter(|name| {
+                BranchMetadata::read(repo.inner(), name)
+                    .ok()
+                    .flatten()
+                    .and_then(|meta| meta.repair_reason(repo.inner()))
+                    == Some(RepairReason::InvalidParentRevision)
+            })
+            .collect();
+        if !invalid_revisions.is_empty() {
+            issues += 1;
+            invalid_revisions.sort();
+            println!(
+                "{} {}",
+                "✗".red(),
+                "Branches with an invalid parent revision (run `stax stack fix`):".yellow()
+            );
+            for branch in invalid_revisions {
+                println!("  {}", branch);
+            }
+        }
+
         let needs_restack = stack.needs_restack();
         if !needs_restack.is_empty() {
             println!(
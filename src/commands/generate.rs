@@ -1,14 +1,15 @@
-use crate::config::Config;
+use crate::config::{AiConfig, Config};
 use crate::engine::{BranchMetadata, Stack};
+use crate::error::StaxError;
 use crate::git::GitRepo;
-use crate::github::pr_template::discover_pr_templates;
-use crate::github::GitHubClient;
+use crate::github::pr_body::PrBodyBoilerplate;
+use crate::github::pr_template::{discover_pr_templates, discover_pr_templates_with};
 use crate::remote;
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Input, Select};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 // ---------------------------------------------------------------------------
@@ -47,18 +48,40 @@ const SUPPORTED_AGENTS: &[&str] = &["claude", "codex", "gemini", "opencode"];
 // Public entry point
 // ---------------------------------------------------------------------------
 
-pub fn run(edit: bool, agent_flag: Option<String>, model_flag: Option<String>) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    edit: bool,
+    agent_flag: Option<String>,
+    model_flag: Option<String>,
+    since_last_submit: bool,
+    whole_stack: bool,
+    title: bool,
+    yes: bool,
+    template_dir: Option<PathBuf>,
+) -> Result<()> {
+    if title {
+        return run_title(edit, agent_flag, model_flag);
+    }
+    if whole_stack {
+        return run_stack(
+            edit,
+            agent_flag,
+            model_flag,
+            since_last_submit,
+            yes,
+            template_dir,
+        );
+    }
+
     let config = Config::load()?;
     let repo = GitRepo::open()?;
     let workdir = repo.workdir()?.to_path_buf();
+    let template = load_template(&workdir, template_dir.as_deref())?;
     let stack = Stack::load(&repo)?;
     let current_branch = repo.current_branch()?;
 
     // Ensure current branch is tracked
-    let branch_info = stack
-        .branches
-        .get(&current_branch)
-        .context("Current branch is not tracked by stax. Run `stax branch track` first.")?;
+    let branch_info = stack.require(&current_branch)?;
 
     let parent = branch_info
         .parent
@@ -74,100 +97,396 @@ pub fn run(edit: bool, agent_flag: Option<String>, model_flag: Option<String>) -
         .as_ref()
         .filter(|p| p.number > 0)
         .map(|p| p.number)
-        .context("No PR found for current branch. Submit first with `stax submit` or `stax ss`.")?;
+        .ok_or_else(|| StaxError::NoPr(current_branch.clone()))?;
 
     // Resolve AI agent and model (interactive if needed)
     let mut config = config;
     let agent = resolve_agent(agent_flag.as_deref(), &mut config)?;
     let model = resolve_model(model_flag.as_deref(), &config, &agent)?;
 
-    // Collect context for the prompt
-    println!("{}", "Collecting context...".dimmed());
-    let diff_stat = get_diff_stat(&workdir, parent, &current_branch);
-    let diff = get_full_diff(&workdir, parent, &current_branch);
-    let commits = collect_commit_messages(&workdir, parent, &current_branch);
-    let templates = discover_pr_templates(&workdir).unwrap_or_default();
-    let template_content = templates.first().map(|t| t.content.as_str());
+    let base = resolve_base(&repo, &meta, parent, since_last_submit);
+    let generated_body = generate_body(
+        &workdir,
+        base,
+        &current_branch,
+        &agent,
+        model.as_deref(),
+        template.as_deref(),
+        &config.ai,
+    )?;
+
+    // Let user review/edit the generated body
+    let final_body = if edit {
+        Editor::new()
+            .edit(&generated_body)?
+            .unwrap_or(generated_body)
+    } else {
+        match review_body(generated_body, "Cancel")? {
+            Some(body) => body,
+            None => {
+                println!("{}", "Cancelled.".yellow());
+                return Ok(());
+            }
+        }
+    };
+
+    // Update the PR body on GitHub
+    print!("  Updating PR #{} body... ", pr_number.to_string().cyan());
+    std::io::stdout().flush().ok();
+
+    let remote_info = remote::RemoteInfo::from_repo(&repo, &config)?;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let client = runtime.block_on(async { remote_info.provider() })?;
+
+    let final_body = PrBodyBoilerplate::from_config(&config, &workdir)?.apply(&final_body);
+    let changed = runtime.block_on(async {
+        client
+            .update_pr_body_if_changed(pr_number, &final_body)
+            .await
+    })?;
+    if !changed {
+        println!("{}", "PR body unchanged.".dimmed());
+        return Ok(());
+    }
+
+    println!("{}", "done".green());
+    println!(
+        "  {} PR #{} body updated successfully",
+        "✓".green().bold(),
+        pr_number
+    );
+
+    Ok(())
+}
+
+/// Generate a one-line title for the current branch's PR and update it
+fn run_title(edit: bool, agent_flag: Option<String>, model_flag: Option<String>) -> Result<()> {
+    let mut config = Config::load()?;
+    let repo = GitRepo::open()?;
+    let workdir = repo.workdir()?.to_path_buf();
+    let stack = Stack::load(&repo)?;
+    let current_branch = repo.current_branch()?;
 
+    let parent = stack
+        .require(&current_branch)?
+        .parent
+        .clone()
+        .context("Current branch has no parent set")?;
+    let meta = BranchMetadata::read(repo.inner(), &current_branch)?
+        .context("No metadata for current branch")?;
+    let pr_number = meta
+        .pr_info
+        .as_ref()
+        .filter(|p| p.number > 0)
+        .map(|p| p.number)
+        .ok_or_else(|| StaxError::NoPr(current_branch.clone()))?;
+
+    let agent = resolve_agent(agent_flag.as_deref(), &mut config)?;
+    let model = resolve_model(model_flag.as_deref(), &config, &agent)?;
+
+    println!("{}", "Collecting context...".dimmed());
+    let diff_stat = get_diff_stat(&workdir, &parent, &current_branch);
+    let diff = get_full_diff(&workdir, &parent, &current_branch, &config.ai.diff_exclude);
+    let commits = collect_commit_messages(&workdir, &parent, &current_branch);
     if diff.trim().is_empty() && commits.is_empty() {
         bail!("No changes found between {} and {}", parent, current_branch);
     }
 
-    // Build the AI prompt
-    let prompt = build_ai_prompt(&diff_stat, &diff, &commits, template_content);
-
-    // Invoke AI agent
-    let model_display = model.as_deref().unwrap_or("default");
+    let prompt = build_title_prompt(&diff_stat, &diff, &commits, config.ai.max_diff_bytes);
     println!(
         "  {} {} (model: {})...",
-        "Generating PR body with".dimmed(),
+        "Generating PR title with".dimmed(),
         agent.cyan().bold(),
-        model_display.dimmed()
+        model.as_deref().unwrap_or("default").dimmed()
     );
+    let generated_title = parse_title(&invoke_ai_agent(&agent, model.as_deref(), &prompt)?)?;
 
-    let generated_body = invoke_ai_agent(&agent, model.as_deref(), &prompt)?;
-
-    if generated_body.trim().is_empty() {
-        bail!("AI agent returned an empty response");
-    }
-
-    // Let user review/edit the generated body
-    let final_body = if edit {
-        Editor::new()
-            .edit(&generated_body)?
-            .unwrap_or(generated_body)
+    let edit_title = |title: String| -> Result<String> {
+        Ok(Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("PR title")
+            .with_initial_text(title)
+            .interact_text()?)
+    };
+    let final_title = if edit {
+        edit_title(generated_title)?
     } else {
-        // Show preview and confirm
         println!();
-        println!("{}", "─── Generated PR Body ───".blue().bold());
-        println!("{}", generated_body);
-        println!("{}", "──────────────────────────".blue().bold());
+        println!("  {} {}", "Generated title:".blue().bold(), generated_title);
         println!();
-
-        let options = vec!["Use as-is", "Edit in $EDITOR", "Cancel"];
+        let options = ["Use as-is", "Edit", "Cancel"];
         let choice = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("What would you like to do?")
-            .items(&options)
+            .items(options)
             .default(0)
             .interact()?;
-
         match choice {
-            0 => generated_body,
-            1 => Editor::new()
-                .edit(&generated_body)?
-                .unwrap_or(generated_body),
+            0 => generated_title,
+            1 => edit_title(generated_title)?,
             _ => {
                 println!("{}", "Cancelled.".yellow());
                 return Ok(());
             }
         }
     };
+    let final_title = final_title.trim();
+    if final_title.is_empty() {
+        bail!("PR title cannot be empty");
+    }
 
-    // Update the PR body on GitHub
-    print!("  Updating PR #{} body... ", pr_number.to_string().cyan());
+    print!("  Updating PR #{} title... ", pr_number.to_string().cyan());
     std::io::stdout().flush().ok();
 
     let remote_info = remote::RemoteInfo::from_repo(&repo, &config)?;
-    let owner = remote_info.owner().to_string();
-    let repo_name = remote_info.repo.clone();
+    let runtime = tokio::runtime::Runtime::new()?;
+    let client = runtime.block_on(async { remote_info.provider() })?;
+    runtime.block_on(async { client.update_pr_title(pr_number, final_title).await })?;
+
+    println!("{}", "done".green());
+    println!(
+        "  {} PR #{} title set to \"{}\"",
+        "✓".green().bold(),
+        pr_number,
+        final_title
+    );
+
+    Ok(())
+}
+
+/// Generate and update PR bodies for every branch with a PR in the current stack
+fn run_stack(
+    edit: bool,
+    agent_flag: Option<String>,
+    model_flag: Option<String>,
+    since_last_submit: bool,
+    yes: bool,
+    template_dir: Option<PathBuf>,
+) -> Result<()> {
+    let mut config = Config::load()?;
+    let repo = GitRepo::open()?;
+    let workdir = repo.workdir()?.to_path_buf();
+    let template = load_template(&workdir, template_dir.as_deref())?;
+    let stack = Stack::load(&repo)?;
+    let current_branch = repo.current_branch()?;
 
+    let branches: Vec<(String, String, BranchMetadata, u64)> = stack
+        .current_stack(&current_branch)
+        .into_iter()
+        .filter(|b| b != &stack.trunk)
+        .filter_map(|branch| {
+            let parent = stack.branches.get(&branch)?.parent.clone()?;
+            let meta = BranchMetadata::read(repo.inner(), &branch).ok()??;
+            let pr_number = meta.pr_info.as_ref().filter(|p| p.number > 0)?.number;
+            Some((branch, parent, meta, pr_number))
+        })
+        .collect();
+
+    if branches.is_empty() {
+        bail!("No branches with PRs in the current stack. Submit first with `stax submit` or `stax ss`.");
+    }
+
+    // Resolve the agent, provider, and template once and reuse them for every branch
+    let agent = resolve_agent(agent_flag.as_deref(), &mut config)?;
+    let model = resolve_model(model_flag.as_deref(), &config, &agent)?;
+    let remote_info = remote::RemoteInfo::from_repo(&repo, &config)?;
     let runtime = tokio::runtime::Runtime::new()?;
-    let client = runtime.block_on(async {
-        GitHubClient::new(&owner, &repo_name, remote_info.api_base_url.clone())
-    })?;
+    let client = runtime.block_on(async { remote_info.provider() })?;
+    let body_boilerplate = PrBodyBoilerplate::from_config(&config, &workdir)?;
 
-    runtime.block_on(async { client.update_pr_body(pr_number, &final_body).await })?;
+    let mut updated: Vec<(String, u64)> = Vec::new();
+    let mut skipped: Vec<(String, u64)> = Vec::new();
 
-    println!("{}", "done".green());
+    for (branch, parent, meta, pr_number) in &branches {
+        println!();
+        println!(
+            "{} {} (PR #{})",
+            "▸".blue(),
+            branch.cyan().bold(),
+            pr_number
+        );
+
+        let base = resolve_base(&repo, meta, parent, since_last_submit);
+        let generated_body = match generate_body(
+            &workdir,
+            base,
+            branch,
+            &agent,
+            model.as_deref(),
+            template.as_deref(),
+            &config.ai,
+        ) {
+            Ok(body) => body,
+            Err(e) => {
+                println!("  {} {}", "✗".red(), e);
+                skipped.push((branch.clone(), *pr_number));
+                continue;
+            }
+        };
+
+        let final_body = if edit {
+            Some(
+                Editor::new()
+                    .edit(&generated_body)?
+                    .unwrap_or(generated_body),
+            )
+        } else if yes {
+            Some(generated_body)
+        } else {
+            review_body(generated_body, "Skip this branch")?
+        };
+
+        let Some(final_body) = final_body else {
+            println!("  {}", "Skipped.".yellow());
+            skipped.push((branch.clone(), *pr_number));
+            continue;
+        };
+
+        print!("  Updating PR #{} body... ", pr_number.to_string().cyan());
+        std::io::stdout().flush().ok();
+        let final_body = body_boilerplate.apply(&final_body);
+        let changed = runtime.block_on(async {
+            client
+                .update_pr_body_if_changed(*pr_number, &final_body)
+                .await
+        })?;
+        if changed {
+            println!("{}", "done".green());
+            updated.push((branch.clone(), *pr_number));
+        } else {
+            println!("{}", "PR body unchanged.".dimmed());
+        }
+    }
+
+    println!();
     println!(
-        "  {} PR #{} body updated successfully",
+        "{} Updated {} of {} PR bodies",
         "✓".green().bold(),
-        pr_number
+        updated.len(),
+        branches.len()
     );
+    for (branch, pr_number) in &updated {
+        println!("  {} #{} {}", "✓".green(), pr_number, branch);
+    }
+    for (branch, pr_number) in &skipped {
+        println!("  {} #{} {}", "–".dimmed(), pr_number, branch.dimmed());
+    }
 
     Ok(())
 }
 
+/// Base revision for the prompt, narrowed to the last submit when requested
+fn resolve_base<'a>(
+    repo: &GitRepo,
+    meta: &'a BranchMetadata,
+    parent: &'a str,
+    since_last_submit: bool,
+) -> &'a str {
+    if !since_last_submit {
+        return parent;
+    }
+
+    match meta
+        .last_submitted_revision
+        .as_deref()
+        .filter(|rev| repo.rev_parse(rev).is_ok())
+    {
+        Some(rev) => {
+            println!(
+                "{}",
+                format!("Diffing since last submit ({})", &rev[..rev.len().min(7)]).dimmed()
+            );
+            rev
+        }
+        None => {
+            println!(
+                "{}",
+                "No recorded submit for this branch, using full range.".dimmed()
+            );
+            parent
+        }
+    }
+}
+
+/// Content of the first PR template, from `template_dir` when given
+fn load_template(workdir: &Path, template_dir: Option<&Path>) -> Result<Option<String>> {
+    let templates = match template_dir {
+        Some(dir) => discover_pr_templates_with(workdir, Some(dir))?,
+        None => discover_pr_templates(workdir).unwrap_or_default(),
+    };
+    Ok(templates.into_iter().next().map(|t| t.content))
+}
+
+/// Collect `base..branch` context and ask the agent for a PR body
+fn generate_body(
+    workdir: &Path,
+    base: &str,
+    branch: &str,
+    agent: &str,
+    model: Option<&str>,
+    template_content: Option<&str>,
+    ai: &AiConfig,
+) -> Result<String> {
+    println!("{}", "Collecting context...".dimmed());
+    let diff_stat = get_diff_stat(workdir, base, branch);
+    let diff = get_full_diff(workdir, base, branch, &ai.diff_exclude);
+    let commits = collect_commit_messages(workdir, base, branch);
+
+    if diff.trim().is_empty() && commits.is_empty() {
+        bail!("No changes found between {} and {}", base, branch);
+    }
+
+    // Build the AI prompt
+    let prompt = build_ai_prompt(
+        &diff_stat,
+        &diff,
+        &commits,
+        template_content,
+        ai.max_diff_bytes,
+    );
+
+    // Invoke AI agent
+    println!(
+        "  {} {} (model: {})...",
+        "Generating PR body with".dimmed(),
+        agent.cyan().bold(),
+        model.unwrap_or("default").dimmed()
+    );
+
+    let generated_body = invoke_ai_agent(agent, model, &prompt)?;
+
+    if generated_body.trim().is_empty() {
+        bail!("AI agent returned an empty response");
+    }
+
+    Ok(generated_body)
+}
+
+/// Preview a generated body and let the user accept, edit, or decline it
+pub(crate) fn review_body(generated_body: String, decline_label: &str) -> Result<Option<String>> {
+    println!();
+    println!("{}", "─── Generated PR Body ───".blue().bold());
+    println!("{}", generated_body);
+    println!("{}", "──────────────────────────".blue().bold());
+    println!();
+
+    let options = vec!["Use as-is", "Edit in $EDITOR", decline_label];
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("What would you like to do?")
+        .items(&options)
+        .default(0)
+        .interact()?;
+
+    Ok(match choice {
+        0 => Some(generated_body),
+        1 => Some(
+            Editor::new()
+                .edit(&generated_body)?
+                .unwrap_or(generated_body),
+        ),
+        _ => None,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Agent resolution
 // ---------------------------------------------------------------------------
@@ -411,9 +730,25 @@ pub fn get_diff_stat(workdir: &Path, parent: &str, branch: &str) -> String {
     }
 }
 
-pub fn get_full_diff(workdir: &Path, parent: &str, branch: &str) -> String {
+/// Full `parent..branch` diff, leaving out files matching any `exclude` glob.
+/// Patterns without a `/` match the file name in any directory.
+pub fn get_full_diff(workdir: &Path, parent: &str, branch: &str, exclude: &[String]) -> String {
+    let mut args = vec!["diff".to_string(), format!("{}..{}", parent, branch)];
+    if !exclude.is_empty() {
+        args.push("--".to_string());
+        args.push(".".to_string());
+        args.extend(exclude.iter().map(|pattern| {
+            let anchored = pattern.contains('/');
+            let pattern = pattern.trim_start_matches('/');
+            if anchored {
+                format!(":(exclude,glob){}", pattern)
+            } else {
+                format!(":(exclude,glob)**/{}", pattern)
+            }
+        }));
+    }
     let output = Command::new("git")
-        .args(["diff", &format!("{}..{}", parent, branch)])
+        .args(&args)
         .current_dir(workdir)
         .output();
 
@@ -448,13 +783,16 @@ fn collect_commit_messages(workdir: &Path, parent: &str, branch: &str) -> Vec<St
 // Prompt construction
 // ---------------------------------------------------------------------------
 
-const MAX_DIFF_BYTES: usize = 80_000; // ~80KB limit to stay within context windows
+/// Ceiling for `ai.max_diff_bytes`, so a typo can't pipe a whole repo into the agent
+const MAX_DIFF_BYTES_LIMIT: usize = 4 * 1024 * 1024;
 
+/// Build the agent prompt; the diff is truncated to `max_diff_bytes` (`ai.max_diff_bytes`)
 pub fn build_ai_prompt(
     diff_stat: &str,
     diff: &str,
     commits: &[String],
     template: Option<&str>,
+    max_diff_bytes: usize,
 ) -> String {
     let mut prompt = String::new();
 
@@ -484,30 +822,116 @@ pub fn build_ai_prompt(
         prompt.push_str("\n```\n\n");
     }
 
-    if !diff.is_empty() {
-        let truncated = if diff.len() > MAX_DIFF_BYTES {
-            let safe = &diff[..MAX_DIFF_BYTES];
-            // Cut at last newline to avoid splitting a line
-            let cut = safe.rfind('\n').unwrap_or(MAX_DIFF_BYTES);
-            format!(
-                "{}\n\n... (diff truncated, showing first ~80KB of {} total) ...",
-                &diff[..cut],
-                format_bytes(diff.len())
-            )
-        } else {
-            diff.to_string()
-        };
+    push_diff(&mut prompt, diff, max_diff_bytes);
+
+    prompt.push_str("Write only the PR body in markdown. Do not include any preamble, explanation, or wrapping code fences.");
+
+    prompt
+}
+
+/// Longest PR title `stax generate --title` accepts from the agent
+const MAX_TITLE_CHARS: usize = 72;
+
+/// Build the agent prompt for a single-line PR title
+pub fn build_title_prompt(
+    diff_stat: &str,
+    diff: &str,
+    commits: &[String],
+    max_diff_bytes: usize,
+) -> String {
+    let mut prompt = String::new();
+
+    prompt.push_str(&format!(
+        "Write a pull request title for the following changes. Use a concise \
+         conventional-commit style (e.g. \"feat(auth): add token refresh\") \
+         in under {} characters.\n\n",
+        MAX_TITLE_CHARS
+    ));
+
+    if !commits.is_empty() {
+        prompt.push_str("Commit messages:\n");
+        for msg in commits {
+            prompt.push_str(&format!("- {}\n", msg));
+        }
+        prompt.push('\n');
+    }
 
-        prompt.push_str("Full diff:\n```diff\n");
-        prompt.push_str(&truncated);
+    if !diff_stat.is_empty() {
+        prompt.push_str("Diff stat (file-level summary):\n```\n");
+        prompt.push_str(diff_stat);
         prompt.push_str("\n```\n\n");
     }
 
-    prompt.push_str("Write only the PR body in markdown. Do not include any preamble, explanation, or wrapping code fences.");
+    push_diff(&mut prompt, diff, max_diff_bytes);
+
+    prompt.push_str(
+        "Reply with the title only, on a single line, without quotes or any explanation.",
+    );
 
     prompt
 }
 
+/// Normalize the agent's reply to a PR title: trim it and strip surrounding quotes,
+/// rejecting empty, multi-line, or overlong answers
+pub fn parse_title(response: &str) -> Result<String> {
+    let mut title = response.trim();
+    while let Some(inner) = ['"', '\'', '`']
+        .iter()
+        .find_map(move |q| title.strip_prefix(*q)?.strip_suffix(*q))
+    {
+        title = inner.trim();
+    }
+
+    if title.is_empty() {
+        bail!("AI agent returned an empty title");
+    }
+    if title.lines().count() > 1 {
+        bail!(
+            "AI agent returned more than one line instead of a title:\n{}",
+            response.trim()
+        );
+    }
+    let len = title.chars().count();
+    if len > MAX_TITLE_CHARS {
+        bail!(
+            "AI agent returned a {}-character title (limit {}): {}",
+            len,
+            MAX_TITLE_CHARS,
+            title
+        );
+    }
+    Ok(title.to_string())
+}
+
+/// Append the diff to `prompt`, truncated to `max_diff_bytes` (`ai.max_diff_bytes`)
+fn push_diff(prompt: &mut String, diff: &str, max_diff_bytes: usize) {
+    if diff.is_empty() {
+        return;
+    }
+    let max_diff_bytes = max_diff_bytes.min(MAX_DIFF_BYTES_LIMIT);
+
+    let truncated = if diff.len() > max_diff_bytes {
+        let mut end = max_diff_bytes;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        // Cut at last newline to avoid splitting a line
+        let cut = diff[..end].rfind('\n').unwrap_or(end);
+        format!(
+            "{}\n\n... (diff truncated, showing first ~{} of {} total) ...",
+            &diff[..cut],
+            format_bytes(max_diff_bytes),
+            format_bytes(diff.len())
+        )
+    } else {
+        diff.to_string()
+    };
+
+    prompt.push_str("Full diff:\n```diff\n");
+    prompt.push_str(&truncated);
+    prompt.push_str("\n```\n\n");
+}
+
 fn format_bytes(bytes: usize) -> String {
     if bytes >= 1_048_576 {
         format!("{:.1}MB", bytes as f64 / 1_048_576.0)
@@ -652,4 +1076,109 @@ mod tests {
         let resolved = resolve_model(None, &config, "claude").unwrap();
         assert_eq!(resolved, None);
     }
+
+    #[test]
+    fn excluded_paths_stay_out_of_the_prompt_but_not_the_stat() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workdir = dir.path();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(workdir)
+                .env("GIT_AUTHOR_NAME", "Test")
+                .env("GIT_AUTHOR_EMAIL", "test@example.com")
+                .env("GIT_COMMITTER_NAME", "Test")
+                .env("GIT_COMMITTER_EMAIL", "test@example.com")
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {:?}", args);
+        };
+
+        git(&["init", "-q", "-b", "main"]);
+        std::fs::write(workdir.join("README.md"), "hello\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+        git(&["checkout", "-q", "-b", "feature"]);
+        std::fs::create_dir(workdir.join("web")).unwrap();
+        std::fs::write(workdir.join("web/package-lock.json"), "lockfile-noise\n").unwrap();
+        std::fs::write(workdir.join("Cargo.lock"), "lockfile-noise\n").unwrap();
+        std::fs::write(workdir.join("lib.rs"), "fn real_change() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "feature"]);
+
+        let exclude = crate::config::AiConfig::default().diff_exclude;
+        let diff_stat = get_diff_stat(workdir, "main", "feature");
+        let diff = get_full_diff(workdir, "main", "feature", &exclude);
+        let prompt = build_ai_prompt(&diff_stat, &diff, &[], None, 80_000);
+
+        assert!(prompt.contains("real_change"));
+        assert!(!prompt.contains("lockfile-noise"));
+        assert!(diff_stat.contains("Cargo.lock"));
+        assert!(diff_stat.contains("package-lock.json"));
+    }
+
+    #[test]
+    fn build_ai_prompt_truncates_at_configured_size() {
+        let diff: String = (0..100).map(|i| format!("+line {:03}\n", i)).collect();
+
+        let prompt = build_ai_prompt("", &diff, &[], None, 200);
+        assert!(prompt.contains("+line 000"));
+        assert!(!prompt.contains("+line 099"));
+        assert!(prompt.contains("showing first ~200B of 1000B total"));
+        // Cut lands on a line boundary
+        assert!(prompt.contains("\n\n... (diff truncated"));
+
+        let prompt = build_ai_prompt("", &diff, &[], None, usize::MAX);
+        assert!(prompt.contains("+line 099"));
+        assert!(!prompt.contains("diff truncated"));
+    }
+
+    #[test]
+    fn build_ai_prompt_truncation_respects_char_boundaries() {
+        let diff = "é".repeat(100);
+        let prompt = build_ai_prompt("", &diff, &[], None, 51);
+        assert!(prompt.contains("diff truncated"));
+    }
+
+    #[test]
+    fn build_title_prompt_asks_for_a_single_line() {
+        let commits = vec!["Add token refresh".to_string()];
+        let diff: String = (0..100).map(|i| format!("+line {:03}\n", i)).collect();
+
+        let prompt = build_title_prompt("src/auth.rs | 10 +", &diff, &commits, 200);
+        assert!(prompt.contains("- Add token refresh"));
+        assert!(prompt.contains("src/auth.rs | 10 +"));
+        assert!(prompt.contains("under 72 characters"));
+        assert!(prompt.contains("diff truncated"));
+        assert!(prompt.ends_with("without quotes or any explanation."));
+    }
+
+    #[test]
+    fn parse_title_strips_quotes_and_whitespace() {
+        assert_eq!(
+            parse_title("  feat(auth): add token refresh\n").unwrap(),
+            "feat(auth): add token refresh"
+        );
+        assert_eq!(
+            parse_title("\"fix: handle empty diff\"").unwrap(),
+            "fix: handle empty diff"
+        );
+        assert_eq!(
+            parse_title("`'docs: update README'`").unwrap(),
+            "docs: update README"
+        );
+        // Quotes inside the title are kept
+        assert_eq!(
+            parse_title("fix: don't drop \"draft\" PRs").unwrap(),
+            "fix: don't drop \"draft\" PRs"
+        );
+    }
+
+    #[test]
+    fn parse_title_rejects_empty_multiline_and_long_responses() {
+        assert!(parse_title("  \"\"  ").is_err());
+        assert!(parse_title("Here is a title:\nfeat: add thing").is_err());
+        assert!(parse_title(&format!("feat: {}", "x".repeat(70))).is_err());
+        assert!(parse_title(&format!("feat: {}", "é".repeat(66))).is_ok());
+    }
 }
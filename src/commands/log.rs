@@ -1,6 +1,6 @@
 use crate::cache::CiCache;
 use crate::config::Config;
-use crate::engine::Stack;
+use crate::engine::{BranchSort, Stack};
 use crate::git::GitRepo;
 use crate::github::GitHubClient;
 use crate::remote::{self, RemoteInfo};
@@ -65,10 +65,17 @@ pub fn run(
     current_only: bool,
     compact: bool,
     quiet: bool,
+    reverse: bool,
+    sort: Option<BranchSort>,
 ) -> Result<()> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
-    let stack = Stack::load(&repo)?;
+    let mut stack = Stack::load(&repo)?;
+    // Without --sort, the largest subtree comes first at every fork
+    if let Some(sort) = sort {
+        stack.sort_children(sort, &current, |b| repo.branch_commit_time(b).unwrap_or(0));
+    }
+    let by_size = sort.is_none();
     let workdir = repo.workdir()?;
     let config = Config::load()?;
     let has_tracked = stack.branches.len() > 1;
@@ -117,11 +124,13 @@ pub fn run(
     let mut max_column = 0;
     let mut next_column = 0;
     let mut sorted_trunk_children = trunk_children;
-    sorted_trunk_children.sort_by(|a, b| {
-        let size_a = count_chain_size(&stack, a, allowed_branches.as_ref());
-        let size_b = count_chain_size(&stack, b, allowed_branches.as_ref());
-        size_b.cmp(&size_a).then_with(|| a.cmp(b))
-    });
+    if by_size {
+        sorted_trunk_children.sort_by(|a, b| {
+            let size_a = count_chain_size(&stack, a, allowed_branches.as_ref());
+            let size_b = count_chain_size(&stack, b, allowed_branches.as_ref());
+            size_b.cmp(&size_a).then_with(|| a.cmp(b))
+        });
+    }
 
     for root in &sorted_trunk_children {
         collect_display_branches_with_nesting(
@@ -131,6 +140,7 @@ pub fn run(
             &mut display_branches,
             &mut max_column,
             allowed_branches.as_ref(),
+            by_size,
         );
         next_column = max_column + 1;
     }
@@ -140,6 +150,9 @@ pub fn run(
     let mut ordered_branches: Vec<String> =
         display_branches.iter().map(|b| b.name.clone()).collect();
     ordered_branches.push(stack.trunk.clone());
+    if reverse {
+        ordered_branches.reverse();
+    }
 
     // Load CI cache and refresh if stale (TTL expired)
     let mut cache = CiCache::load(git_dir);
@@ -254,14 +267,31 @@ pub fn run(
         return Ok(());
     }
 
-    // Render each branch
-    for (i, db) in display_branches.iter().enumerate() {
+    if reverse {
+        render_trunk_line(
+            &stack,
+            &current,
+            &remote_branches,
+            &branch_log_map,
+            max_column,
+            tree_target_width,
+            true,
+        );
+    }
+
+    // Render each branch, tips first unless --reverse
+    let mut render_order: Vec<usize> = (0..display_branches.len()).collect();
+    if reverse {
+        render_order.reverse();
+    }
+    for i in render_order {
+        let db = &display_branches[i];
         let branch = &db.name;
         let is_current = branch == &current;
         let has_remote = remote_branches.contains(branch);
         let color = DEPTH_COLORS[db.column % DEPTH_COLORS.len()];
 
-        // Check if we need a corner connector
+        // Check if we need a corner connector (a side branch joins here)
         let prev_branch_col = if i > 0 {
             Some(display_branches[i - 1].column)
         } else {
@@ -280,7 +310,8 @@ pub fn run(
                 visual_width += 1;
 
                 if needs_corner {
-                    tree.push_str(&format!("{}", "─┘".color(color)));
+                    let corner = if reverse { "─┐" } else { "─┘" };
+                    tree.push_str(&format!("{}", corner.color(color)));
                     visual_width += 2;
                 }
             } else {
@@ -376,7 +407,67 @@ pub fn run(
         }
     }
 
-    // Render trunk with corner connector
+    if !reverse {
+        render_trunk_line(
+            &stack,
+            &current,
+            &remote_branches,
+            &branch_log_map,
+            max_column,
+            tree_target_width,
+            false,
+        );
+    }
+
+    if !has_tracked && !quiet {
+        println!(
+            "{}",
+            "No tracked branches yet (showing trunk only).".dimmed()
+        );
+        super::print_empty_stack_hint();
+    }
+
+    // Show legend and restack hint
+    let needs_restack = stack.needs_restack();
+    let config = Config::load().unwrap_or_default();
+    if !quiet && config.ui.tips {
+        println!();
+        // Always show the legend when there are tracked branches
+        if has_tracked {
+            println!("{}", "↑ ahead   ↓ behind   ⇅ needs restack".dimmed());
+        }
+
+        if !needs_restack.is_empty() {
+            println!(
+                "{} Run {} to rebase.",
+                format!(
+                    "⇅ {} {} need restacking.",
+                    needs_restack.len(),
+                    if needs_restack.len() == 1 {
+                        "branch"
+                    } else {
+                        "branches"
+                    }
+                )
+                .bright_yellow(),
+                "stax rs --restack".bright_cyan()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Render the trunk line and its details; `reverse` puts it above the branches
+fn render_trunk_line(
+    stack: &Stack,
+    current: &str,
+    remote_branches: &HashSet<String>,
+    branch_log_map: &HashMap<String, BranchLogJson>,
+    max_column: usize,
+    tree_target_width: usize,
+    reverse: bool,
+) {
     let is_trunk_current = stack.trunk == current;
     let trunk_color = DEPTH_COLORS[0];
 
@@ -389,11 +480,13 @@ pub fn run(
 
     if max_column >= 1 {
         for col in 1..=max_column {
-            if col < max_column {
-                trunk_tree.push_str(&format!("{}", "─┴".color(trunk_color)));
-            } else {
-                trunk_tree.push_str(&format!("{}", "─┘".color(trunk_color)));
-            }
+            let connector = match (col < max_column, reverse) {
+                (true, false) => "─┴",
+                (false, false) => "─┘",
+                (true, true) => "─┬",
+                (false, true) => "─┐",
+            };
+            trunk_tree.push_str(&format!("{}", connector.color(trunk_color)));
             trunk_visual_width += 2;
         }
     }
@@ -431,47 +524,6 @@ pub fn run(
             );
         }
     }
-
-    if !has_tracked && !quiet {
-        println!(
-            "{}",
-            "No tracked branches yet (showing trunk only).".dimmed()
-        );
-        println!(
-            "Use {} to start tracking branches.",
-            "stax branch track".cyan()
-        );
-    }
-
-    // Show legend and restack hint
-    let needs_restack = stack.needs_restack();
-    let config = Config::load().unwrap_or_default();
-    if !quiet && config.ui.tips {
-        println!();
-        // Always show the legend when there are tracked branches
-        if has_tracked {
-            println!("{}", "↑ ahead   ↓ behind   ⇅ needs restack".dimmed());
-        }
-
-        if !needs_restack.is_empty() {
-            println!(
-                "{} Run {} to rebase.",
-                format!(
-                    "⇅ {} {} need restacking.",
-                    needs_restack.len(),
-                    if needs_restack.len() == 1 {
-                        "branch"
-                    } else {
-                        "branches"
-                    }
-                )
-                .bright_yellow(),
-                "stax rs --restack".bright_cyan()
-            );
-        }
-    }
-
-    Ok(())
 }
 
 fn build_detail_prefix(
@@ -506,6 +558,7 @@ fn collect_display_branches_with_nesting(
     result: &mut Vec<DisplayBranch>,
     max_column: &mut usize,
     allowed: Option<&HashSet<String>>,
+    by_size: bool,
 ) {
     if allowed.is_some_and(|set| !set.contains(branch)) {
         return;
@@ -522,14 +575,16 @@ fn collect_display_branches_with_nesting(
                 .map(|c| (c, count_chain_size(stack, c, allowed)))
                 .collect();
 
-            children_with_sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            if by_size {
+                children_with_sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            }
 
             let main_child = children_with_sizes[0].0;
             let side_children: Vec<&String> =
                 children_with_sizes[1..].iter().map(|(c, _)| *c).collect();
 
             collect_display_branches_with_nesting(
-                stack, main_child, column, result, max_column, allowed,
+                stack, main_child, column, result, max_column, allowed, by_size,
             );
 
             for side in &side_children {
@@ -540,6 +595,7 @@ fn collect_display_branches_with_nesting(
                     result,
                     max_column,
                     allowed,
+                    by_size,
                 );
             }
         } else if children.len() == 1 {
@@ -550,6 +606,7 @@ fn collect_display_branches_with_nesting(
                 result,
                 max_column,
                 allowed,
+                by_size,
             );
         }
     }
@@ -22,9 +22,21 @@ pub mod range_diff;
 pub mod redo;
 pub mod restack;
 pub mod split;
+pub mod stack;
 pub mod standup;
 pub mod status;
 pub mod submit;
 pub mod sync;
 pub mod undo;
 pub mod upstack;
+
+use colored::Colorize;
+
+/// First-run hint for commands that find nothing tracked besides trunk
+pub(crate) fn print_empty_stack_hint() {
+    println!(
+        "Create one with {} or track an existing branch with {}.",
+        "stax create".cyan(),
+        "stax branch track".cyan()
+    );
+}
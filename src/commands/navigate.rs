@@ -1,4 +1,5 @@
 use crate::engine::Stack;
+use crate::error::StaxError;
 use crate::git::{refs, GitRepo};
 use anyhow::{bail, Result};
 use colored::Colorize;
@@ -86,7 +87,7 @@ pub fn down(count: Option<usize>) -> Result<()> {
                             "Already at the bottom of the stack (on trunk).".dimmed()
                         );
                     } else {
-                        bail!("Branch '{}' has no tracked parent.", current);
+                        return Err(StaxError::NoParent(current).into());
                     }
                     return Ok(());
                 }
@@ -220,3 +221,100 @@ pub fn prev() -> Result<()> {
 
     Ok(())
 }
+
+/// Check out the branch named by `selector`: `top`, `bottom`, `trunk`,
+/// `parent`, `child`, or a branch name. Selectors resolve within the
+/// current stack; a fork prompts when interactive and errors otherwise.
+pub fn go(selector: &str) -> Result<()> {
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+
+    let target = match selector {
+        "trunk" => stack.trunk.clone(),
+        "parent" => stack.require_parent(&current)?,
+        "child" => {
+            let children = children_of(&stack, &current);
+            if children.is_empty() {
+                bail!("Branch '{}' has no tracked children.", current);
+            }
+            pick_child(&current, &children)?
+        }
+        "top" => {
+            let mut tip = current.clone();
+            loop {
+                let children = children_of(&stack, &tip);
+                if children.is_empty() {
+                    break tip;
+                }
+                tip = pick_child(&tip, &children)?;
+            }
+        }
+        "bottom" => stack
+            .current_stack(&current)
+            .into_iter()
+            .find(|b| b != &stack.trunk)
+            .ok_or_else(|| anyhow::anyhow!("No branches above trunk in the current stack."))?,
+        branch => {
+            if repo.branch_commit(branch).is_err() {
+                bail!(
+                    "'{}' is not a branch. Expected top, bottom, trunk, parent, child, or a branch name.",
+                    branch
+                );
+            }
+            branch.to_string()
+        }
+    };
+
+    if target == current {
+        println!("{}", format!("Already on '{}'.", current).dimmed());
+        return Ok(());
+    }
+
+    // Git refuses to check out a branch that another worktree holds
+    if let Some(path) = repo.branch_worktree_path(&target)? {
+        if path != repo.workdir()? {
+            bail!(
+                "Branch '{}' is checked out in another worktree at {}",
+                target,
+                path.display()
+            );
+        }
+    }
+
+    let _ = refs::write_prev_branch(repo.inner(), &current);
+    repo.checkout(&target)?;
+    println!("Switched to branch '{}'", target.bright_cyan());
+
+    Ok(())
+}
+
+fn children_of(stack: &Stack, branch: &str) -> Vec<String> {
+    let mut children = stack
+        .branches
+        .get(branch)
+        .map(|b| b.children.clone())
+        .unwrap_or_default();
+    children.sort();
+    children
+}
+
+/// Pick among a branch's children: the only one, a prompt, or an error when scripted
+fn pick_child(branch: &str, children: &[String]) -> Result<String> {
+    if children.len() == 1 {
+        return Ok(children[0].clone());
+    }
+    if !console::Term::stderr().is_term() {
+        bail!(
+            "Branch '{}' has multiple children ({}). Pass a branch name instead.",
+            branch,
+            children.join(", ")
+        );
+    }
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Multiple child branches - select one")
+        .items(children)
+        .default(0)
+        .interact()?;
+    Ok(children[selection].clone())
+}
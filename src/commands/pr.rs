@@ -1,9 +1,24 @@
+use crate::commands::ci::{fetch_all_checks, CheckRunInfo};
 use crate::config::Config;
-use crate::engine::Stack;
+use crate::engine::{BranchMetadata, Stack};
+use crate::error::StaxError;
 use crate::git::GitRepo;
+use crate::github::pr_body::{add_issue_link, issue_reference};
+use crate::github::GitHubClient;
 use crate::remote::RemoteInfo;
 use anyhow::Result;
 use colored::Colorize;
+use serde::Serialize;
+
+/// Check results for the current branch's PR head
+#[derive(Debug, Serialize)]
+struct PrChecks {
+    branch: String,
+    pr_number: u64,
+    head_sha: String,
+    overall_status: Option<String>,
+    checks: Vec<CheckRunInfo>,
+}
 
 /// Open the PR for the current branch in the default browser
 pub fn run() -> Result<()> {
@@ -12,27 +27,10 @@ pub fn run() -> Result<()> {
     let stack = Stack::load(&repo)?;
     let config = Config::load()?;
 
-    // Check if branch is tracked
-    let branch_info = stack.branches.get(&current);
-    if branch_info.is_none() {
-        anyhow::bail!(
-            "Branch '{}' is not tracked. Use {} to track it first.",
-            current,
-            "stax branch track".cyan()
-        );
-    }
-
-    // Check if branch has a PR
-    let pr_number = branch_info.and_then(|b| b.pr_number);
-    if pr_number.is_none() {
-        anyhow::bail!(
-            "No PR found for branch '{}'. Use {} to create one.",
-            current,
-            "stax submit".cyan()
-        );
-    }
-
-    let pr_number = pr_number.unwrap();
+    let pr_number = stack
+        .require(&current)?
+        .pr_number
+        .ok_or_else(|| StaxError::NoPr(current.clone()))?;
     let remote_info = RemoteInfo::from_repo(&repo, &config)?;
     let pr_url = remote_info.pr_url(pr_number);
 
@@ -62,3 +60,260 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
+
+/// List every check run and commit status on the current branch's PR head
+pub fn checks(json: bool) -> Result<()> {
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+    let config = Config::load()?;
+
+    let pr_number = stack
+        .branches
+        .get(&current)
+        .and_then(|b| b.pr_number)
+        .ok_or_else(|| StaxError::NoPr(current.clone()))?;
+
+    let remote = RemoteInfo::from_repo(&repo, &config)?;
+    let client = GitHubClient::new(remote.owner(), &remote.repo, remote.api_base_url.clone())?;
+    let rt = tokio::runtime::Runtime::new()?;
+
+    // CI runs against what GitHub has, which may lag the local branch
+    let head_sha = rt.block_on(client.get_pr_head_sha(pr_number))?;
+    let (overall_status, mut checks) = rt.block_on(fetch_all_checks(&repo, &client, &head_sha))?;
+    // Failing checks first; the sort is stable so the API order holds otherwise
+    checks.sort_by_key(|check| !is_failing(check));
+
+    let result = PrChecks {
+        branch: current,
+        pr_number,
+        head_sha,
+        overall_status,
+        checks,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    println!(
+        "PR #{} checks {}",
+        result.pr_number.to_string().bright_magenta(),
+        format!("({})", &result.head_sha[..result.head_sha.len().min(7)]).dimmed()
+    );
+
+    if result.checks.is_empty() {
+        println!("  {}", "No CI checks reported".dimmed());
+        return Ok(());
+    }
+
+    for check in &result.checks {
+        let state = check_state(check);
+        let line = format!("{} {}", state, check.name);
+        if is_failing(check) {
+            println!("  {} {}", "✗".red(), line.red().bold());
+        } else if check.conclusion.as_deref() == Some("success") {
+            println!("  {} {}", "✓".green(), line);
+        } else {
+            println!("  {} {}", "●".yellow(), line);
+        }
+        if let Some(url) = &check.url {
+            println!("      {}", url.dimmed());
+        }
+    }
+
+    let failing = result.checks.iter().filter(|c| is_failing(c)).count();
+    println!();
+    if failing > 0 {
+        println!(
+            "{}",
+            format!("{} of {} checks failing", failing, result.checks.len()).red()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("{} checks, none failing", result.checks.len()).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Close the current branch's PR without merging
+pub fn close() -> Result<()> {
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+
+    let pr_number = stack
+        .require(&current)?
+        .pr_number
+        .ok_or_else(|| StaxError::NoPr(current.clone()))?;
+
+    // Dependent PRs stay open but lose the base they were reviewed against
+    let dependents: Vec<(String, u64)> = stack
+        .descendants(&current)
+        .into_iter()
+        .filter_map(|b| {
+            let branch = stack.branches.get(&b)?;
+            let open = branch
+                .pr_state
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case("open"));
+            branch.pr_number.filter(|_| open).map(|n| (b, n))
+        })
+        .collect();
+    if !dependents.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Warning: {} open PR(s) stacked on '{}' depend on this one:",
+                dependents.len(),
+                current
+            )
+            .yellow()
+        );
+        for (branch, number) in &dependents {
+            println!("  {} {}", format!("#{}", number).dimmed(), branch);
+        }
+    }
+
+    set_pr_state(&repo, &current, pr_number, false)?;
+    println!("Closed PR {}", format!("#{}", pr_number).cyan());
+    Ok(())
+}
+
+/// Reopen the current branch's closed PR
+pub fn reopen() -> Result<()> {
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+
+    let pr_number = stack
+        .require(&current)?
+        .pr_number
+        .ok_or_else(|| StaxError::NoPr(current.clone()))?;
+
+    set_pr_state(&repo, &current, pr_number, true)?;
+    println!("Reopened PR {}", format!("#{}", pr_number).cyan());
+    Ok(())
+}
+
+/// Ensure the current branch's PR body closes `issue` on merge
+pub fn link(issue: &str) -> Result<()> {
+    let reference = issue_reference(issue)?;
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+    let config = Config::load()?;
+
+    let pr_number = stack
+        .require(&current)?
+        .pr_number
+        .ok_or_else(|| StaxError::NoPr(current.clone()))?;
+
+    let remote = RemoteInfo::from_repo(&repo, &config)?;
+    let client = GitHubClient::new(remote.owner(), &remote.repo, remote.api_base_url.clone())?;
+    let rt = tokio::runtime::Runtime::new()?;
+
+    let body = rt.block_on(client.get_pr_body(pr_number))?;
+    match add_issue_link(&body, &reference) {
+        Some(body) => {
+            rt.block_on(client.update_pr_body(pr_number, &body))?;
+            println!(
+                "✓ PR {} now closes {}",
+                format!("#{}", pr_number).cyan(),
+                reference.green()
+            );
+        }
+        None => println!(
+            "PR {} already closes {}",
+            format!("#{}", pr_number).cyan(),
+            reference
+        ),
+    }
+    Ok(())
+}
+
+/// Open or close the PR on GitHub, then mirror the new state into the branch metadata
+fn set_pr_state(repo: &GitRepo, branch: &str, pr_number: u64, open: bool) -> Result<()> {
+    let config = Config::load()?;
+    let remote = RemoteInfo::from_repo(repo, &config)?;
+    let client = GitHubClient::new(remote.owner(), &remote.repo, remote.api_base_url.clone())?;
+    let rt = tokio::runtime::Runtime::new()?;
+
+    if open {
+        rt.block_on(client.reopen_pr(pr_number))?;
+    } else {
+        rt.block_on(client.close_pr(pr_number))?;
+    }
+
+    if let Some(mut meta) = BranchMetadata::read(repo.inner(), branch)? {
+        if let Some(info) = meta.pr_info.as_mut() {
+            // Same spelling submit records from the API
+            info.state = if open { "Open" } else { "Closed" }.to_string();
+        }
+        meta.write(repo.inner(), branch)?;
+    }
+    Ok(())
+}
+
+fn is_failing(check: &CheckRunInfo) -> bool {
+    matches!(
+        check.conclusion.as_deref(),
+        Some("failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure")
+    )
+}
+
+/// Short label for a check: its conclusion once done, otherwise its status
+fn check_state(check: &CheckRunInfo) -> String {
+    let state = match check.conclusion.as_deref() {
+        Some(conclusion) if check.status == "completed" => conclusion,
+        _ => check.status.as_str(),
+    };
+    format!("{:<10}", state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(status: &str, conclusion: Option<&str>) -> CheckRunInfo {
+        CheckRunInfo {
+            name: "build".to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            url: None,
+            started_at: None,
+            completed_at: None,
+            elapsed_secs: None,
+            average_secs: None,
+            completion_percent: None,
+        }
+    }
+
+    #[test]
+    fn failing_checks_sort_first() {
+        let mut checks = [
+            check("completed", Some("success")),
+            check("in_progress", None),
+            check("completed", Some("timed_out")),
+        ];
+        checks.sort_by_key(|c| !is_failing(c));
+        assert_eq!(checks[0].conclusion.as_deref(), Some("timed_out"));
+        assert_eq!(checks[1].conclusion.as_deref(), Some("success"));
+    }
+
+    #[test]
+    fn check_state_prefers_conclusion_when_completed() {
+        assert_eq!(
+            check_state(&check("completed", Some("failure"))).trim(),
+            "failure"
+        );
+        assert_eq!(
+            check_state(&check("in_progress", None)).trim(),
+            "in_progress"
+        );
+    }
+}
@@ -60,7 +60,20 @@ pub fn run(stack_filter: Option<String>, all: bool) -> Result<()> {
             continue;
         }
 
-        let current_parent = repo.branch_commit(&meta.parent_branch_name)?;
+        if !meta.parent_revision_valid(repo.inner()) {
+            println!(
+                "\n{} {}",
+                "Range-diff".cyan(),
+                format!("{}..{}", meta.parent_branch_name, branch).bold()
+            );
+            println!(
+                "{}",
+                "  (recorded parent revision is invalid; run `stax stack fix`)".yellow()
+            );
+            continue;
+        }
+
+        let current_parent = repo.parent_commit(&meta.parent_branch_name)?;
 
         println!(
             "\n{} {}",
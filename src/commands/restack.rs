@@ -1,15 +1,30 @@
+use crate::config::Config;
 use crate::engine::{BranchMetadata, Stack};
-use crate::git::{GitRepo, RebaseResult};
+use crate::git::{GitRepo, RebaseOptions, RebaseResult};
 use crate::ops::receipt::{OpKind, PlanSummary};
 use crate::ops::tx::{self, Transaction};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm};
-
-pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Result<()> {
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    all: bool,
+    r#continue: bool,
+    quiet: bool,
+    verbose: bool,
+    jobs: usize,
+    dry_run: bool,
+    auto_stash_pop: bool,
+    onto_remote: bool,
+    rebase_options: RebaseOptions,
+) -> Result<()> {
     let repo = GitRepo::open()?;
-    let current = repo.current_branch()?;
-    let stack = Stack::load(&repo)?;
 
     if r#continue {
         crate::commands::continue_cmd::run()?;
@@ -17,9 +32,24 @@ pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Re
             return Ok(());
         }
     }
+    ensure_no_rebase_in_progress(&repo)?;
+
+    let current = repo.current_branch()?;
+    let mut stack = Stack::load(&repo)?;
+
+    if !stack.has_tracked_branches() {
+        if !quiet {
+            println!(
+                "{}",
+                "No tracked branches yet, nothing to restack.".dimmed()
+            );
+            super::print_empty_stack_hint();
+        }
+        return Ok(());
+    }
 
     let mut stashed = false;
-    if repo.is_dirty()? {
+    if !dry_run && repo.is_dirty()? {
         if auto_stash_pop {
             stashed = repo.stash_push()?;
             if stashed && !quiet {
@@ -43,6 +73,17 @@ pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Re
         }
     }
 
+    if onto_remote && current != stack.trunk {
+        if !rebase_onto_own_remote(&repo, &current, auto_stash_pop, &rebase_options, quiet)? {
+            if stashed && !quiet {
+                println!("{}", "Stash kept to avoid conflicts.".yellow());
+            }
+            return Ok(());
+        }
+        // Others' pushes may have moved the branch onto a newer parent
+        stack = Stack::load(&repo)?;
+    }
+
     // Determine the operation scope once, then evaluate restack status live per branch.
     let mut scope_branches: Vec<String> = if all {
         stack
@@ -88,6 +129,20 @@ pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Re
     } else {
         "branches"
     };
+    let summary = PlanSummary {
+        branches_to_rebase: scope_branches.len(),
+        branches_to_push: 0,
+        description: vec![format!(
+            "Restack up to {} {}",
+            scope_branches.len(),
+            branch_word
+        )],
+    };
+
+    if dry_run {
+        return print_dry_run(&repo, &stack, &scope_branches, OpKind::Restack, &summary);
+    }
+
     if !quiet {
         println!(
             "Restacking up to {} {}...",
@@ -99,20 +154,89 @@ pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Re
     // Begin transaction
     let mut tx = Transaction::begin(OpKind::Restack, &repo, quiet)?;
     tx.plan_branches(&repo, &scope_branches)?;
-    let summary = PlanSummary {
-        branches_to_rebase: scope_branches.len(),
-        branches_to_push: 0,
-        description: vec![format!(
-            "Restack up to {} {}",
-            scope_branches.len(),
-            branch_word
-        )],
-    };
     tx::print_plan(tx.kind(), &summary, quiet);
     tx.set_plan_summary(summary);
     tx.snapshot()?;
 
     let mut summary: Vec<(String, String)> = Vec::new();
+    let started = Instant::now();
+
+    let update_refs_chain = if rebase_options.update_refs {
+        let chain = update_refs_chain(&repo, &stack, &current)?;
+        if chain.is_none() && !quiet {
+            println!(
+                "{}",
+                "--update-refs needs a linear stack where only the bottom branch is behind (and git 2.38+); restacking branch by branch."
+                    .dimmed()
+            );
+        }
+        chain
+    } else {
+        None
+    };
+
+    if let Some(chain) = update_refs_chain {
+        let tip = &chain[chain.len() - 1];
+        let onto = stack.branches[&chain[0]]
+            .parent
+            .clone()
+            .unwrap_or_else(|| stack.trunk.clone());
+
+        if !quiet {
+            println!("  {} onto {}", chain.join(" → ").white(), onto.blue());
+        }
+
+        let rebase_started = Instant::now();
+        let result = repo.rebase_branch_onto_with(tip, &onto, auto_stash_pop, &rebase_options)?;
+        if verbose {
+            print_timing(&chain.join(", "), &result, rebase_started.elapsed());
+        }
+        match result {
+            RebaseResult::Success => {
+                // git moved the intermediate refs; bring their metadata along
+                refresh_parent_revisions(&repo, &chain)?;
+                for branch in &chain {
+                    tx.record_after(&repo, branch)?;
+                    summary.push((branch.clone(), "ok".to_string()));
+                }
+                if !quiet {
+                    println!("    {}", "✓ done".green());
+                }
+            }
+            RebaseResult::Conflict => {
+                if !quiet {
+                    println!("    {}", "✗ conflict".red());
+                    println!();
+                    println!("{}", "Resolve conflicts and run:".yellow());
+                    println!("  {}", "stax continue".cyan());
+                }
+                if stashed && !quiet {
+                    println!("{}", "Stash kept to avoid conflicts.".yellow());
+                }
+                tx.finish_err("Rebase conflict", Some("rebase"), Some(tip))?;
+                return Ok(());
+            }
+        }
+    }
+
+    // Per-branch rebases must not drag unrelated refs along
+    let rebase_options = RebaseOptions {
+        update_refs: false,
+        ..rebase_options
+    };
+
+    if jobs > 1 {
+        restack_in_parallel(
+            &repo,
+            &mut tx,
+            &scope_branches,
+            jobs,
+            &rebase_options,
+            quiet,
+            verbose,
+            &mut summary,
+        )?;
+    }
 
     for branch in &scope_branches {
         let live_stack = Stack::load(&repo)?;
@@ -140,15 +264,22 @@ pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Re
         }
 
         // Rebase onto parent in the branch's own worktree when needed.
-        match repo.rebase_branch_onto(branch, &meta.parent_branch_name, auto_stash_pop)? {
+        let rebase_started = Instant::now();
+        let result = repo.rebase_branch_onto_with(
+            branch,
+            &meta.parent_branch_name,
+            auto_stash_pop,
+            &rebase_options,
+        )?;
+        if verbose {
+            print_timing(branch, &result, rebase_started.elapsed());
+        }
+        match result {
             RebaseResult::Success => {
                 // Update metadata with new parent revision
-                let new_parent_rev = repo.branch_commit(&meta.parent_branch_name)?;
-                let updated_meta = BranchMetadata {
-                    parent_branch_revision: new_parent_rev,
-                    ..meta
-                };
-                updated_meta.write(repo.inner(), branch)?;
+                let new_parent_rev = repo.parent_commit(&meta.parent_branch_name)?;
+                meta.restacked_onto(new_parent_rev)
+                    .write(repo.inner(), branch)?;
 
                 // Record the after-OID for this branch
                 tx.record_after(&repo, branch)?;
@@ -199,6 +330,23 @@ pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Re
         }
     }
 
+    if verbose {
+        println!(
+            "{}",
+            format!(
+                "Restacked {} {} in {}",
+                summary.len(),
+                if summary.len() == 1 {
+                    "branch"
+                } else {
+                    "branches"
+                },
+                format_duration(started.elapsed())
+            )
+            .dimmed()
+        );
+    }
+
     // Check for merged branches and offer to delete them
     cleanup_merged_branches(&repo, quiet)?;
 
@@ -212,6 +360,546 @@ pub fn run(all: bool, r#continue: bool, quiet: bool, auto_stash_pop: bool) -> Re
     Ok(())
 }
 
+/// `--verbose` timing line for one rebase
+fn print_timing(branches: &str, result: &RebaseResult, elapsed: Duration) {
+    let outcome = match result {
+        RebaseResult::Success => "restacked",
+        RebaseResult::Conflict => "stopped on conflict in",
+    };
+    println!(
+        "    {}",
+        format!("{} {} in {}", outcome, branches, format_duration(elapsed)).dimmed()
+    );
+}
+
+fn format_duration(elapsed: Duration) -> String {
+    format!("{:.1}s", elapsed.as_secs_f64())
+}
+
+/// Fetch `branch` and rebase it onto its own remote so commits others pushed
+/// to it are kept. Returns false when the rebase stopped on conflicts.
+fn rebase_onto_own_remote(
+    repo: &GitRepo,
+    branch: &str,
+    auto_stash_pop: bool,
+    rebase_options: &RebaseOptions,
+    quiet: bool,
+) -> Result<bool> {
+    let config = Config::load()?;
+    let remote_name = config.remote_name();
+    let remote_ref = format!("{}/{}", remote_name, branch);
+
+    let output = Command::new("git")
+        .args([
+            "fetch",
+            remote_name,
+            &format!("+refs/heads/{}:refs/remotes/{}", branch, remote_ref),
+        ])
+        .current_dir(repo.workdir()?)
+        .output()
+        .context("Failed to fetch")?;
+    if !output.status.success() {
+        if !quiet {
+            println!(
+                "{}",
+                format!(
+                    "'{}' has no branch on {}; skipping --onto-remote.",
+                    branch, remote_name
+                )
+                .dimmed()
+            );
+        }
+        return Ok(true);
+    }
+
+    let (_, behind) = repo.commits_ahead_behind(&remote_ref, branch)?;
+    if behind == 0 {
+        if !quiet {
+            println!(
+                "{}",
+                format!("'{}' already includes {}.", branch, remote_ref).dimmed()
+            );
+        }
+        return Ok(true);
+    }
+
+    let mut tx = Transaction::begin(OpKind::Restack, repo, quiet)?;
+    tx.plan_branches(repo, &[branch.to_string()])?;
+    let summary = PlanSummary {
+        branches_to_rebase: 1,
+        branches_to_push: 0,
+        description: vec![format!("Rebase '{}' onto '{}'", branch, remote_ref)],
+    };
+    tx::print_plan(tx.kind(), &summary, quiet);
+    tx.set_plan_summary(summary);
+    tx.snapshot()?;
+
+    if !quiet {
+        println!(
+            "  {} onto {} ({} new remote {})",
+            branch.white(),
+            remote_ref.blue(),
+            behind,
+            if behind == 1 { "commit" } else { "commits" }
+        );
+    }
+
+    match repo.rebase_branch_onto_with(branch, &remote_ref, auto_stash_pop, rebase_options)? {
+        RebaseResult::Success => {
+            tx.record_after(repo, branch)?;
+            tx.finish_ok()?;
+            if !quiet {
+                println!("    {}", "✓ done".green());
+            }
+            Ok(true)
+        }
+        RebaseResult::Conflict => {
+            if !quiet {
+                println!("    {}", "✗ conflict".red());
+                println!();
+                println!("{}", "Resolve conflicts and run:".yellow());
+                println!("  {}", "stax restack --continue".cyan());
+            }
+            tx.finish_err("Rebase conflict", Some("rebase"), Some(branch))?;
+            Ok(false)
+        }
+    }
+}
+
+/// Restack the current linear stack with a single `git rebase -i --update-refs`,
+/// letting the user edit the combined todo list in their sequence editor.
+pub fn run_interactive() -> Result<()> {
+    if !git_supports_update_refs() {
+        anyhow::bail!(
+            "restack --interactive needs git 2.38 or newer for `git rebase --update-refs`."
+        );
+    }
+    let repo = GitRepo::open()?;
+    ensure_no_rebase_in_progress(&repo)?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+    let workdir = repo.workdir()?;
+
+    if current == stack.trunk {
+        anyhow::bail!("You are on trunk. Check out a branch in the stack to restack.");
+    }
+    if !stack.branches.contains_key(&current) {
+        anyhow::bail!(
+            "Branch '{}' is not tracked. Run `stax branch track` first.",
+            current
+        );
+    }
+    if repo.is_dirty()? {
+        anyhow::bail!("Working tree is dirty. Please stash or commit changes first.");
+    }
+
+    let chain = stack.linear_stack(&current)?;
+    let tip = chain[chain.len() - 1].clone();
+
+    // One rebase of the tip can only carry the other branches along if they
+    // already sit on each other; only the bottom may lag behind trunk.
+    let stale: Vec<&str> = chain[1..]
+        .iter()
+        .filter(|b| stack.branches[*b].needs_restack)
+        .map(String::as_str)
+        .collect();
+    if !stale.is_empty() {
+        anyhow::bail!(
+            "Branches need a regular restack first: {}. Run `stax restack`, then retry with --interactive.",
+            stale.join(", ")
+        );
+    }
+
+    for branch in &chain {
+        if let Some(path) = repo.branch_worktree_path(branch)? {
+            if path != workdir {
+                anyhow::bail!(
+                    "Branch '{}' is checked out in another worktree at {}",
+                    branch,
+                    path.display()
+                );
+            }
+        }
+    }
+
+    println!(
+        "Interactive restack of {} onto {}",
+        chain.join(" → ").cyan(),
+        stack.trunk.blue()
+    );
+
+    let mut tx = Transaction::begin(OpKind::Restack, &repo, false)?;
+    tx.plan_branches(&repo, &chain)?;
+    let summary = PlanSummary {
+        branches_to_rebase: chain.len(),
+        branches_to_push: 0,
+        description: vec![format!(
+            "Interactive rebase of {} {} onto {}",
+            chain.len(),
+            if chain.len() == 1 {
+                "branch"
+            } else {
+                "branches"
+            },
+            stack.trunk
+        )],
+    };
+    tx::print_plan(tx.kind(), &summary, false);
+    tx.set_plan_summary(summary);
+    tx.snapshot()?;
+
+    if current != tip {
+        repo.checkout(&tip)?;
+    }
+
+    // Stdio is inherited so GIT_SEQUENCE_EDITOR / core.editor can take over the terminal
+    let status = Command::new("git")
+        .args(["rebase", "--interactive", "--update-refs", &stack.trunk])
+        .current_dir(workdir)
+        .status()
+        .context("Failed to run git rebase --interactive")?;
+
+    if !status.success() {
+        if repo.rebase_in_progress()? {
+            println!("{}", "✗ rebase stopped".red());
+            println!();
+            println!("{}", "Resolve conflicts and run:".yellow());
+            println!("  {}", "stax continue".cyan());
+            tx.finish_err("Rebase conflict", Some("rebase"), Some(&tip))?;
+            return Ok(());
+        }
+        tx.finish_err("Interactive rebase failed", Some("rebase"), Some(&tip))?;
+        anyhow::bail!("git rebase --interactive failed");
+    }
+
+    refresh_parent_revisions(&repo, &chain)?;
+    for branch in &chain {
+        if repo.branch_commit(branch).is_ok() {
+            tx.record_after(&repo, branch)?;
+        }
+    }
+
+    if repo.current_branch()? != current && repo.branch_commit(&current).is_ok() {
+        repo.checkout(&current)?;
+    }
+    tx.finish_ok()?;
+
+    println!("{}", "✓ Stack restacked successfully!".green());
+    Ok(())
+}
+
+/// The current linear stack, if one `git rebase --update-refs` of its tip can
+/// restack all of it: only the bottom may be behind its parent, and no branch
+/// may be checked out in another worktree (git won't move those refs).
+fn update_refs_chain(repo: &GitRepo, stack: &Stack, current: &str) -> Result<Option<Vec<String>>> {
+    if current == stack.trunk || !git_supports_update_refs() {
+        return Ok(None);
+    }
+    let Ok(chain) = stack.linear_stack(current) else {
+        return Ok(None);
+    };
+    if chain[1..].iter().any(|b| stack.branches[b].needs_restack) {
+        return Ok(None);
+    }
+    let workdir = repo.workdir()?;
+    for branch in &chain {
+        if let Some(path) = repo.branch_worktree_path(branch)? {
+            if path != workdir {
+                return Ok(None);
+            }
+        }
+    }
+    Ok(Some(chain))
+}
+
+/// `git rebase --update-refs` first shipped in git 2.38
+fn git_supports_update_refs() -> bool {
+    Command::new("git")
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| parse_git_version(&String::from_utf8_lossy(&output.stdout)))
+        .is_some_and(|version| version >= (2, 38))
+}
+
+/// Parse `git version 2.43.0` (or `git version 2.39.3 (Apple Git-146)`) into (major, minor)
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.trim().strip_prefix("git version ")?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Refuse to start a rebase-based operation on top of one that is unfinished
+pub fn ensure_no_rebase_in_progress(repo: &GitRepo) -> Result<()> {
+    if repo.rebase_in_progress()? {
+        anyhow::bail!(
+            "A rebase is already in progress.\n\
+             Resolve any conflicts and run `stax continue`, or run `git rebase --abort` to cancel it."
+        );
+    }
+    Ok(())
+}
+
+/// Point each branch's recorded parent revision at its parent's current tip,
+/// for branches that already contain that tip (i.e. are stacked on it).
+pub fn refresh_parent_revisions(repo: &GitRepo, branches: &[String]) -> Result<()> {
+    for branch in branches {
+        let Some(meta) = BranchMetadata::read(repo.inner(), branch)? else {
+            continue;
+        };
+        let (Ok(parent_tip), Ok(_)) = (
+            repo.parent_commit(&meta.parent_branch_name),
+            repo.branch_commit(branch),
+        ) else {
+            continue;
+        };
+        if meta.parent_branch_revision == parent_tip {
+            continue;
+        }
+        if repo.merge_base(&meta.parent_branch_name, branch)? == parent_tip {
+            meta.restacked_onto(parent_tip)
+                .write(repo.inner(), branch)?;
+        }
+    }
+    Ok(())
+}
+
+/// `--dry-run`: print the plan and each branch that would move, with the number of
+/// commits it carries, then stop before any snapshot, checkout, or ref write
+pub(crate) fn print_dry_run(
+    repo: &GitRepo,
+    stack: &Stack,
+    scope: &[String],
+    kind: OpKind,
+    summary: &PlanSummary,
+) -> Result<()> {
+    println!("{}", "Dry run: nothing will be rebased.".dimmed());
+    tx::print_plan(&kind, summary, false);
+
+    // A branch also moves when its parent is rebased earlier in the same run
+    let mut moving: HashSet<&str> = HashSet::new();
+    for branch in scope {
+        let Some(info) = stack.branches.get(branch) else {
+            continue;
+        };
+        let parent = info.parent.as_deref().unwrap_or(&stack.trunk);
+        if !info.needs_restack && !moving.contains(parent) {
+            continue;
+        }
+        moving.insert(branch);
+
+        let (commits, _) = repo.commits_ahead_behind(parent, branch)?;
+        println!(
+            "  {} onto {} ({} {})",
+            branch.white(),
+            parent.blue(),
+            commits,
+            if commits == 1 { "commit" } else { "commits" }
+        );
+    }
+
+    Ok(())
+}
+
+/// One branch of a chain handed to a `--jobs` worker
+struct ChainStep {
+    branch: String,
+    parent: String,
+    needs_restack: bool,
+}
+
+/// What a `--jobs` worker did with one branch
+struct StepOutcome {
+    branch: String,
+    parent: String,
+    result: RebaseResult,
+    elapsed: Duration,
+}
+
+/// `--jobs`: rebase independent chains side by side in scratch worktrees, then record
+/// the metadata here. Chains that hit a conflict, or include a branch checked out in
+/// some worktree, are left for the sequential pass, which stops on the conflict in this
+/// worktree so `stax continue` works as usual.
+#[allow(clippy::too_many_arguments)]
+fn restack_in_parallel(
+    repo: &GitRepo,
+    tx: &mut Transaction,
+    scope: &[String],
+    jobs: usize,
+    options: &RebaseOptions,
+    quiet: bool,
+    verbose: bool,
+    summary: &mut Vec<(String, String)>,
+) -> Result<()> {
+    let stack = Stack::load(repo)?;
+    let mut checked_out: HashSet<String> =
+        repo.branches_in_other_worktrees()?.into_keys().collect();
+    checked_out.insert(repo.current_branch()?);
+
+    let chains: Vec<Vec<ChainStep>> = independent_chains(&stack, scope)
+        .into_iter()
+        .filter(|chain| chain.iter().any(|b| stack.branches[b].needs_restack))
+        .filter(|chain| !chain.iter().any(|b| checked_out.contains(b)))
+        .map(|chain| {
+            chain
+                .into_iter()
+                .map(|branch| ChainStep {
+                    parent: stack.branches[&branch]
+                        .parent
+                        .clone()
+                        .unwrap_or_else(|| stack.trunk.clone()),
+                    needs_restack: stack.branches[&branch].needs_restack,
+                    branch,
+                })
+                .collect()
+        })
+        .collect();
+    if chains.len() < 2 {
+        return Ok(());
+    }
+
+    let workers = jobs.min(chains.len());
+    if !quiet {
+        println!(
+            "  Rebasing {} independent stacks with {} jobs...",
+            chains.len().to_string().cyan(),
+            workers
+        );
+    }
+
+    let mut worktrees = Vec::new();
+    for i in 0..workers {
+        match repo.add_scratch_worktree(&format!("restack-{}", i)) {
+            Ok(path) => worktrees.push(path),
+            Err(err) => {
+                for path in &worktrees {
+                    let _ = repo.remove_worktree(path);
+                }
+                return Err(err);
+            }
+        }
+    }
+
+    let queue = Mutex::new(VecDeque::from(chains));
+    let outcomes: Vec<StepOutcome> = std::thread::scope(|scope| {
+        let handles: Vec<_> = worktrees
+            .iter()
+            .map(|path| {
+                let queue = &queue;
+                scope.spawn(move || {
+                    let mut outcomes = Vec::new();
+                    loop {
+                        let next = queue.lock().unwrap().pop_front();
+                        let Some(chain) = next else {
+                            break;
+                        };
+                        outcomes.extend(rebase_chain(path, &chain, options));
+                    }
+                    outcomes
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("restack worker panicked"))
+            .collect()
+    });
+
+    for path in &worktrees {
+        repo.remove_worktree(path)?;
+    }
+
+    for outcome in outcomes {
+        if !quiet {
+            println!(
+                "  {} onto {}",
+                outcome.branch.white(),
+                outcome.parent.blue()
+            );
+        }
+        if verbose {
+            print_timing(&outcome.branch, &outcome.result, outcome.elapsed);
+        }
+        if outcome.result == RebaseResult::Conflict {
+            if !quiet {
+                println!("    {}", "conflict in parallel run; retrying here".yellow());
+            }
+            continue;
+        }
+
+        if let Some(meta) = BranchMetadata::read(repo.inner(), &outcome.branch)? {
+            let new_parent_rev = repo.parent_commit(&outcome.parent)?;
+            meta.restacked_onto(new_parent_rev)
+                .write(repo.inner(), &outcome.branch)?;
+        }
+        tx.record_after(repo, &outcome.branch)?;
+        if !quiet {
+            println!("    {}", "✓ done".green());
+        }
+        summary.push((outcome.branch, "ok".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Rebase one chain parent-first in the worktree at `path`, stopping at the first conflict
+fn rebase_chain(path: &Path, chain: &[ChainStep], options: &RebaseOptions) -> Vec<StepOutcome> {
+    let mut rebased = HashSet::new();
+    let mut outcomes = Vec::new();
+
+    for step in chain {
+        if !step.needs_restack && !rebased.contains(&step.parent) {
+            continue;
+        }
+        let started = Instant::now();
+        // Any failure is retried by the sequential pass, which reports it properly
+        let result = GitRepo::rebase_in_worktree(path, &step.branch, &step.parent, options)
+            .unwrap_or(RebaseResult::Conflict);
+        let conflict = result == RebaseResult::Conflict;
+        outcomes.push(StepOutcome {
+            branch: step.branch.clone(),
+            parent: step.parent.clone(),
+            result,
+            elapsed: started.elapsed(),
+        });
+        if conflict {
+            break;
+        }
+        rebased.insert(step.branch.clone());
+    }
+
+    outcomes
+}
+
+/// Split `scope` into chains that share no branches: each branch whose parent is outside
+/// the scope (usually trunk) together with its descendants, in scope order
+fn independent_chains(stack: &Stack, scope: &[String]) -> Vec<Vec<String>> {
+    scope
+        .iter()
+        .filter(|branch| {
+            stack
+                .branches
+                .get(*branch)
+                .and_then(|b| b.parent.as_ref())
+                .is_none_or(|parent| !scope.contains(parent))
+        })
+        .map(|root| {
+            let members: HashSet<String> = stack
+                .descendants(root)
+                .into_iter()
+                .chain(std::iter::once(root.clone()))
+                .collect();
+            scope
+                .iter()
+                .filter(|b| members.contains(*b))
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
 fn branches_needing_restack(stack: &Stack, scope: &[String]) -> Vec<String> {
     scope
         .iter()
@@ -282,3 +970,18 @@ fn cleanup_merged_branches(repo: &GitRepo, quiet: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_version() {
+        assert_eq!(parse_git_version("git version 2.43.0\n"), Some((2, 43)));
+        assert_eq!(
+            parse_git_version("git version 2.39.3 (Apple Git-146)"),
+            Some((2, 39))
+        );
+        assert_eq!(parse_git_version("not git"), None);
+    }
+}
@@ -1,12 +1,18 @@
-use crate::engine::Stack;
+use crate::engine::{BranchMetadata, Stack};
 use crate::git::GitRepo;
+use crate::ops::receipt::{OpKind, PlanSummary};
+use crate::ops::tx::{self, Transaction};
 use crate::tui;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use std::io::IsTerminal;
+use std::process::Command;
 
-/// Split the current branch into multiple stacked branches
-pub fn run() -> Result<()> {
+/// Split the current branch into multiple stacked branches.
+///
+/// Each `at` entry is `<commit>:<name>`: a new branch `name` ending at `commit`.
+/// Without any, the interactive picker opens.
+pub fn run(at: Vec<String>) -> Result<()> {
     let repo = GitRepo::open()?;
     let stack = Stack::load(&repo)?;
     let current = repo.current_branch()?;
@@ -53,11 +59,152 @@ pub fn run() -> Result<()> {
         );
     }
 
+    if !at.is_empty() {
+        let tips = plan_split(&repo, parent, &current, &at)?;
+        return apply(&repo, &current, parent, &tips);
+    }
+
     // Validate: interactive terminal required for TUI
     if !std::io::stdin().is_terminal() {
-        anyhow::bail!("Split requires an interactive terminal.");
+        anyhow::bail!(
+            "Split requires an interactive terminal. Use {} to split without one.",
+            "--at <commit>:<name>".cyan()
+        );
     }
 
     // Launch split TUI
     tui::split::run()
 }
+
+/// Resolve `--at` entries into `(commit, name)` tips, oldest first, checking that the
+/// ranges between them cover every commit on the branch exactly once
+fn plan_split(
+    repo: &GitRepo,
+    parent: &str,
+    branch: &str,
+    at: &[String],
+) -> Result<Vec<(String, String)>> {
+    // Oldest first, like the picker shows them
+    let output = Command::new("git")
+        .args(["rev-list", "--reverse", &format!("{}..{}", parent, branch)])
+        .current_dir(repo.workdir()?)
+        .output()
+        .context("Failed to list commits")?;
+    let commits: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let existing = repo.list_branches()?;
+
+    let mut tips: Vec<(usize, String)> = Vec::new();
+    for entry in at {
+        let Some((rev, name)) = entry.rsplit_once(':') else {
+            bail!("Invalid --at '{}'. Use <commit>:<branch-name>.", entry);
+        };
+        let name = name.trim();
+        if !git2::Branch::name_is_valid(name)? {
+            bail!("'{}' is not a valid branch name.", name);
+        }
+        if existing.iter().any(|b| b == name) || tips.iter().any(|(_, n)| n == name) {
+            bail!("Branch '{}' already exists.", name);
+        }
+
+        let sha = repo.rev_parse(rev.trim())?;
+        let Some(index) = commits.iter().position(|c| c == &sha) else {
+            bail!(
+                "Commit '{}' is not on '{}' above '{}'.",
+                rev,
+                branch,
+                parent
+            );
+        };
+        if index + 1 == commits.len() {
+            bail!(
+                "Commit '{}' is the tip of '{}'; leave at least one commit on '{}'.",
+                rev,
+                branch,
+                branch
+            );
+        }
+        if let Some((_, other)) = tips.iter().find(|(i, _)| *i == index) {
+            bail!(
+                "Commit '{}' ends both '{}' and '{}'; each range needs its own commits.",
+                rev,
+                other,
+                name
+            );
+        }
+        tips.push((index, name.to_string()));
+    }
+
+    tips.sort_by_key(|(index, _)| *index);
+    Ok(tips
+        .into_iter()
+        .map(|(index, name)| (commits[index].clone(), name))
+        .collect())
+}
+
+/// Create a branch at each `(commit, name)` tip, oldest first, stacked on one another
+/// between `parent` and `branch`. `branch` keeps the commits above the last tip, so its
+/// children stay on top of the new stack. Nothing is rebased.
+pub(crate) fn apply(
+    repo: &GitRepo,
+    branch: &str,
+    parent: &str,
+    tips: &[(String, String)],
+) -> Result<()> {
+    if tips.is_empty() {
+        return Ok(());
+    }
+
+    let new_branches: Vec<String> = tips.iter().map(|(_, name)| name.clone()).collect();
+
+    // Begin transaction
+    let mut tx = Transaction::begin(OpKind::Split, repo, false)?;
+    let mut affected = new_branches.clone();
+    affected.push(branch.to_string());
+    tx.plan_branches(repo, &affected)?;
+
+    let summary = PlanSummary {
+        branches_to_rebase: 0,
+        branches_to_push: 0,
+        description: vec![format!("Split into {} new branches", new_branches.len())],
+    };
+    tx::print_plan(tx.kind(), &summary, false);
+    tx.set_plan_summary(summary);
+    tx.snapshot()?;
+
+    // Create branches at split points
+    let mut prev_parent = parent.to_string();
+    for (commit, name) in tips {
+        repo.create_branch_at_commit(name, commit)?;
+
+        // Create metadata for the new branch
+        let parent_rev = repo.branch_commit(&prev_parent)?;
+        BranchMetadata::new(&prev_parent, &parent_rev).write(repo.inner(), name)?;
+        tx.record_after(repo, name)?;
+
+        let (count, _) = repo.commits_ahead_behind(&prev_parent, name)?;
+        println!("Created branch '{}' with {} commits", name, count);
+
+        prev_parent = name.clone();
+    }
+
+    // Update the original branch's parent to the last split branch
+    let parent_rev = repo.branch_commit(&prev_parent)?;
+    if let Some(meta) = BranchMetadata::read(repo.inner(), branch)? {
+        BranchMetadata {
+            parent_branch_name: prev_parent.clone(),
+            parent_branch_revision: parent_rev.clone(),
+            fork_point: Some(parent_rev),
+            ..meta
+        }
+        .write(repo.inner(), branch)?;
+    }
+    println!("Updated '{}' parent to '{}'", branch, prev_parent);
+
+    tx.finish_ok()?;
+    println!("\nSplit complete! Use `stax status` to see the new stack structure.");
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::commands::diff::{page_diff, print_diff, DiffMode};
+use crate::engine::Stack;
+use crate::git::GitRepo;
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::io::IsTerminal;
+
+/// Show everything the stack changes against trunk, as a single PR would
+pub fn run(tip: Option<String>, mode: DiffMode, no_pager: bool) -> Result<()> {
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+    let workdir = repo.workdir()?;
+
+    let tips = match tip {
+        Some(tip) => {
+            if tip == stack.trunk || !stack.branches.contains_key(&tip) {
+                bail!("Branch '{}' is not tracked in the stack.", tip);
+            }
+            vec![tip]
+        }
+        None => {
+            if current == stack.trunk {
+                bail!("You are on trunk. Check out a branch in the stack or pass --tip.");
+            }
+            if !stack.branches.contains_key(&current) {
+                bail!(
+                    "Branch '{}' is not tracked. Run `stax branch track` first.",
+                    current
+                );
+            }
+            let mut leaves: Vec<String> = std::iter::once(current.clone())
+                .chain(stack.descendants(&current))
+                .filter(|b| stack.branches[b].children.is_empty())
+                .collect();
+            leaves.sort();
+            leaves
+        }
+    };
+
+    // A single tip goes straight to git so its pager applies; several are printed inline
+    if tips.len() == 1 && !no_pager && std::io::stdout().is_terminal() {
+        return page_diff(workdir, &cumulative_range(&stack, &tips[0]), mode);
+    }
+
+    if tips.len() > 1 {
+        println!(
+            "{}",
+            format!(
+                "Stack forks into {} tips; showing each. Pass --tip to pick one.",
+                tips.len()
+            )
+            .dimmed()
+        );
+    }
+
+    for tip in &tips {
+        let branch_count = stack
+            .ancestors(tip)
+            .iter()
+            .filter(|b| *b != &stack.trunk)
+            .count()
+            + 1;
+        println!(
+            "\n{} {} {}",
+            "Stack diff".cyan(),
+            format!("{}..{}", stack.trunk, tip).bold(),
+            format!(
+                "({} branch{})",
+                branch_count,
+                if branch_count == 1 { "" } else { "es" }
+            )
+            .dimmed()
+        );
+        print_diff(workdir, &cumulative_range(&stack, tip), mode)?;
+    }
+
+    Ok(())
+}
+
+/// Diff from where the stack forked off trunk, so trunk's newer commits don't show up
+fn cumulative_range(stack: &Stack, tip: &str) -> String {
+    format!("{}...{}", stack.trunk, tip)
+}
@@ -0,0 +1,68 @@
+use crate::engine::{BranchMetadata, RepairReason};
+use crate::git::{refs, GitRepo};
+use anyhow::Result;
+use colored::Colorize;
+
+/// Repair tracked branches whose metadata can't be used as-is
+pub fn run() -> Result<()> {
+    let repo = GitRepo::open()?;
+    let mut branches = refs::list_metadata_branches(repo.inner())?;
+    branches.sort();
+
+    let mut fixed = 0;
+    let mut unfixable = Vec::new();
+
+    for branch in &branches {
+        if repo.branch_commit(branch).is_err() {
+            continue;
+        }
+        let Some(meta) = BranchMetadata::read(repo.inner(), branch)? else {
+            continue;
+        };
+
+        match meta.repair_reason(repo.inner()) {
+            None => {}
+            Some(RepairReason::ParentMissing) => unfixable.push((branch, meta.parent_branch_name)),
+            Some(RepairReason::InvalidParentRevision) => {
+                // Same base `branch reparent` records: where the branch forked from its parent
+                let base = repo
+                    .merge_base(&meta.parent_branch_name, branch)
+                    .or_else(|_| repo.parent_commit(&meta.parent_branch_name))?;
+                let fork_point = meta
+                    .fork_point
+                    .clone()
+                    .filter(|rev| repo.rev_parse(rev).is_ok())
+                    .unwrap_or_else(|| base.clone());
+                BranchMetadata {
+                    parent_branch_revision: base.clone(),
+                    fork_point: Some(fork_point),
+                    ..meta
+                }
+                .write(repo.inner(), branch)?;
+
+                println!(
+                    "✓ Recomputed parent revision of '{}' ({})",
+                    branch.green(),
+                    base[..base.len().min(7)].dimmed()
+                );
+                fixed += 1;
+            }
+        }
+    }
+
+    for (branch, parent) in &unfixable {
+        println!(
+            "{} '{}' has missing parent '{}'. Run {} to pick a new one.",
+            "⚠".yellow(),
+            branch,
+            parent,
+            format!("stax branch reparent --branch {}", branch).cyan()
+        );
+    }
+
+    if fixed == 0 && unfixable.is_empty() {
+        println!("{}", "Stack metadata is healthy; nothing to fix.".dimmed());
+    }
+
+    Ok(())
+}
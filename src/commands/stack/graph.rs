@@ -0,0 +1,139 @@
+use crate::engine::Stack;
+use crate::git::GitRepo;
+use anyhow::Result;
+
+pub fn run(dot: bool) -> Result<()> {
+    if !dot {
+        anyhow::bail!("Please specify an output format. Usage: stax stack graph --dot");
+    }
+
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+
+    print!("{}", render_dot(&stack, &current));
+    Ok(())
+}
+
+/// Render the stack as a Graphviz digraph rooted at trunk (edges point parent -> child)
+fn render_dot(stack: &Stack, current: &str) -> String {
+    let mut out = String::from("digraph stax {\n");
+    out.push_str("  rankdir=TB;\n");
+    out.push_str("  node [shape=box, style=rounded, fontname=\"Helvetica\"];\n");
+
+    // Walk from trunk so nodes appear parent-first, with siblings sorted for stable output
+    let mut order = Vec::new();
+    let mut queue = vec![stack.trunk.clone()];
+    while let Some(name) = queue.pop() {
+        let Some(branch) = stack.branches.get(&name) else {
+            continue;
+        };
+        order.push(name);
+        let mut children = branch.children.clone();
+        children.sort_by(|a, b| b.cmp(a));
+        queue.extend(children);
+    }
+
+    for name in &order {
+        let branch = &stack.branches[name];
+        let mut label = escape(name);
+        if let Some(number) = branch.pr_number {
+            label.push_str(&format!("\\n#{}", number));
+        }
+        if branch.needs_restack {
+            label.push_str("\\n(needs restack)");
+        }
+
+        let mut attrs = vec![format!("label=\"{}\"", label)];
+        if name == &stack.trunk {
+            attrs.push("shape=box3d".to_string());
+        }
+        if name == current {
+            attrs.push("penwidth=2".to_string());
+        }
+        if branch.needs_restack {
+            attrs.push("color=orange".to_string());
+        }
+        out.push_str(&format!("  \"{}\" [{}];\n", escape(name), attrs.join(", ")));
+    }
+
+    for name in &order {
+        if let Some(parent) = stack.branches[name].parent.as_ref() {
+            if stack.branches.contains_key(parent) {
+                out.push_str(&format!(
+                    "  \"{}\" -> \"{}\";\n",
+                    escape(parent),
+                    escape(name)
+                ));
+            }
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::stack::StackBranch;
+    use std::collections::HashMap;
+
+    fn branch(name: &str, parent: Option<&str>, children: &[&str]) -> StackBranch {
+        StackBranch {
+            name: name.to_string(),
+            parent: parent.map(str::to_string),
+            children: children.iter().map(|c| c.to_string()).collect(),
+            needs_restack: false,
+            pr_number: None,
+            pr_state: None,
+            pr_is_draft: None,
+        }
+    }
+
+    fn test_stack() -> Stack {
+        let mut branches = HashMap::new();
+        branches.insert("main".to_string(), branch("main", None, &["b", "a"]));
+        let mut a = branch("a", Some("main"), &["a2"]);
+        a.pr_number = Some(12);
+        branches.insert("a".to_string(), a);
+        let mut a2 = branch("a2", Some("a"), &[]);
+        a2.needs_restack = true;
+        branches.insert("a2".to_string(), a2);
+        branches.insert("b".to_string(), branch("b", Some("main"), &[]));
+        Stack {
+            branches,
+            trunk: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_dot_edges_and_labels() {
+        let dot = render_dot(&test_stack(), "a2");
+        assert!(dot.starts_with("digraph stax {"));
+        assert!(dot.contains("\"main\" -> \"a\";"));
+        assert!(dot.contains("\"main\" -> \"b\";"));
+        assert!(dot.contains("\"a\" -> \"a2\";"));
+        assert!(dot.contains("label=\"a\\n#12\""));
+        assert!(dot.contains("label=\"a2\\n(needs restack)\", penwidth=2, color=orange"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_render_dot_orders_parent_first() {
+        let dot = render_dot(&test_stack(), "main");
+        let pos = |needle: &str| dot.find(needle).unwrap();
+        assert!(pos("\"main\" [") < pos("\"a\" ["));
+        assert!(pos("\"a\" [") < pos("\"a2\" ["));
+        assert!(pos("\"a2\" [") < pos("\"b\" ["));
+    }
+
+    #[test]
+    fn test_escape_quotes() {
+        assert_eq!(escape("a\"b"), "a\\\"b");
+    }
+}
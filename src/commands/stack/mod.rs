@@ -0,0 +1,5 @@
+pub mod diff;
+pub mod fix;
+pub mod graph;
+pub mod onto;
+pub mod squash_all;
@@ -0,0 +1,156 @@
+use crate::engine::{BranchMetadata, Stack};
+use crate::git::{GitRepo, RebaseOptions, RebaseResult};
+use crate::ops::receipt::{OpKind, PlanSummary};
+use crate::ops::tx::{self, Transaction};
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+
+/// Stack the current stack's bottom branch onto the tip of the stack containing `target`
+pub fn run(target: String) -> Result<()> {
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+
+    if current == stack.trunk {
+        bail!("You are on trunk. Check out a branch in the stack you want to move.");
+    }
+    for branch in [&current, &target] {
+        if !stack.branches.contains_key(branch) {
+            bail!(
+                "Branch '{}' is not tracked. Run `stax branch track` first.",
+                branch
+            );
+        }
+    }
+    if target == stack.trunk {
+        bail!("'{}' is trunk; the stack already sits on it.", target);
+    }
+
+    let bottom = stack
+        .ancestors(&current)
+        .into_iter()
+        .rfind(|a| a != &stack.trunk)
+        .unwrap_or_else(|| current.clone());
+    let tip = stack_tip(&stack, &target)?;
+
+    let mut moved = vec![bottom.clone()];
+    moved.extend(stack.descendants(&bottom));
+
+    // Landing anywhere inside the moved stack would make it its own ancestor
+    if moved.contains(&tip) || moved.contains(&target) {
+        bail!(
+            "Cannot stack '{}' onto '{}': it is part of the same stack, which would create a cycle.",
+            bottom,
+            target
+        );
+    }
+
+    if stack.branches[&bottom].parent.as_deref() == Some(tip.as_str()) {
+        println!("'{}' already sits on '{}'.", bottom.cyan(), tip.cyan());
+        return Ok(());
+    }
+
+    if repo.is_dirty()? {
+        bail!("Working tree is dirty. Please stash or commit changes first.");
+    }
+
+    // Old tips delimit each branch's own commits once parents start moving
+    let old_tips: HashMap<String, String> = stack
+        .branches
+        .keys()
+        .filter_map(|b| repo.branch_commit(b).ok().map(|oid| (b.clone(), oid)))
+        .collect();
+
+    let mut tx = Transaction::begin(OpKind::StackOnto, &repo, false)?;
+    tx.plan_branches(&repo, &moved)?;
+    let summary = PlanSummary {
+        branches_to_rebase: moved.len(),
+        branches_to_push: 0,
+        description: vec![format!("Stack '{}' onto '{}'", bottom, tip)],
+    };
+    tx::print_plan(tx.kind(), &summary, false);
+    tx.set_plan_summary(summary);
+    tx.snapshot()?;
+
+    for b in &moved {
+        let old_parent = match stack.branches.get(b).and_then(|br| br.parent.clone()) {
+            Some(p) => p,
+            None => continue,
+        };
+        let new_parent = if b == &bottom {
+            tip.clone()
+        } else {
+            old_parent.clone()
+        };
+        let upstream = old_tips.get(&old_parent).map(String::as_str);
+
+        println!("  {} onto {}", b.white(), new_parent.blue());
+
+        let result = repo.rebase_branch_onto_from(
+            b,
+            &new_parent,
+            upstream,
+            false,
+            &RebaseOptions::default(),
+        )?;
+
+        // Point metadata at the new parent either way so `stax continue` finishes the branch
+        let new_parent_rev = repo.branch_commit(&new_parent)?;
+        let updated = match BranchMetadata::read(repo.inner(), b)? {
+            Some(meta) => BranchMetadata {
+                parent_branch_name: new_parent.clone(),
+                parent_branch_revision: new_parent_rev.clone(),
+                fork_point: Some(new_parent_rev.clone()),
+                ..meta
+            },
+            None => BranchMetadata::new(&new_parent, &new_parent_rev),
+        };
+        updated.write(repo.inner(), b)?;
+
+        match result {
+            RebaseResult::Success => {
+                tx.record_after(&repo, b)?;
+                println!("    {}", "✓ done".green());
+            }
+            RebaseResult::Conflict => {
+                println!("    {}", "✗ conflict".red());
+                println!();
+                println!("{}", "Resolve conflicts and run:".yellow());
+                println!("  {}", "stax continue".cyan());
+                tx.finish_err("Rebase conflict", Some("rebase"), Some(b))?;
+                return Ok(());
+            }
+        }
+    }
+
+    repo.checkout(&current)?;
+    tx.finish_ok()?;
+
+    let combined = Stack::load(&repo)?;
+    let chain: Vec<String> = combined
+        .current_stack(&current)
+        .into_iter()
+        .filter(|b| b != &combined.trunk)
+        .collect();
+    println!("✓ Stacked '{}' onto '{}'", bottom.green(), tip.blue());
+    println!("  {} → {}", combined.trunk.dimmed(), chain.join(" → "));
+
+    Ok(())
+}
+
+/// The topmost branch above `branch`, following single children
+fn stack_tip(stack: &Stack, branch: &str) -> Result<String> {
+    let mut tip = branch.to_string();
+    loop {
+        let children = &stack.branches[&tip].children;
+        match children.len() {
+            0 => return Ok(tip),
+            1 => tip = children[0].clone(),
+            _ => bail!(
+                "'{}' has multiple children; the tip of its stack is ambiguous.",
+                tip
+            ),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::engine::{BranchMetadata, Stack};
+use crate::git::GitRepo;
+use crate::ops::receipt::{OpKind, PlanSummary};
+use crate::ops::tx::{self, Transaction};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::process::Command;
+
+/// Collapse the current linear stack into its bottom branch
+pub fn run(skip_confirm: bool) -> Result<()> {
+    let repo = GitRepo::open()?;
+    let current = repo.current_branch()?;
+    let stack = Stack::load(&repo)?;
+    let workdir = repo.workdir()?;
+
+    if current == stack.trunk {
+        bail!("You are on trunk. Check out a branch in the stack you want to collapse.");
+    }
+    if !stack.branches.contains_key(&current) {
+        bail!(
+            "Branch '{}' is not tracked. Run `stax branch track` first.",
+            current
+        );
+    }
+
+    let chain = stack.linear_stack(&current)?;
+    if chain.len() < 2 {
+        println!(
+            "{}",
+            "Nothing to squash: the stack has a single branch.".yellow()
+        );
+        return Ok(());
+    }
+
+    let bottom = chain[0].clone();
+    let tip = chain[chain.len() - 1].clone();
+    let collapsed = &chain[1..];
+
+    let stale: Vec<&String> = chain
+        .iter()
+        .filter(|b| stack.branches[*b].needs_restack)
+        .collect();
+    if !stale.is_empty() {
+        bail!(
+            "Branches need restacking first: {}. Run `stax restack`.",
+            stale
+                .iter()
+                .map(|b| b.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let open_prs: Vec<String> = collapsed
+        .iter()
+        .filter_map(|b| {
+            let info = &stack.branches[b];
+            let open = info
+                .pr_state
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case("open"));
+            match (open, info.pr_number) {
+                (true, Some(n)) => Some(format!("{} (#{})", b, n)),
+                _ => None,
+            }
+        })
+        .collect();
+    if !open_prs.is_empty() {
+        bail!(
+            "Cannot collapse branches with open PRs: {}. Close or merge them first.",
+            open_prs.join(", ")
+        );
+    }
+
+    for branch in collapsed {
+        if let Some(path) = repo.branch_worktree_path(branch)? {
+            if path != workdir {
+                bail!(
+                    "Branch '{}' is checked out in another worktree at {}",
+                    branch,
+                    path.display()
+                );
+            }
+        }
+    }
+
+    if repo.is_dirty()? {
+        bail!("Working tree is dirty. Please stash or commit changes first.");
+    }
+
+    println!(
+        "Squashing {} branches into '{}':",
+        chain.len().to_string().cyan(),
+        bottom.green()
+    );
+    println!();
+    let log_output = Command::new("git")
+        .args([
+            "log",
+            "--oneline",
+            "--reverse",
+            &format!("{}..{}", stack.trunk, tip),
+        ])
+        .current_dir(workdir)
+        .output()
+        .context("Failed to list commits")?;
+    println!("{}", "Commits (oldest first):".bold());
+    for line in String::from_utf8_lossy(&log_output.stdout).lines() {
+        println!("  {}", line.dimmed());
+    }
+    println!();
+    println!("{}", "Branches to delete:".bold());
+    for branch in collapsed {
+        println!("  {}", branch.red());
+    }
+    println!();
+
+    if !skip_confirm {
+        let confirm = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Collapse {} branches into '{}'? This deletes the upper branches.",
+                chain.len(),
+                bottom
+            ))
+            .default(false)
+            .interact()?;
+
+        if !confirm {
+            println!("{}", "Aborted.".red());
+            return Ok(());
+        }
+    }
+
+    let mut tx = Transaction::begin(OpKind::SquashAll, &repo, false)?;
+    tx.plan_branches(&repo, &chain)?;
+    let summary = PlanSummary {
+        branches_to_rebase: 0,
+        branches_to_push: 0,
+        description: vec![format!(
+            "Collapse {} into '{}'",
+            collapsed.join(", "),
+            bottom
+        )],
+    };
+    tx::print_plan(tx.kind(), &summary, false);
+    tx.set_plan_summary(summary);
+    tx.snapshot()?;
+
+    // The stack is linear and restacked, so the tip already contains every
+    // commit in order: moving the bottom branch there keeps history intact.
+    let result = (|| -> Result<()> {
+        let tip_oid = repo.branch_commit(&tip)?;
+        repo.checkout(&bottom)?;
+        repo.reset_hard(&tip_oid)?;
+
+        for branch in collapsed {
+            repo.delete_branch(branch, true)?;
+            BranchMetadata::delete(repo.inner(), branch)?;
+        }
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        tx.finish_err(&err.to_string(), Some("squash-all"), Some(&bottom))?;
+        return Err(err);
+    }
+
+    tx.record_after(&repo, &bottom)?;
+    tx.finish_ok()?;
+
+    println!(
+        "✓ Collapsed stack into '{}' ({} branches deleted)",
+        bottom.green(),
+        collapsed.len()
+    );
+    println!(
+        "  {}",
+        "Run `stax undo` to restore the original branches.".dimmed()
+    );
+
+    Ok(())
+}
@@ -1,11 +1,11 @@
 use crate::cache::CiCache;
 use crate::config::Config;
-use crate::engine::Stack;
+use crate::engine::{BranchMetadata, BranchSort, Stack};
 use crate::git::GitRepo;
-use crate::github::GitHubClient;
+use crate::github::pr::PrInfoWithChecks;
 use crate::remote::{self, RemoteInfo};
 use anyhow::Result;
-use colored::{Color, Colorize};
+use colored::{Color, ColoredString, Colorize};
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::process::Command;
@@ -32,6 +32,7 @@ struct DisplayBranch {
 #[derive(Serialize, Clone)]
 struct BranchStatusJson {
     name: String,
+    /// Stax parent from the branch metadata; null for trunk
     parent: Option<String>,
     is_current: bool,
     is_trunk: bool,
@@ -46,32 +47,142 @@ struct BranchStatusJson {
     lines_added: usize,
     lines_deleted: usize,
     has_remote: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commits_since_submit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unpushed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unpushed_commits: Option<usize>,
+    /// Path of the linked worktree this branch is checked out in, if not this one
+    worktree: Option<String>,
+    /// Base branch of the PR on the remote (with --check-pr-base)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pr_base: Option<String>,
+    /// Whether the PR base differs from the stax parent (with --check-pr-base)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pr_base_mismatch: Option<bool>,
+    /// CI of the PR head: pass, fail, or pending (with --checks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checks_state: Option<String>,
 }
 
+/// Trunk compared to its cached remote-tracking ref
+#[derive(Serialize)]
+struct TrunkRemoteJson {
+    remote_ref: String,
+    ahead: usize,
+    behind: usize,
+}
+
+/// Version of the `status --json` shape; bump it whenever fields are renamed, removed,
+/// or change meaning
+const STATUS_JSON_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Serialize)]
 struct StatusJson {
+    #[serde(rename = "schemaVersion")]
+    schema_version: u32,
     trunk: String,
+    /// Absent when trunk has no remote-tracking ref
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trunk_remote: Option<TrunkRemoteJson>,
     current: String,
+    /// Whether the current worktree has uncommitted or untracked changes
+    dirty: bool,
     branches: Vec<BranchStatusJson>,
 }
 
-pub fn run(
-    json: bool,
-    stack_filter: Option<String>,
-    current_only: bool,
-    compact: bool,
-    quiet: bool,
-    verbose: bool,
-) -> Result<()> {
+/// Predicate for `status --filter`; several combine with AND
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFilter {
+    NeedsRestack,
+    HasPr,
+    NoPr,
+    Dirty,
+    Merged,
+}
+
+impl std::str::FromStr for StatusFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "needs-restack" => Ok(StatusFilter::NeedsRestack),
+            "has-pr" => Ok(StatusFilter::HasPr),
+            "no-pr" => Ok(StatusFilter::NoPr),
+            "dirty" => Ok(StatusFilter::Dirty),
+            "merged" => Ok(StatusFilter::Merged),
+            _ => anyhow::bail!(
+                "Invalid filter: {}. Use: needs-restack, has-pr, no-pr, dirty, or merged",
+                s
+            ),
+        }
+    }
+}
+
+impl StatusFilter {
+    /// `dirty` only knows about this worktree, so it matches the current branch
+    fn matches(self, entry: &BranchStatusJson, dirty: bool) -> bool {
+        match self {
+            StatusFilter::NeedsRestack => entry.needs_restack,
+            StatusFilter::HasPr => entry.pr_number.is_some(),
+            StatusFilter::NoPr => entry.pr_number.is_none(),
+            StatusFilter::Dirty => entry.is_current && dirty,
+            StatusFilter::Merged => entry
+                .pr_state
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case("merged")),
+        }
+    }
+}
+
+/// Flags shared by `status` and `ll`; `verbose` is what `ll` adds
+#[derive(Debug, Clone, Default)]
+pub struct StatusOptions {
+    pub json: bool,
+    pub stack_filter: Option<String>,
+    pub current_only: bool,
+    pub compact: bool,
+    pub dense: bool,
+    pub quiet: bool,
+    pub verbose: bool,
+    pub since_last_submit: bool,
+    pub ahead_of_remote: bool,
+    pub sort: BranchSort,
+    pub check_pr_base: bool,
+    pub checks: bool,
+    pub filters: Vec<StatusFilter>,
+}
+
+pub fn run(options: StatusOptions) -> Result<()> {
+    let StatusOptions {
+        json,
+        stack_filter,
+        current_only,
+        compact,
+        dense,
+        quiet,
+        verbose,
+        since_last_submit,
+        ahead_of_remote,
+        sort,
+        check_pr_base,
+        checks,
+        filters,
+    } = options;
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
-    let stack = Stack::load(&repo)?;
+    let mut stack = Stack::load(&repo)?;
+    stack.sort_children(sort, &current, |b| repo.branch_commit_time(b).unwrap_or(0));
     let config = Config::load()?;
+    let dense = dense || config.ui.compact;
     let workdir = repo.workdir()?;
+    let dirty = repo.is_dirty().unwrap_or(false);
     let has_tracked = stack.branches.len() > 1;
     let git_dir = repo.git_dir()?;
 
     let remote_info = RemoteInfo::from_repo(&repo, &config).ok();
+    let worktrees = repo.branches_in_other_worktrees().unwrap_or_default();
     let remote_branches = remote::get_remote_branches(workdir, config.remote_name())
         .unwrap_or_default()
         .into_iter()
@@ -100,6 +211,17 @@ pub fn run(
         None // Default: show all branches
     };
 
+    // Cached remote-tracking ref only; run `stax sync` to refresh it
+    let trunk_remote_ref = format!("{}/{}", config.remote_name(), stack.trunk);
+    let trunk_remote = repo
+        .commits_ahead_behind(&trunk_remote_ref, &stack.trunk)
+        .ok()
+        .map(|(ahead, behind)| TrunkRemoteJson {
+            remote_ref: trunk_remote_ref,
+            ahead,
+            behind,
+        });
+
     // Get trunk children and build display list with proper tree structure
     let trunk_info = stack.branches.get(&stack.trunk);
     let trunk_children: Vec<String> = trunk_info
@@ -112,9 +234,8 @@ pub fn run(
     // Build display list: each trunk child gets its own column, stacked left to right
     let mut display_branches: Vec<DisplayBranch> = Vec::new();
     let mut max_column = 0;
-    let mut sorted_trunk_children = trunk_children;
-    // Sort trunk children alphabetically (like fp)
-    sorted_trunk_children.sort();
+    // Already in `sort` order
+    let sorted_trunk_children = trunk_children;
 
     // Each trunk child gets column = index (first at 0, second at 1, etc.)
     for (i, root) in sorted_trunk_children.iter().enumerate() {
@@ -133,12 +254,21 @@ pub fn run(
         display_branches.iter().map(|b| b.name.clone()).collect();
     ordered_branches.push(stack.trunk.clone());
 
-    // Load CI cache and refresh if stale (TTL expired)
+    // Load CI cache; PR state, base, and CI for the whole stack come from one batched query.
+    // --checks always refreshes, otherwise only a stale cache does.
     let mut cache = CiCache::load(git_dir);
-    if cache.is_stale() {
-        let fresh_states = fetch_ci_states(&repo, remote_info.as_ref(), &stack, &ordered_branches);
-        for (branch, state) in fresh_states {
-            cache.update(&branch, Some(state), None);
+    let refresh_ci = checks || cache.is_stale();
+    let fetch = check_pr_base || checks || (refresh_ci && Config::github_token().is_some());
+    let remote_prs = if fetch {
+        fetch_remote_prs(remote_info.as_ref(), &stack, &ordered_branches)
+    } else {
+        HashMap::new()
+    };
+    if refresh_ci {
+        for (branch, pr) in &remote_prs {
+            if let Some(state) = &pr.checks {
+                cache.update(branch, Some(state.clone()), None);
+            }
         }
         cache.mark_refreshed();
         cache.cleanup(&ordered_branches);
@@ -151,6 +281,15 @@ pub fn run(
         .filter_map(|b| cache.get_ci_state(b).map(|s| (b.clone(), s)))
         .collect();
 
+    let pr_bases: HashMap<String, String> = if check_pr_base {
+        remote_prs
+            .iter()
+            .map(|(branch, pr)| (branch.clone(), pr.info.base.clone()))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
     let mut branch_statuses: Vec<BranchStatusJson> = Vec::new();
     let mut branch_status_map: HashMap<String, BranchStatusJson> = HashMap::new();
 
@@ -175,24 +314,44 @@ pub fn run(
         let (lines_added, lines_deleted) = if json {
             parent
                 .as_deref()
-                .and_then(|p| get_line_diff_stats(workdir, p, name))
+                .and_then(|p| get_line_diff_stats(workdir, &diff_range(&repo, p, name)))
                 .unwrap_or((0, 0))
         } else {
             (0, 0)
         };
 
-        let pr_state = info.and_then(|b| b.pr_state.clone()).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s)
-            }
-        });
+        let remote_pr = remote_prs.get(name).map(|pr| &pr.info);
+        let pr_state = remote_pr
+            .map(|pr| pr.state.clone())
+            .or_else(|| info.and_then(|b| b.pr_state.clone()))
+            .and_then(|s| if s.trim().is_empty() { None } else { Some(s) });
 
         let pr_number = info.and_then(|b| b.pr_number);
         let pr_url = pr_number.and_then(|n| remote_info.as_ref().map(|r| r.pr_url(n)));
         let ci_state = ci_states.get(name).cloned();
 
+        let commits_since_submit = if since_last_submit && !is_trunk {
+            commits_since_last_submit(&repo, name)
+        } else {
+            None
+        };
+
+        // Compare against the cached remote-tracking ref; no fetch
+        let unpushed_commits = if ahead_of_remote && !is_trunk {
+            let remote_ref = format!("{}/{}", config.remote_name(), name);
+            repo.commits_ahead_behind(&remote_ref, name)
+                .ok()
+                .map(|(ahead, _)| ahead)
+        } else {
+            None
+        };
+
+        let pr_base = pr_bases.get(name).cloned();
+        let pr_base_mismatch = pr_base.as_ref().zip(parent.as_ref()).map(|(base, parent)| {
+            let remote = config.remote_name();
+            base != &super::submit::pr_base(&repo, remote, parent)
+        });
+
         let entry = BranchStatusJson {
             name: name.clone(),
             parent: parent.clone(),
@@ -201,7 +360,9 @@ pub fn run(
             needs_restack: info.map(|b| b.needs_restack).unwrap_or(false),
             pr_number,
             pr_state,
-            pr_is_draft: info.and_then(|b| b.pr_is_draft),
+            pr_is_draft: remote_pr
+                .map(|pr| pr.is_draft)
+                .or_else(|| info.and_then(|b| b.pr_is_draft)),
             pr_url,
             ci_state,
             ahead,
@@ -209,16 +370,40 @@ pub fn run(
             lines_added,
             lines_deleted,
             has_remote: remote_branches.contains(name),
+            commits_since_submit,
+            unpushed: unpushed_commits.map(|count| count > 0),
+            unpushed_commits,
+            worktree: worktrees.get(name).map(|path| path.display().to_string()),
+            pr_base,
+            pr_base_mismatch,
+            checks_state: remote_prs
+                .get(name)
+                .filter(|_| checks)
+                .and_then(|pr| pr.info.checks_state.clone()),
         };
 
         branch_status_map.insert(name.clone(), entry.clone());
         branch_statuses.push(entry);
     }
 
+    // Trunk stays as the root of the tree but is not itself a filter result
+    let matches_filters = |name: &String| {
+        branch_status_map
+            .get(name)
+            .is_some_and(|entry| !entry.is_trunk && filters.iter().all(|f| f.matches(entry, dirty)))
+    };
+    if !filters.is_empty() {
+        branch_statuses.retain(|entry| matches_filters(&entry.name));
+        display_branches.retain(|db| matches_filters(&db.name));
+    }
+
     if json {
         let output = StatusJson {
+            schema_version: STATUS_JSON_SCHEMA_VERSION,
             trunk: stack.trunk.clone(),
+            trunk_remote,
             current: current.clone(),
+            dirty,
             branches: branch_statuses,
         };
         println!("{}", serde_json::to_string_pretty(&output)?);
@@ -246,11 +431,25 @@ pub fn run(
         return Ok(());
     }
 
+    if !quiet {
+        if let Some(ref divergence) = trunk_remote {
+            print_trunk_divergence(&stack.trunk, divergence);
+        }
+    }
+
     // Render each branch
     for (i, db) in display_branches.iter().enumerate() {
         let branch = &db.name;
         let is_current = branch == &current;
         let entry = branch_status_map.get(branch);
+
+        if dense {
+            if let Some(entry) = entry {
+                let depth = stack.ancestors(branch).len();
+                println!("{}", compact_line(entry, depth));
+            }
+            continue;
+        }
         // Show cloud if branch exists on remote OR has a PR (PR implies it was pushed)
         let has_remote =
             remote_branches.contains(branch) || entry.and_then(|e| e.pr_number).is_some();
@@ -328,6 +527,32 @@ pub fn run(
             if entry.needs_restack {
                 info_str.push_str(&format!(" {}", "(needs restack)".bright_yellow()));
             }
+            if let Some(count) = entry.commits_since_submit {
+                info_str.push_str(&format!(
+                    " {}",
+                    format!("(+{} since submit)", count).bright_cyan()
+                ));
+            }
+            if let Some(count) = entry.unpushed_commits.filter(|c| *c > 0) {
+                info_str.push_str(&format!(
+                    " {}",
+                    format!("unpushed ({})", count).bright_red()
+                ));
+            }
+            if let Some(ref path) = entry.worktree {
+                info_str.push_str(&format!(" {}", format!("[worktree: {}]", path).dimmed()));
+            }
+            if let (Some(base), Some(true)) = (&entry.pr_base, entry.pr_base_mismatch) {
+                info_str.push_str(&format!(
+                    " {}",
+                    format!(
+                        "(PR base: {}, stax parent: {} ⚠)",
+                        base,
+                        entry.parent.as_deref().unwrap_or_default()
+                    )
+                    .yellow()
+                ));
+            }
 
             // Only show PR info in verbose mode (ll command)
             if verbose {
@@ -343,7 +568,16 @@ pub fn run(
                         pr_text.push_str(&format!(" {}", url));
                     }
                     info_str.push_str(&format!("{}", pr_text.bright_magenta()));
+                    if let Some(dot) = checks_dot(entry) {
+                        info_str.push_str(&format!(" {}", dot));
+                    }
                 }
+            } else if let (Some(pr_number), Some(dot)) = (entry.pr_number, checks_dot(entry)) {
+                info_str.push_str(&format!(
+                    " {} {}",
+                    format!("#{}", pr_number).bright_magenta(),
+                    dot
+                ));
             }
 
             // Only show CI state in verbose mode (ll command)
@@ -357,79 +591,94 @@ pub fn run(
         println!("{}{}", tree, info_str);
     }
 
-    // Render trunk with corner connector (fp-style: ○─┘ for 1 col, ○─┴─┘ for 2, etc.)
-    // Only connect columns used by direct trunk children, not nested columns
-    let is_trunk_current = stack.trunk == current;
-    let trunk_child_max_col = if sorted_trunk_children.is_empty() {
-        0
+    if dense {
+        if let Some(entry) = branch_status_map.get(&stack.trunk) {
+            println!("{}", compact_line(entry, 0));
+        }
     } else {
-        sorted_trunk_children.len() - 1
-    };
+        // Render trunk with corner connector (fp-style: ○─┘ for 1 col, ○─┴─┘ for 2, etc.)
+        // Only connect columns used by direct trunk children, not nested columns
+        let is_trunk_current = stack.trunk == current;
+        let trunk_child_max_col = if sorted_trunk_children.is_empty() {
+            0
+        } else {
+            sorted_trunk_children.len() - 1
+        };
 
-    let mut trunk_tree = String::new();
-    let mut trunk_visual_width = 0;
+        let mut trunk_tree = String::new();
+        let mut trunk_visual_width = 0;
 
-    let trunk_circle = if is_trunk_current { "◉" } else { "○" };
-    let trunk_color = COLUMN_COLORS[0];
-    trunk_tree.push_str(&format!("{}", trunk_circle.color(trunk_color)));
-    trunk_visual_width += 1;
+        let trunk_circle = if is_trunk_current { "◉" } else { "○" };
+        let trunk_color = COLUMN_COLORS[0];
+        trunk_tree.push_str(&format!("{}", trunk_circle.color(trunk_color)));
+        trunk_visual_width += 1;
 
-    // Show connectors only for trunk children columns: ─┴ for middle, ─┘ for last
-    if trunk_child_max_col >= 1 {
-        for col in 1..=trunk_child_max_col {
-            let col_color = COLUMN_COLORS[col % COLUMN_COLORS.len()];
-            if col < trunk_child_max_col {
-                trunk_tree.push_str(&format!("{}", "─┴".color(col_color)));
-            } else {
-                trunk_tree.push_str(&format!("{}", "─┘".color(col_color)));
+        // Show connectors only for trunk children columns: ─┴ for middle, ─┘ for last
+        if trunk_child_max_col >= 1 {
+            for col in 1..=trunk_child_max_col {
+                let col_color = COLUMN_COLORS[col % COLUMN_COLORS.len()];
+                if col < trunk_child_max_col {
+                    trunk_tree.push_str(&format!("{}", "─┴".color(col_color)));
+                } else {
+                    trunk_tree.push_str(&format!("{}", "─┘".color(col_color)));
+                }
+                trunk_visual_width += 2;
             }
-            trunk_visual_width += 2;
         }
-    }
 
-    // Pad to match branch name alignment
-    while trunk_visual_width < tree_target_width {
-        trunk_tree.push(' ');
-        trunk_visual_width += 1;
-    }
+        // Pad to match branch name alignment
+        while trunk_visual_width < tree_target_width {
+            trunk_tree.push(' ');
+            trunk_visual_width += 1;
+        }
 
-    let mut trunk_info = String::new();
-    trunk_info.push(' '); // Space after tree (same as branches)
-                          // Show cloud icon or space for alignment
-    if remote_branches.contains(&stack.trunk) {
-        trunk_info.push_str(&format!("{} ", "☁️".bright_blue()));
-    } else {
-        trunk_info.push_str("   "); // Space for alignment when no remote (emoji is 2 cells wide)
-    }
-    // Color trunk name to match column 0
-    if is_trunk_current {
-        trunk_info.push_str(&format!("{}", stack.trunk.color(trunk_color).bold()));
-    } else {
-        trunk_info.push_str(&format!("{}", stack.trunk.color(trunk_color)));
-    }
+        let mut trunk_info = String::new();
+        trunk_info.push(' '); // Space after tree (same as branches)
+                              // Show cloud icon or space for alignment
+        if remote_branches.contains(&stack.trunk) {
+            trunk_info.push_str(&format!("{} ", "☁️".bright_blue()));
+        } else {
+            trunk_info.push_str("   "); // Space for alignment when no remote (emoji is 2 cells wide)
+        }
+        // Color trunk name to match column 0
+        if is_trunk_current {
+            trunk_info.push_str(&format!("{}", stack.trunk.color(trunk_color).bold()));
+        } else {
+            trunk_info.push_str(&format!("{}", stack.trunk.color(trunk_color)));
+        }
 
-    // Show commits ahead/behind for trunk (compared to origin)
-    if let Some(entry) = branch_status_map.get(&stack.trunk) {
-        if entry.ahead > 0 || entry.behind > 0 {
-            if entry.behind > 0 {
-                trunk_info.push_str(&format!(" {}", format!("{} behind", entry.behind).red()));
-            }
-            if entry.ahead > 0 {
-                trunk_info.push_str(&format!(" {}", format!("{} ahead", entry.ahead).green()));
+        // Show commits ahead/behind for trunk (compared to origin)
+        if let Some(entry) = branch_status_map.get(&stack.trunk) {
+            if entry.ahead > 0 || entry.behind > 0 {
+                if entry.behind > 0 {
+                    trunk_info.push_str(&format!(" {}", format!("{} behind", entry.behind).red()));
+                }
+                if entry.ahead > 0 {
+                    trunk_info.push_str(&format!(" {}", format!("{} ahead", entry.ahead).green()));
+                }
             }
         }
+
+        println!("{}{}", trunk_tree, trunk_info);
     }
 
-    println!("{}{}", trunk_tree, trunk_info);
+    if !filters.is_empty() && display_branches.is_empty() && !quiet {
+        println!("{}", "No branches match the filter.".dimmed());
+    }
 
     if !has_tracked && !quiet {
         println!(
             "{}",
             "No tracked branches yet (showing trunk only).".dimmed()
         );
+        super::print_empty_stack_hint();
+    }
+
+    if dirty && !quiet {
+        println!();
         println!(
-            "Use {} to start tracking branches.",
-            "stax branch track".cyan()
+            "{}",
+            format!("⚠ uncommitted changes on '{}'", current).bright_yellow()
         );
     }
 
@@ -503,8 +752,48 @@ pub fn run(
     Ok(())
 }
 
+/// Header line for a trunk that has drifted from its remote-tracking ref
+fn print_trunk_divergence(trunk: &str, divergence: &TrunkRemoteJson) {
+    let plural = |n: usize| if n == 1 { "commit" } else { "commits" };
+    if divergence.behind > 0 {
+        let mut line = format!(
+            "{} is {} {} behind {}",
+            trunk,
+            divergence.behind,
+            plural(divergence.behind),
+            divergence.remote_ref
+        );
+        if divergence.ahead > 0 {
+            line.push_str(&format!(
+                " and {} {} ahead",
+                divergence.ahead,
+                plural(divergence.ahead)
+            ));
+        }
+        println!(
+            "{} Run {} to update.",
+            format!("↓ {}.", line).bright_yellow(),
+            "stax sync".bright_cyan()
+        );
+        println!();
+    } else if divergence.ahead > 0 {
+        println!(
+            "{}",
+            format!(
+                "↑ {} is {} {} ahead of {}.",
+                trunk,
+                divergence.ahead,
+                plural(divergence.ahead),
+                divergence.remote_ref
+            )
+            .dimmed()
+        );
+        println!();
+    }
+}
+
 /// Collect branches with proper nesting for branches that have multiple children
-/// fp-style: children sorted alphabetically, each child gets column + index
+/// fp-style: each child (in the stack's sort order) gets column + index
 fn collect_display_branches_with_nesting(
     stack: &Stack,
     branch: &str,
@@ -531,16 +820,13 @@ fn collect_recursive(
     *max_column = (*max_column).max(column);
 
     if let Some(info) = stack.branches.get(branch) {
-        let mut children: Vec<&String> = info
+        let children: Vec<&String> = info
             .children
             .iter()
             .filter(|c| allowed.is_none_or(|set| set.contains(*c)))
             .collect();
 
         if !children.is_empty() {
-            // Sort children alphabetically (like fp)
-            children.sort();
-
             // Each child gets column + index: first child at same column, second at +1, etc.
             for (i, child) in children.iter().enumerate() {
                 collect_recursive(stack, child, column + i, result, max_column, allowed);
@@ -555,14 +841,101 @@ fn collect_recursive(
     });
 }
 
-/// Get line additions and deletions between parent and branch
-fn get_line_diff_stats(
-    workdir: &std::path::Path,
-    parent: &str,
-    branch: &str,
-) -> Option<(usize, usize)> {
+/// Dense single-line rendering used with `--dense` or `ui.compact`
+fn compact_line(entry: &BranchStatusJson, depth: usize) -> String {
+    let name = if entry.is_current {
+        entry.name.bold().to_string()
+    } else {
+        entry.name.clone()
+    };
+    let mut line = format!("{}{} {}", "  ".repeat(depth), compact_glyph(entry), name);
+    if let Some(number) = entry.pr_number {
+        line.push_str(&format!(" {}", format!("#{}", number).bright_magenta()));
+        if let Some(dot) = checks_dot(entry) {
+            line.push_str(&format!(" {}", dot));
+        }
+    }
+    line
+}
+
+/// Single glyph summarizing branch health: restack first, then CI, else current/idle
+fn compact_glyph(entry: &BranchStatusJson) -> ColoredString {
+    if entry.needs_restack {
+        return "⟳".bright_yellow();
+    }
+    match entry.ci_state.as_deref() {
+        Some("failure") | Some("error") => "✗".red(),
+        Some("pending") => "●".yellow(),
+        Some("success") => "✓".green(),
+        _ if entry.is_current => "◉".cyan(),
+        _ => "○".dimmed(),
+    }
+}
+
+/// Colored dot for the PR's CI (with --checks)
+fn checks_dot(entry: &BranchStatusJson) -> Option<ColoredString> {
+    match entry.checks_state.as_deref()? {
+        "pass" => Some("●".green()),
+        "fail" => Some("●".red()),
+        _ => Some("●".yellow()),
+    }
+}
+
+/// Count commits added to a branch since it was last pushed by submit
+fn commits_since_last_submit(repo: &GitRepo, branch: &str) -> Option<usize> {
+    let meta = BranchMetadata::read(repo.inner(), branch).ok().flatten()?;
+    let last = meta.last_submitted_revision?;
+    repo.commits_ahead_behind(&last, branch)
+        .ok()
+        .map(|(ahead, _)| ahead)
+}
+
+/// Revision range for a branch's own changes.
+///
+/// Prefers the recorded fork point so an amended or rewritten parent doesn't
+/// inflate the diff, falling back to the live merge-base for untracked forks
+/// or once a restack has moved the branch past the recorded point.
+fn diff_range(repo: &GitRepo, parent: &str, branch: &str) -> String {
+    let merge_base_range = format!("{}...{}", parent, branch);
+    let Some(fork_point) = BranchMetadata::read(repo.inner(), branch)
+        .ok()
+        .flatten()
+        .and_then(|meta| meta.fork_point)
+    else {
+        return merge_base_range;
+    };
+
+    let oid = |rev: &str| git2::Oid::from_str(rev).ok();
+    let (Some(fork_oid), Some(tip_oid), Some(base_oid)) = (
+        oid(&fork_point),
+        repo.branch_commit(branch).ok().as_deref().and_then(oid),
+        repo.merge_base(parent, branch)
+            .ok()
+            .as_deref()
+            .and_then(oid),
+    ) else {
+        return merge_base_range;
+    };
+
+    let inner = repo.inner();
+    let on_branch = fork_oid == tip_oid
+        || inner
+            .graph_descendant_of(tip_oid, fork_oid)
+            .unwrap_or(false);
+    let superseded = inner
+        .graph_descendant_of(base_oid, fork_oid)
+        .unwrap_or(false);
+    if on_branch && !superseded {
+        format!("{}..{}", fork_point, branch)
+    } else {
+        merge_base_range
+    }
+}
+
+/// Get line additions and deletions for a revision range
+fn get_line_diff_stats(workdir: &std::path::Path, range: &str) -> Option<(usize, usize)> {
     let output = Command::new("git")
-        .args(["diff", "--numstat", &format!("{}...{}", parent, branch)])
+        .args(["diff", "--numstat", range])
         .current_dir(workdir)
         .output()
         .ok()?;
@@ -591,17 +964,24 @@ fn get_line_diff_stats(
     Some((additions, deletions))
 }
 
-fn fetch_ci_states(
-    repo: &GitRepo,
+/// Remote PR state, base, and CI for every branch with a PR, in one round trip
+fn fetch_remote_prs(
     remote_info: Option<&RemoteInfo>,
     stack: &Stack,
     branches: &[String],
-) -> HashMap<String, String> {
+) -> HashMap<String, PrInfoWithChecks> {
     let Some(remote) = remote_info else {
         return HashMap::new();
     };
 
-    if Config::github_token().is_none() {
+    let pr_numbers: HashMap<u64, &String> = branches
+        .iter()
+        .filter_map(|branch| {
+            let number = stack.branches.get(branch)?.pr_number.filter(|n| *n > 0)?;
+            Some((number, branch))
+        })
+        .collect();
+    if pr_numbers.is_empty() {
         return HashMap::new();
     }
 
@@ -610,39 +990,17 @@ fn fetch_ci_states(
         Err(_) => return HashMap::new(),
     };
 
-    let client = match rt.block_on(async {
-        GitHubClient::new(remote.owner(), &remote.repo, remote.api_base_url.clone())
-    }) {
+    let client = match rt.block_on(async { remote.provider() }) {
         Ok(client) => client,
         Err(_) => return HashMap::new(),
     };
 
-    let mut results = HashMap::new();
-    for branch in branches {
-        let has_pr = stack
-            .branches
-            .get(branch)
-            .and_then(|b| b.pr_number)
-            .is_some();
-
-        if !has_pr {
-            continue;
-        }
-
-        let sha = match repo.branch_commit(branch) {
-            Ok(sha) => sha,
-            Err(_) => continue,
-        };
-
-        let state = rt
-            .block_on(async { client.combined_status_state(&sha).await })
-            .ok()
-            .flatten();
-
-        if let Some(state) = state {
-            results.insert(branch.clone(), state);
-        }
-    }
+    let numbers: Vec<u64> = pr_numbers.keys().copied().collect();
+    let prs = rt
+        .block_on(async { client.fetch_prs_batch(&numbers).await })
+        .unwrap_or_default();
 
-    results
+    prs.into_iter()
+        .filter_map(|(number, pr)| Some((pr_numbers.get(&number)?.to_string(), pr)))
+        .collect()
 }
@@ -1,18 +1,22 @@
 use crate::config::Config;
 use crate::engine::{BranchMetadata, Stack};
 use crate::git::GitRepo;
-use crate::github::pr::{generate_stack_comment, PrInfoWithHead, StackPrInfo};
-use crate::github::pr_template::{discover_pr_templates, select_template_interactive};
-use crate::github::GitHubClient;
-use crate::ops::receipt::{OpKind, PlanSummary};
+use crate::github::codeowners::CodeOwners;
+use crate::github::pr::{generate_stack_comment, MergeMethod, PrInfoWithHead, StackPrInfo};
+use crate::github::pr_body::PrBodyBoilerplate;
+use crate::github::pr_template::{
+    discover_pr_templates, discover_pr_templates_with, select_template_interactive,
+};
+use crate::ops::receipt::{OpKind, OpReceipt, PlanSummary};
 use crate::ops::tx::{self, Transaction};
+use crate::provider::RemoteProvider;
 use crate::remote::{self, RemoteInfo};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Editor, Input, Select};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,6 +58,7 @@ pub fn run(
     scope: SubmitScope,
     draft: bool,
     no_pr: bool,
+    no_push: bool,
     _force: bool, // kept for CLI compatibility
     yes: bool,
     no_prompt: bool,
@@ -66,12 +71,26 @@ pub fn run(
     no_template: bool,
     edit: bool,
     ai_body: bool,
+    no_verify: bool,
+    restart: bool,
+    assign_me: bool,
+    comment_only: bool,
+    template_dir: Option<PathBuf>,
+    no_track_pr: bool,
+    auto_merge: bool,
 ) -> Result<()> {
     let repo = GitRepo::open()?;
     let current = repo.current_branch()?;
     let stack = Stack::load(&repo)?;
     let config = Config::load()?;
-    let _ = yes; // Used for future auto-confirm features
+
+    // Resolve the auto-merge method up front so a bad config fails before any push
+    let auto_merge_method: Option<MergeMethod> = if auto_merge {
+        let method = config.remote.merge_method.as_deref().map(str::parse);
+        Some(method.transpose()?.unwrap_or_default())
+    } else {
+        None
+    };
 
     // Track if --draft was explicitly passed (we'll ask interactively if not)
     let draft_flag_set = draft;
@@ -115,37 +134,68 @@ pub fn run(
         }
     }
 
-    // Check for branches with no changes (empty branches)
+    // Run local gates before anything is pushed
+    let branches_to_submit = if no_verify || comment_only {
+        branches_to_submit
+    } else {
+        run_pre_submit_hook(&config, &repo, &stack, branches_to_submit, quiet)?
+    };
+    if branches_to_submit.is_empty() {
+        if !quiet {
+            println!("{}", "No branches passed the pre-submit hook.".yellow());
+        }
+        return Ok(());
+    }
+
+    // Branches with no commits beyond their parent (an empty parent..branch
+    // range) can't have a PR; GitHub rejects them with an unhelpful error
     let empty_branches: Vec<_> = branches_to_submit
         .iter()
         .filter(|b| {
-            if let Some(branch_info) = stack.branches.get(*b) {
-                if let Some(parent) = &branch_info.parent {
-                    if let Ok(branch_commit) = repo.branch_commit(b) {
-                        if let Ok(parent_commit) = repo.branch_commit(parent) {
-                            return branch_commit == parent_commit;
-                        }
-                    }
-                }
-            }
-            false
+            stack
+                .branches
+                .get(*b)
+                .and_then(|info| info.parent.as_ref())
+                .and_then(|parent| repo.commits_ahead_behind(parent, b).ok())
+                .is_some_and(|(ahead, _)| ahead == 0)
         })
         .collect();
-
-    // Empty branches will be pushed but won't get PRs created
     let empty_set: HashSet<_> = empty_branches.iter().cloned().collect();
 
-    if !empty_branches.is_empty() && !quiet {
-        println!("  {} Empty branches (will push, skip PR):", "!".yellow());
+    // An empty branch is still pushed when a submitted child needs it as its PR base
+    let is_base = |branch: &String| {
+        branches_to_submit
+            .iter()
+            .any(|other| stack.branches.get(other).and_then(|b| b.parent.as_ref()) == Some(branch))
+    };
+    let skip_push: HashSet<String> = empty_branches
+        .iter()
+        .filter(|b| !is_base(b))
+        .map(|b| b.to_string())
+        .collect();
+
+    if !quiet {
         for b in &empty_branches {
-            println!("    {}", b.dimmed());
+            let action = if skip_push.contains(*b) {
+                "skipping"
+            } else {
+                "pushing only as a base for its children"
+            };
+            println!(
+                "  {} {} has no commits to submit ({})",
+                "!".yellow(),
+                b.cyan(),
+                action
+            );
         }
     }
 
-    let remote_info = RemoteInfo::from_repo(&repo, &config)?;
+    if !no_verify && !comment_only {
+        check_diff_sizes(&config, &repo, &stack, &branches_to_submit, quiet)?;
+    }
 
-    let owner = remote_info.owner().to_string();
-    let repo_name = remote_info.repo.clone();
+    let remote_info = RemoteInfo::from_repo(&repo, &config)?;
+    let body_boilerplate = PrBodyBoilerplate::from_config(&config, repo.workdir()?)?;
 
     // Fetch to ensure we have latest remote refs (non-fatal if it fails)
     if !quiet {
@@ -183,6 +233,21 @@ pub fn run(
         );
     }
 
+    // --no-push opens PRs from what is already on the remote, so every head must exist there
+    if no_push {
+        if let Some(missing) = branches_to_submit
+            .iter()
+            .find(|b| !empty_set.contains(b) && !remote_branches.contains(b))
+        {
+            anyhow::bail!(
+                "Branch '{}' has no remote head on '{}'.\n\
+                 Push it first or run submit without --no-push.",
+                missing,
+                remote_info.name
+            );
+        }
+    }
+
     if matches!(scope, SubmitScope::Branch | SubmitScope::Upstack) {
         validate_narrow_scope_submit(
             scope,
@@ -202,24 +267,21 @@ pub fn run(
 
     let mut plans: Vec<PrPlan> = Vec::new();
     let mut rt: Option<tokio::runtime::Runtime> = None;
-    let mut client: Option<GitHubClient> = None;
+    let mut client: Option<Box<dyn RemoteProvider>> = None;
 
     if no_pr {
         let runtime = tokio::runtime::Runtime::new().ok();
-        let gh_client = runtime.as_ref().and_then(|runtime| {
-            runtime
-                .block_on(async {
-                    GitHubClient::new(&owner, &repo_name, remote_info.api_base_url.clone())
-                })
-                .ok()
-        });
+        let gh_client = runtime
+            .as_ref()
+            .and_then(|runtime| runtime.block_on(async { remote_info.provider() }).ok());
         let mut open_prs_by_head: Option<HashMap<String, PrInfoWithHead>> = None;
 
         for branch in &branches_to_submit {
             let mut meta = BranchMetadata::read(repo.inner(), branch)?
                 .context(format!("No metadata for branch {}", branch))?;
             let is_empty = empty_set.contains(branch);
-            let needs_push = branch_needs_push(repo.workdir()?, &remote_info.name, branch);
+            let needs_push = !skip_push.contains(branch)
+                && branch_needs_push(repo.workdir()?, &remote_info.name, branch);
             let mut existing_pr = None;
 
             // Best-effort metadata refresh when no-pr is used.
@@ -263,7 +325,7 @@ pub fn run(
                             })
                             .unwrap_or(true);
 
-                        if needs_meta_update && owner_matches {
+                        if needs_meta_update && owner_matches && !no_track_pr {
                             meta = BranchMetadata {
                                 pr_info: Some(crate::engine::metadata::PrInfo {
                                     number: pr.info.number,
@@ -292,9 +354,7 @@ pub fn run(
         }
     } else {
         let runtime = tokio::runtime::Runtime::new()?;
-        let gh_client = runtime.block_on(async {
-            GitHubClient::new(&owner, &repo_name, remote_info.api_base_url.clone())
-        })?;
+        let gh_client = runtime.block_on(async { remote_info.provider() })?;
         let mut open_prs_by_head: Option<HashMap<String, PrInfoWithHead>> = None;
 
         for branch in &branches_to_submit {
@@ -385,7 +445,7 @@ pub fn run(
                     })
                     .unwrap_or(true);
 
-                if needs_meta_update && owner_matches {
+                if needs_meta_update && owner_matches && !no_track_pr {
                     let updated_meta = BranchMetadata {
                         pr_info: Some(crate::engine::metadata::PrInfo {
                             number: pr.info.number,
@@ -410,13 +470,15 @@ pub fn run(
             let base = meta.parent_branch_name.clone();
 
             // Check if we actually need to push
-            let needs_push = branch_needs_push(repo.workdir()?, &remote_info.name, branch);
+            let needs_push = !no_push
+                && !skip_push.contains(branch)
+                && branch_needs_push(repo.workdir()?, &remote_info.name, branch);
 
             // Check if PR base needs updating (not for empty branches)
             let needs_pr_update = if is_empty {
                 false
             } else if let Some(pr) = &existing_pr {
-                pr.info.base != base || needs_push
+                pr.info.base != pr_base(&repo, &remote_info.name, &base) || needs_push
             } else {
                 true // New PR always needs creation
             };
@@ -442,6 +504,90 @@ pub fn run(
         println!("{}", "done".green());
     }
 
+    // --comment-only refreshes the stack lists and leaves branches and the rest of each PR alone
+    if comment_only {
+        if !config.remote.stack_comment {
+            println!(
+                "{}",
+                "Stack lists are disabled (remote.stack_comment = false); nothing to do.".yellow()
+            );
+            return Ok(());
+        }
+        let pr_infos: Vec<StackPrInfo> = plans
+            .iter()
+            .filter(|p| !p.is_empty)
+            .map(|p| StackPrInfo {
+                branch: p.branch.clone(),
+                pr_number: p.existing_pr,
+            })
+            .collect();
+        if pr_infos.iter().all(|p| p.pr_number.is_none()) {
+            if !quiet {
+                println!(
+                    "{}",
+                    "No open PRs in this stack; nothing to comment on.".yellow()
+                );
+            }
+            return Ok(());
+        }
+
+        let rt = rt.context("Internal error: missing runtime for PR submission")?;
+        let client = client.context("Internal error: missing GitHub client for PR submission")?;
+        rt.block_on(update_stack_comments(
+            client.as_ref(),
+            &pr_infos,
+            &remote_info,
+            &stack.trunk,
+            quiet,
+        ))?;
+        if !quiet {
+            println!();
+            println!("{}", "✓ Stack lists updated!".green().bold());
+        }
+        return Ok(());
+    }
+
+    // Pick up after an interrupted submit of this stack unless asked to start over
+    let resumed = if restart {
+        None
+    } else {
+        OpReceipt::load_latest(repo.git_dir()?)
+            .ok()
+            .flatten()
+            .filter(|r| r.is_resumable_submit() && r.head_branch_before == current)
+    };
+    if let Some(prev) = &resumed {
+        let progress = &prev.submit_progress;
+        let mut already_pushed = 0;
+        let mut already_created = 0;
+        for plan in &mut plans {
+            // A branch moved since the interrupted run needs pushing again
+            let local_oid = repo.branch_commit(&plan.branch).ok();
+            if local_oid.is_some() && progress.pushed.get(&plan.branch) == local_oid.as_ref() {
+                plan.needs_push = false;
+                already_pushed += 1;
+            }
+            if plan.existing_pr.is_none() && !plan.is_empty {
+                if let Some(&number) = progress.created_prs.get(&plan.branch) {
+                    plan.existing_pr = Some(number);
+                    plan.needs_pr_update = true;
+                    already_created += 1;
+                }
+            }
+        }
+        if !quiet {
+            println!(
+                "  {} Resuming interrupted submit {}: {} already pushed, {} {} already created {}",
+                "↻".cyan(),
+                prev.op_id.dimmed(),
+                already_pushed,
+                already_created,
+                if already_created == 1 { "PR" } else { "PRs" },
+                "(--restart to start over)".dimmed()
+            );
+        }
+    }
+
     // Show plan summary (exclude empty branches from PR counts)
     let creates: Vec<_> = plans
         .iter()
@@ -485,9 +631,11 @@ pub fn run(
 
     // Collect PR details for new PRs BEFORE pushing (skip empty branches)
     if !no_pr {
-        // Discover all available PR templates
+        // Discover all available PR templates once for every new PR
         let discovered_templates = if no_template {
             Vec::new()
+        } else if template_dir.is_some() {
+            discover_pr_templates_with(repo.workdir()?, template_dir.as_deref())?
         } else {
             discover_pr_templates(repo.workdir()?).unwrap_or_default()
         };
@@ -572,8 +720,11 @@ pub fn run(
                     Ok(generated) => {
                         if edit {
                             Editor::new().edit(&generated)?.unwrap_or(generated)
-                        } else {
+                        } else if no_prompt || yes {
                             generated
+                        } else {
+                            super::generate::review_body(generated, "Use default body instead")?
+                                .unwrap_or(default_body)
                         }
                     }
                     Err(e) => {
@@ -629,7 +780,7 @@ pub fn run(
             };
 
             plan.title = Some(title);
-            plan.body = Some(body);
+            plan.body = Some(body_boilerplate.apply(&body));
             plan.is_draft = Some(is_draft);
         }
     }
@@ -637,8 +788,10 @@ pub fn run(
     // Now push branches that need it
     let branches_needing_push: Vec<_> = plans.iter().filter(|p| p.needs_push).collect();
 
-    // Create transaction if we have branches to push
-    let mut tx = if !branches_needing_push.is_empty() {
+    let creating_prs = !no_pr && plans.iter().any(|p| p.existing_pr.is_none() && !p.is_empty);
+
+    // Create a transaction whenever there is work whose progress a re-run could resume
+    let mut tx = if !branches_needing_push.is_empty() || creating_prs || resumed.is_some() {
         let mut tx = Transaction::begin(OpKind::Submit, &repo, quiet)?;
 
         // Plan local branches (for backup)
@@ -656,18 +809,25 @@ pub fn run(
         let summary = PlanSummary {
             branches_to_rebase: 0,
             branches_to_push: branches_needing_push.len(),
-            description: vec![format!(
-                "Submit {} {}",
-                branches_needing_push.len(),
-                if branches_needing_push.len() == 1 {
-                    "branch"
-                } else {
-                    "branches"
-                }
-            )],
+            description: if branches_needing_push.is_empty() {
+                Vec::new()
+            } else {
+                vec![format!(
+                    "Submit {} {}",
+                    branches_needing_push.len(),
+                    if branches_needing_push.len() == 1 {
+                        "branch"
+                    } else {
+                        "branches"
+                    }
+                )]
+            },
         };
         tx::print_plan(tx.kind(), &summary, quiet);
         tx.set_plan_summary(summary);
+        if let Some(prev) = &resumed {
+            tx.resume_submit_progress(prev.submit_progress.clone());
+        }
         tx.snapshot()?;
 
         Some(tx)
@@ -692,11 +852,26 @@ pub fn run(
 
             match push_branch(repo.workdir()?, &remote_info.name, &plan.branch) {
                 Ok(()) => {
+                    // Remember what was pushed so later diffs can focus on new work
+                    if !no_track_pr {
+                        if let (Some(oid), Some(meta)) = (
+                            &local_oid,
+                            BranchMetadata::read(repo.inner(), &plan.branch)?,
+                        ) {
+                            let updated_meta = BranchMetadata {
+                                last_submitted_revision: Some(oid.clone()),
+                                ..meta
+                            };
+                            updated_meta.write(repo.inner(), &plan.branch)?;
+                        }
+                    }
+
                     // Record after-OIDs
                     if let Some(ref mut tx) = tx {
                         let _ = tx.record_after(&repo, &plan.branch);
                         if let Some(oid) = &local_oid {
                             tx.record_remote_after(&remote_info.name, &plan.branch, oid);
+                            tx.record_pushed(&plan.branch, oid)?;
                         }
                     }
                     if !quiet {
@@ -735,6 +910,9 @@ pub fn run(
         .any(|p| !p.is_empty && (p.existing_pr.is_none() || p.needs_pr_update));
 
     if !any_pr_work && branches_needing_push.is_empty() {
+        if let Some(tx) = tx {
+            tx.finish_ok()?;
+        }
         if !quiet {
             println!();
             println!("{}", "✓ Stack already up to date!".green().bold());
@@ -751,6 +929,27 @@ pub fn run(
     let rt = rt.context("Internal error: missing runtime for PR submission")?;
     let client = client.context("Internal error: missing GitHub client for PR submission")?;
 
+    // Looked up once per run; every PR touched below reuses the login
+    let assignees = if assign_me {
+        let login = rt
+            .block_on(client.current_user())
+            .context("Failed to look up your GitHub login for --assign-me")?;
+        with_assignee(assignees, login)
+    } else {
+        assignees
+    };
+
+    // Without --reviewers, new PRs can ask the CODEOWNERS of their changed files instead
+    let codeowners = if reviewers.is_empty() && config.remote.auto_request_codeowners {
+        CodeOwners::load(repo.workdir()?)
+    } else {
+        None
+    };
+    let author = match &codeowners {
+        Some(_) => rt.block_on(client.current_user()).ok(),
+        None => None,
+    };
+
     rt.block_on(async {
         let mut pr_infos: Vec<StackPrInfo> = Vec::new();
 
@@ -775,7 +974,13 @@ pub fn run(
                 }
 
                 let pr = client
-                    .create_pr(&plan.branch, &plan.parent, title, body, is_draft)
+                    .create_pr(
+                        &plan.branch,
+                        &pr_base(&repo, &remote_info.name, &plan.parent),
+                        title,
+                        body,
+                        is_draft,
+                    )
                     .await
                     .context(format!(
                         "Failed to create PR for '{}' with base '{}'\n\
@@ -795,34 +1000,69 @@ pub fn run(
                 }
 
                 // Update metadata with PR info
-                let updated_meta = BranchMetadata {
-                    pr_info: Some(crate::engine::metadata::PrInfo {
-                        number: pr.number,
-                        state: pr.state.clone(),
-                        is_draft: Some(pr.is_draft),
-                    }),
-                    ..meta
-                };
-                updated_meta.write(repo.inner(), &plan.branch)?;
+                if !no_track_pr {
+                    let updated_meta = BranchMetadata {
+                        pr_info: Some(crate::engine::metadata::PrInfo {
+                            number: pr.number,
+                            state: pr.state.clone(),
+                            is_draft: Some(pr.is_draft),
+                        }),
+                        ..meta
+                    };
+                    updated_meta.write(repo.inner(), &plan.branch)?;
+                }
+                if let Some(tx) = tx.as_mut() {
+                    tx.record_created_pr(&plan.branch, pr.number)?;
+                }
 
-                apply_pr_metadata(&client, pr.number, &reviewers, &labels, &assignees).await?;
+                let pr_reviewers = match &codeowners {
+                    Some(codeowners) => codeowner_reviewers(
+                        &repo,
+                        codeowners,
+                        &plan.branch,
+                        &plan.parent,
+                        author.as_deref(),
+                    ),
+                    None => reviewers.clone(),
+                };
+                apply_pr_metadata(
+                    client.as_ref(),
+                    pr.number,
+                    &pr_reviewers,
+                    &labels,
+                    &assignees,
+                )
+                .await?;
 
                 pr_infos.push(StackPrInfo {
                     branch: plan.branch.clone(),
                     pr_number: Some(pr.number),
                 });
-            } else if plan.needs_pr_update {
+            } else if let Some(pr_number) = plan.existing_pr.filter(|_| plan.needs_pr_update) {
                 // Update existing PR (only if needed)
-                let pr_number = plan.existing_pr.unwrap();
                 if !quiet {
                     print!("  Updating {} #{}... ", plan.branch, pr_number);
                     std::io::Write::flush(&mut std::io::stdout()).ok();
                 }
 
                 // Update base if needed
-                client.update_pr_base(pr_number, &plan.parent).await?;
-
-                apply_pr_metadata(&client, pr_number, &reviewers, &labels, &assignees).await?;
+                client
+                    .update_base(pr_number, &pr_base(&repo, &remote_info.name, &plan.parent))
+                    .await?;
+
+                apply_pr_metadata(client.as_ref(), pr_number, &reviewers, &labels, &assignees)
+                    .await?;
+
+                // Refresh the configured header/footer; markers keep this from stacking up
+                if !body_boilerplate.is_empty() {
+                    let current_body = client.get_pr_body(pr_number).await?;
+                    let body = body_boilerplate.apply(&current_body);
+                    if body != current_body {
+                        client.update_pr_body(pr_number, &body).await?;
+                    } else if verbose && !quiet {
+                        print!("{} ", "PR body unchanged.".dimmed());
+                    }
+                }
 
                 if !quiet {
                     println!("{}", "done".green());
@@ -831,15 +1071,17 @@ pub fn run(
                 // Get current PR state
                 let pr = client.get_pr(pr_number).await?;
 
-                let updated_meta = BranchMetadata {
-                    pr_info: Some(crate::engine::metadata::PrInfo {
-                        number: pr.number,
-                        state: pr.state.clone(),
-                        is_draft: Some(pr.is_draft),
-                    }),
-                    ..meta
-                };
-                updated_meta.write(repo.inner(), &plan.branch)?;
+                if !no_track_pr {
+                    let updated_meta = BranchMetadata {
+                        pr_info: Some(crate::engine::metadata::PrInfo {
+                            number: pr.number,
+                            state: pr.state.clone(),
+                            is_draft: Some(pr.is_draft),
+                        }),
+                        ..meta
+                    };
+                    updated_meta.write(repo.inner(), &plan.branch)?;
+                }
 
                 pr_infos.push(StackPrInfo {
                     branch: plan.branch.clone(),
@@ -854,25 +1096,20 @@ pub fn run(
             }
         }
 
-        // Update stack comment on ALL PRs in the stack
-        let prs_with_numbers: Vec<_> = pr_infos
-            .iter()
-            .filter_map(|p| p.pr_number.map(|num| (num, p.branch.clone())))
-            .collect();
+        if let Some(method) = auto_merge_method {
+            enable_auto_merge(client.as_ref(), &pr_infos, method, quiet).await?;
+        }
 
-        for (pr_number, _branch) in &prs_with_numbers {
-            if !quiet {
-                print!("  Updating stack comment on #{}... ", pr_number);
-                std::io::Write::flush(&mut std::io::stdout()).ok();
-            }
-            let stack_comment =
-                generate_stack_comment(&pr_infos, *pr_number, &remote_info, &stack.trunk);
-            client
-                .update_stack_comment(*pr_number, &stack_comment)
-                .await?;
-            if !quiet {
-                println!("{}", "done".green());
-            }
+        // Update the stack list on ALL PRs in the stack
+        if config.remote.stack_comment {
+            update_stack_comments(
+                client.as_ref(),
+                &pr_infos,
+                &remote_info,
+                &stack.trunk,
+                quiet,
+            )
+            .await?;
         }
 
         if !quiet {
@@ -900,6 +1137,95 @@ pub fn run(
     Ok(())
 }
 
+/// Warn about branches whose diff is big enough to suggest a wrong base or
+/// committed build output, and refuse them past `remote.max_pr_lines`
+fn check_diff_sizes(
+    config: &Config,
+    repo: &GitRepo,
+    stack: &Stack,
+    branches: &[String],
+    quiet: bool,
+) -> Result<()> {
+    let warn_at = config.remote.warn_pr_lines;
+    let max = config.remote.max_pr_lines;
+    let mut over_max = Vec::new();
+
+    for branch in branches {
+        let Some(parent) = stack.branches.get(branch).and_then(|b| b.parent.as_ref()) else {
+            continue;
+        };
+        let mut stat = repo.diff_stat(branch, parent).unwrap_or_default();
+        let lines: usize = stat.iter().map(|(_, added, deleted)| added + deleted).sum();
+        let blocked = max.is_some_and(|m| lines > m);
+        if !blocked && (warn_at == 0 || lines <= warn_at) {
+            continue;
+        }
+        if blocked {
+            over_max.push(branch.as_str());
+        }
+
+        if !quiet {
+            stat.sort_by_key(|s| std::cmp::Reverse(s.1 + s.2));
+            let largest: Vec<String> = stat
+                .iter()
+                .take(3)
+                .map(|(file, added, deleted)| format!("{} ({})", file, added + deleted))
+                .collect();
+            println!(
+                "  {} {} changes {} lines vs {}; check its parent and for generated files",
+                "!".yellow(),
+                branch.cyan(),
+                lines.to_string().yellow(),
+                parent
+            );
+            println!(
+                "    {} {}",
+                "largest:".dimmed(),
+                largest.join(", ").dimmed()
+            );
+        }
+    }
+
+    if !over_max.is_empty() {
+        anyhow::bail!(
+            "{} over remote.max_pr_lines ({}): {}\n\
+             Fix the branch, raise the limit, or pass --no-verify to submit anyway.",
+            if over_max.len() == 1 {
+                "Branch is"
+            } else {
+                "Branches are"
+            },
+            max.unwrap_or_default(),
+            over_max.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Write or refresh the stack list in the body of every PR in `pr_infos`
+async fn update_stack_comments(
+    client: &dyn RemoteProvider,
+    pr_infos: &[StackPrInfo],
+    remote_info: &RemoteInfo,
+    trunk: &str,
+    quiet: bool,
+) -> Result<()> {
+    for pr_number in pr_infos.iter().filter_map(|p| p.pr_number) {
+        if !quiet {
+            print!("  Updating stack list on #{}... ", pr_number);
+            std::io::Write::flush(&mut std::io::stdout()).ok();
+        }
+        let stack_comment = generate_stack_comment(pr_infos, pr_number, remote_info, trunk);
+        client
+            .update_stack_comment(pr_number, &stack_comment)
+            .await?;
+        if !quiet {
+            println!("{}", "done".green());
+        }
+    }
+    Ok(())
+}
+
 fn push_branch(workdir: &std::path::Path, remote: &str, branch: &str) -> Result<()> {
     let status = Command::new("git")
         .args(["push", "-f", "-u", remote, branch])
@@ -915,6 +1241,122 @@ fn push_branch(workdir: &std::path::Path, remote: &str, branch: &str) -> Result<
     Ok(())
 }
 
+/// Run `hooks.pre_submit` and return the branches that may be submitted.
+/// Runs once by default; per branch (with STAX_BRANCH/STAX_PARENT, in a checkout
+/// of that branch) when configured.
+fn run_pre_submit_hook(
+    config: &Config,
+    repo: &GitRepo,
+    stack: &Stack,
+    branches: Vec<String>,
+    quiet: bool,
+) -> Result<Vec<String>> {
+    let Some(command) = config
+        .hooks
+        .pre_submit
+        .as_deref()
+        .filter(|c| !c.trim().is_empty())
+    else {
+        return Ok(branches);
+    };
+    let workdir = repo.workdir()?;
+
+    if !config.hooks.pre_submit_per_branch {
+        if !quiet {
+            println!("  Running pre-submit hook: {}", command.dimmed());
+        }
+        let status = hook_command(command)
+            .current_dir(workdir)
+            .env("STAX_BRANCHES", branches.join(" "))
+            .status()
+            .context("Failed to run pre-submit hook")?;
+        if !status.success() {
+            anyhow::bail!(
+                "Pre-submit hook failed ({}). Fix the failure or rerun with --no-verify.",
+                status
+            );
+        }
+        return Ok(branches);
+    }
+
+    // Each run sees that branch's committed tree, checked out in a scratch worktree,
+    // rather than whatever is checked out here
+    let worktree = repo.add_scratch_worktree("pre-submit")?;
+    let result = run_pre_submit_hook_per_branch(config, stack, command, &worktree, branches, quiet);
+    repo.remove_worktree(&worktree)?;
+    result
+}
+
+fn run_pre_submit_hook_per_branch(
+    config: &Config,
+    stack: &Stack,
+    command: &str,
+    worktree: &Path,
+    branches: Vec<String>,
+    quiet: bool,
+) -> Result<Vec<String>> {
+    let mut skipped: HashSet<String> = HashSet::new();
+    let mut passed = Vec::new();
+    for branch in branches {
+        if skipped.contains(&branch) {
+            continue;
+        }
+        let parent = stack
+            .branches
+            .get(&branch)
+            .and_then(|b| b.parent.clone())
+            .unwrap_or_else(|| stack.trunk.clone());
+
+        if !quiet {
+            println!("  Running pre-submit hook for {}...", branch.cyan());
+        }
+        GitRepo::checkout_detached_in_worktree(worktree, &branch)?;
+        let status = hook_command(command)
+            .current_dir(worktree)
+            .env("STAX_BRANCH", &branch)
+            .env("STAX_PARENT", &parent)
+            .status()
+            .context("Failed to run pre-submit hook")?;
+
+        if status.success() {
+            passed.push(branch);
+            continue;
+        }
+        if config.hooks.pre_submit_abort_all {
+            anyhow::bail!(
+                "Pre-submit hook failed for '{}' ({}). Fix the failure or rerun with --no-verify.",
+                branch,
+                status
+            );
+        }
+        if !quiet {
+            println!(
+                "  {} Pre-submit hook failed for {}, skipping it and its descendants",
+                "✗".red(),
+                branch.cyan()
+            );
+        }
+        skipped.extend(stack.descendants(&branch));
+    }
+
+    Ok(passed)
+}
+
+fn hook_command(command: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
 fn resolve_branches_for_scope(stack: &Stack, current: &str, scope: SubmitScope) -> Vec<String> {
     let branches = match scope {
         SubmitScope::Stack => stack.current_stack(current),
@@ -987,6 +1429,11 @@ fn validate_narrow_scope_submit(
             );
         }
 
+        // A remote-tracking parent is the remote branch itself
+        if repo.is_remote_only_branch(&parent) {
+            continue;
+        }
+
         if !branch_matches_remote(repo.workdir()?, remote_name, &parent) {
             anyhow::bail!(
                 "Parent branch '{}' is not in sync with '{}/{}'.\n\
@@ -1003,6 +1450,23 @@ fn validate_narrow_scope_submit(
     Ok(())
 }
 
+/// Add `login` to the assignees unless it is already there (GitHub logins are case-insensitive)
+fn with_assignee(mut assignees: Vec<String>, login: String) -> Vec<String> {
+    if !assignees.iter().any(|a| a.eq_ignore_ascii_case(&login)) {
+        assignees.push(login);
+    }
+    assignees
+}
+
+/// The PR base for a tracked parent: remote-tracking parents such as
+/// `origin/integration` name the branch on the remote
+pub(crate) fn pr_base(repo: &GitRepo, remote: &str, parent: &str) -> String {
+    match parent.strip_prefix(&format!("{}/", remote)) {
+        Some(branch) if repo.is_remote_only_branch(parent) => branch.to_string(),
+        _ => parent.to_string(),
+    }
+}
+
 /// Check if a branch needs to be pushed (local differs from remote)
 fn branch_needs_push(workdir: &Path, remote: &str, branch: &str) -> bool {
     // Get local commit
@@ -1166,8 +1630,55 @@ fn load_pr_template(workdir: &Path) -> Option<String> {
     None
 }
 
+/// Queue GitHub auto-merge on every non-draft PR of the submitted branches
+async fn enable_auto_merge(
+    client: &dyn RemoteProvider,
+    prs: &[StackPrInfo],
+    method: MergeMethod,
+    quiet: bool,
+) -> Result<()> {
+    if !quiet {
+        println!();
+        println!(
+            "{}",
+            format!("Enabling auto-merge ({})...", method.as_str()).bold()
+        );
+    }
+
+    for number in prs.iter().filter_map(|pr| pr.pr_number) {
+        if client.get_pr(number).await?.is_draft {
+            if !quiet {
+                println!("  {} #{} is a draft, skipped", "-".dimmed(), number);
+            }
+            continue;
+        }
+        client.enable_auto_merge(number, method).await?;
+        if !quiet {
+            println!("  {} #{}", "✓".green(), number);
+        }
+    }
+
+    Ok(())
+}
+
+/// CODEOWNERS of the files a branch changes, minus the PR author (GitHub rejects that request)
+fn codeowner_reviewers(
+    repo: &GitRepo,
+    codeowners: &CodeOwners,
+    branch: &str,
+    parent: &str,
+    author: Option<&str>,
+) -> Vec<String> {
+    let files = repo.files_modified(branch, parent).unwrap_or_default();
+    codeowners
+        .reviewers_for(files.iter().map(String::as_str))
+        .into_iter()
+        .filter(|reviewer| author.is_none_or(|author| !reviewer.eq_ignore_ascii_case(author)))
+        .collect()
+}
+
 async fn apply_pr_metadata(
-    client: &GitHubClient,
+    client: &dyn RemoteProvider,
     pr_number: u64,
     reviewers: &[String],
     labels: &[String],
@@ -1213,9 +1724,33 @@ fn generate_ai_body(
     let model = config.ai.model.clone();
 
     let diff_stat = generate::get_diff_stat(workdir, parent, branch);
-    let diff = generate::get_full_diff(workdir, parent, branch);
+    let diff = generate::get_full_diff(workdir, parent, branch, &config.ai.diff_exclude);
     let commits = collect_commit_messages(workdir, parent, branch);
-    let prompt = generate::build_ai_prompt(&diff_stat, &diff, &commits, template);
+    let prompt = generate::build_ai_prompt(
+        &diff_stat,
+        &diff,
+        &commits,
+        template,
+        config.ai.max_diff_bytes,
+    );
 
     generate::invoke_ai_agent(&agent, model.as_deref(), &prompt)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_assignee_skips_existing_login() {
+        let assignees = vec!["Octocat".to_string(), "alice".to_string()];
+        assert_eq!(
+            with_assignee(assignees.clone(), "octocat".to_string()),
+            assignees
+        );
+        assert_eq!(
+            with_assignee(vec!["alice".to_string()], "bob".to_string()),
+            vec!["alice".to_string(), "bob".to_string()]
+        );
+    }
+}
@@ -14,6 +14,7 @@ use std::io::Write;
 use std::process::Command;
 
 /// Sync repo: pull trunk from remote, delete merged branches, optionally restack
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     restack: bool,
     delete_merged: bool,
@@ -23,13 +24,9 @@ pub fn run(
     quiet: bool,
     verbose: bool,
     auto_stash_pop: bool,
+    no_fetch: bool,
 ) -> Result<()> {
     let repo = GitRepo::open()?;
-    let stack = Stack::load(&repo)?;
-    let current = repo.current_branch()?;
-    let workdir = repo.workdir()?;
-    let config = Config::load()?;
-    let remote_name = config.remote_name().to_string();
 
     if r#continue {
         crate::commands::continue_cmd::run()?;
@@ -37,6 +34,13 @@ pub fn run(
             return Ok(());
         }
     }
+    crate::commands::restack::ensure_no_rebase_in_progress(&repo)?;
+
+    let stack = Stack::load(&repo)?;
+    let current = repo.current_branch()?;
+    let workdir = repo.workdir()?;
+    let config = Config::load()?;
+    let remote_name = config.remote_name().to_string();
 
     let auto_confirm = force;
     let mut stashed = false;
@@ -69,37 +73,50 @@ pub fn run(
         println!("{}", "Syncing repository...".bold());
     }
 
-    // 1. Fetch from remote
-    if !quiet {
-        print!("  Fetching from {}... ", remote_name);
-        let _ = std::io::stdout().flush();
-    }
+    // 1. Fetch from remote (--no-fetch works from the refs of the last fetch)
+    if no_fetch {
+        if !quiet {
+            println!(
+                "  {}",
+                format!(
+                    "Skipping fetch; using {} refs from the last fetch",
+                    remote_name
+                )
+                .dimmed()
+            );
+        }
+    } else {
+        if !quiet {
+            print!("  Fetching from {}... ", remote_name);
+            let _ = std::io::stdout().flush();
+        }
 
-    let output = Command::new("git")
-        .args(["fetch", &remote_name])
-        .current_dir(workdir)
-        .output()
-        .context("Failed to fetch")?;
+        let output = Command::new("git")
+            .args(["fetch", &remote_name])
+            .current_dir(workdir)
+            .output()
+            .context("Failed to fetch")?;
 
-    if !quiet {
-        if output.status.success() {
-            println!("{}", "done".green());
-            if verbose {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                if !stderr.trim().is_empty() {
-                    for line in stderr.lines() {
-                        println!("    {}", line.dimmed());
+        if !quiet {
+            if output.status.success() {
+                println!("{}", "done".green());
+                if verbose {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    if !stderr.trim().is_empty() {
+                        for line in stderr.lines() {
+                            println!("    {}", line.dimmed());
+                        }
                     }
                 }
-            }
-        } else {
-            // Fetch may fail partially (lock files, etc.) but still update most refs
-            println!("{}", "done (with warnings)".yellow());
-            if verbose {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                if !stderr.trim().is_empty() {
-                    for line in stderr.lines() {
-                        println!("    {}", line.dimmed());
+            } else {
+                // Fetch may fail partially (lock files, etc.) but still update most refs
+                println!("{}", "done (with warnings)".yellow());
+                if verbose {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    if !stderr.trim().is_empty() {
+                        for line in stderr.lines() {
+                            println!("    {}", line.dimmed());
+                        }
                     }
                 }
             }
@@ -120,7 +137,7 @@ pub fn run(
         }
 
         let output = Command::new("git")
-            .args(["pull", "--ff-only", &remote_name, &stack.trunk])
+            .args(pull_args(&remote_name, &stack.trunk, no_fetch))
             .current_dir(workdir)
             .output()
             .context("Failed to pull trunk")?;
@@ -185,7 +202,7 @@ pub fn run(
 
         if let Some(trunk_worktree_path) = repo.branch_worktree_path(&stack.trunk)? {
             let output = Command::new("git")
-                .args(["pull", "--ff-only", &remote_name, &stack.trunk])
+                .args(pull_args(&remote_name, &stack.trunk, no_fetch))
                 .current_dir(&trunk_worktree_path)
                 .output()
                 .context("Failed to pull trunk in its worktree")?;
@@ -235,12 +252,18 @@ pub fn run(
             }
         } else {
             // Trunk isn't checked out in any worktree; update via refspec fetch.
+            // Fetching from the repo itself keeps the fast-forward check offline.
+            let source = if no_fetch { "." } else { remote_name.as_str() };
+            let refspec = if no_fetch {
+                format!(
+                    "refs/remotes/{}/{}:refs/heads/{}",
+                    remote_name, stack.trunk, stack.trunk
+                )
+            } else {
+                format!("{}:{}", stack.trunk, stack.trunk)
+            };
             let output = Command::new("git")
-                .args([
-                    "fetch",
-                    &remote_name,
-                    &format!("{}:{}", stack.trunk, stack.trunk),
-                ])
+                .args(["fetch", source, &refspec])
                 .current_dir(workdir)
                 .output()
                 .context("Failed to update trunk")?;
@@ -259,32 +282,107 @@ pub fn run(
         }
     }
 
+    // 2b. Relink branches whose upstream was renamed on the remote
+    // (detection asks the remote directly, so --no-fetch skips it)
+    let renamed = if no_fetch {
+        Vec::new()
+    } else {
+        find_renamed_upstreams(&repo, &stack, &remote_name)?
+    };
+    let mut relinked_any = false;
+    if !renamed.is_empty() {
+        let github_client = github_client(&repo, &config);
+
+        for rename in &renamed {
+            if !quiet {
+                println!(
+                    "  {} upstream {} is gone; same commit found at {}",
+                    rename.branch.cyan(),
+                    format!("{}/{}", remote_name, rename.old_upstream).bright_black(),
+                    format!("{}/{}", remote_name, rename.new_upstream).cyan()
+                );
+            }
+
+            let confirm = if auto_confirm {
+                true
+            } else if quiet {
+                false
+            } else {
+                let prompt = if rename.branch == rename.new_upstream {
+                    format!(
+                        "Point '{}' at '{}/{}'?",
+                        rename.branch, remote_name, rename.new_upstream
+                    )
+                } else {
+                    format!(
+                        "Rename local '{}' to '{}' to follow {}/{}?",
+                        rename.branch, rename.new_upstream, remote_name, rename.new_upstream
+                    )
+                };
+                Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt(prompt)
+                    .default(true)
+                    .interact()?
+            };
+
+            if !confirm {
+                if !quiet {
+                    println!(
+                        "    {} {}",
+                        rename.branch.bright_black(),
+                        "skipped".dimmed()
+                    );
+                }
+                continue;
+            }
+
+            match relink_renamed_branch(&repo, &stack, &remote_name, rename, &github_client) {
+                Ok(()) => {
+                    relinked_any = true;
+                    if !quiet && rename.branch != rename.new_upstream {
+                        println!(
+                            "    {} renamed local {} → {} to follow {}",
+                            "↪".cyan(),
+                            rename.branch.cyan(),
+                            rename.new_upstream.cyan(),
+                            format!("{}/{}", remote_name, rename.new_upstream).cyan()
+                        );
+                    } else if !quiet {
+                        println!(
+                            "    {} relinked {} → {}",
+                            "↪".cyan(),
+                            rename.branch.cyan(),
+                            format!("{}/{}", remote_name, rename.new_upstream).cyan()
+                        );
+                    }
+                }
+                Err(e) => {
+                    if !quiet {
+                        println!(
+                            "    {} couldn't relink {}: {}",
+                            "⚠".yellow(),
+                            rename.branch,
+                            e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Local branch names may have changed; reload so later steps see the new names
+    let (stack, current) = if relinked_any {
+        (Stack::load(&repo)?, repo.current_branch()?)
+    } else {
+        (stack, current)
+    };
+
     // 3. Delete merged branches
     if delete_merged {
         let merged = find_merged_branches(workdir, &stack, &remote_name)?;
 
         // Lazy-initialize GitHub client for updating PR bases (only if needed)
-        let github_client: Option<(tokio::runtime::Runtime, GitHubClient)> = {
-            let remote_info = RemoteInfo::from_repo(&repo, &config).ok();
-            let has_github_token = Config::github_token().is_some();
-
-            if has_github_token {
-                if let Some(info) = remote_info {
-                    tokio::runtime::Runtime::new().ok().and_then(|rt| {
-                        // Must create client inside block_on - Octocrab requires runtime context
-                        rt.block_on(async {
-                            GitHubClient::new(info.owner(), &info.repo, info.api_base_url.clone())
-                                .ok()
-                        })
-                        .map(|client| (rt, client))
-                    })
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        };
+        let github_client = github_client(&repo, &config);
 
         if !merged.is_empty() {
             if !quiet {
@@ -353,7 +451,7 @@ pub fn run(
 
                             // Pull latest changes for the parent branch
                             let pull_status = Command::new("git")
-                                .args(["pull", "--ff-only", &remote_name, &parent_branch])
+                                .args(pull_args(&remote_name, &parent_branch, no_fetch))
                                 .current_dir(workdir)
                                 .stdout(std::process::Stdio::null())
                                 .stderr(std::process::Stdio::null())
@@ -547,7 +645,7 @@ pub fn run(
         }
 
         let output = Command::new("git")
-            .args(["pull", "--ff-only", &remote_name, &stack.trunk])
+            .args(pull_args(&remote_name, &stack.trunk, no_fetch))
             .current_dir(workdir)
             .output()
             .context("Failed to pull trunk")?;
@@ -674,8 +772,9 @@ pub fn run(
 
                 match repo.rebase_branch_onto(branch, &meta.parent_branch_name, auto_stash_pop)? {
                     RebaseResult::Success => {
-                        let parent_commit = repo.branch_commit(&meta.parent_branch_name)?;
+                        let parent_commit = repo.parent_commit(&meta.parent_branch_name)?;
                         let updated_meta = BranchMetadata {
+                            fork_point: Some(parent_commit.clone()),
                             parent_branch_revision: parent_commit,
                             ..meta
                         };
@@ -963,6 +1062,215 @@ fn find_merged_branches(
     Ok(merged)
 }
 
+/// GitHub client (with its runtime) when a token and a GitHub remote are available
+fn github_client(
+    repo: &GitRepo,
+    config: &Config,
+) -> Option<(tokio::runtime::Runtime, GitHubClient)> {
+    Config::github_token()?;
+    let info = RemoteInfo::from_repo(repo, config).ok()?;
+    let rt = tokio::runtime::Runtime::new().ok()?;
+    // Must create client inside block_on - Octocrab requires runtime context
+    let client = rt.block_on(async {
+        GitHubClient::new(info.owner(), &info.repo, info.api_base_url.clone()).ok()
+    })?;
+    Some((rt, client))
+}
+
+/// A tracked branch whose recorded upstream disappeared from the remote while
+/// another remote branch points at the same commit (i.e. it was renamed)
+#[derive(Debug)]
+struct RenamedUpstream {
+    branch: String,
+    old_upstream: String,
+    new_upstream: String,
+}
+
+/// Find tracked branches whose upstream was renamed on the remote
+fn find_renamed_upstreams(
+    repo: &GitRepo,
+    stack: &Stack,
+    remote_name: &str,
+) -> Result<Vec<RenamedUpstream>> {
+    let workdir = repo.workdir()?;
+
+    // Ask the remote directly: remote-tracking refs for deleted branches linger without --prune
+    let output = Command::new("git")
+        .args(["ls-remote", "--heads", remote_name])
+        .current_dir(workdir)
+        .output()
+        .context("Failed to list remote heads")?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let remote_heads: Vec<(String, String)> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (sha, name) = line.split_once('\t')?;
+            let name = name.strip_prefix("refs/heads/")?;
+            Some((name.to_string(), sha.to_string()))
+        })
+        .collect();
+
+    let mut renamed = Vec::new();
+
+    for branch in stack.branches.keys() {
+        if branch == &stack.trunk {
+            continue;
+        }
+
+        // Only branches with an upstream on this remote (i.e. pushed before)
+        let upstream_remote = git_config(workdir, &format!("branch.{}.remote", branch));
+        if upstream_remote.as_deref() != Some(remote_name) {
+            continue;
+        }
+        let Some(old_upstream) = git_config(workdir, &format!("branch.{}.merge", branch))
+            .and_then(|merge| merge.strip_prefix("refs/heads/").map(str::to_string))
+        else {
+            continue;
+        };
+
+        if remote_heads.iter().any(|(name, _)| name == &old_upstream) {
+            continue;
+        }
+
+        let Ok(tip) = repo.branch_commit(branch) else {
+            continue;
+        };
+
+        // Other tracked branches own their same-named remotes; don't steal them
+        let candidates: Vec<&String> = remote_heads
+            .iter()
+            .filter(|(name, sha)| {
+                sha == &tip
+                    && name != &stack.trunk
+                    && (name == branch || !stack.branches.contains_key(name))
+            })
+            .map(|(name, _)| name)
+            .collect();
+
+        // Ambiguous matches are left alone rather than guessed at
+        if let [new_upstream] = candidates.as_slice() {
+            renamed.push(RenamedUpstream {
+                branch: branch.clone(),
+                old_upstream,
+                new_upstream: (*new_upstream).clone(),
+            });
+        }
+    }
+
+    Ok(renamed)
+}
+
+/// Rename the local branch to match its renamed upstream, move its metadata and
+/// children, point the upstream at the new remote branch, and refresh its PR
+fn relink_renamed_branch(
+    repo: &GitRepo,
+    stack: &Stack,
+    remote_name: &str,
+    rename: &RenamedUpstream,
+    github_client: &Option<(tokio::runtime::Runtime, GitHubClient)>,
+) -> Result<()> {
+    let workdir = repo.workdir()?;
+    let new_name = &rename.new_upstream;
+
+    if new_name != &rename.branch {
+        if repo.branch_commit(new_name).is_ok() {
+            anyhow::bail!("local branch '{}' already exists", new_name);
+        }
+
+        let status = Command::new("git")
+            .args(["branch", "-m", &rename.branch, new_name])
+            .current_dir(workdir)
+            .status()
+            .context("Failed to rename branch")?;
+        if !status.success() {
+            anyhow::bail!("git branch -m failed");
+        }
+
+        if let Some(meta) = BranchMetadata::read(repo.inner(), &rename.branch)? {
+            meta.write(repo.inner(), new_name)?;
+            crate::git::refs::delete_metadata(repo.inner(), &rename.branch)?;
+        }
+
+        for (child_name, child_info) in &stack.branches {
+            if child_info.parent.as_deref() == Some(rename.branch.as_str()) {
+                if let Some(mut meta) = BranchMetadata::read(repo.inner(), child_name)? {
+                    meta.parent_branch_name = new_name.clone();
+                    meta.write(repo.inner(), child_name)?;
+                }
+            }
+        }
+    }
+
+    let status = Command::new("git")
+        .args([
+            "branch",
+            &format!("--set-upstream-to={}/{}", remote_name, new_name),
+            new_name,
+        ])
+        .current_dir(workdir)
+        .stdout(std::process::Stdio::null())
+        .status()
+        .context("Failed to set upstream")?;
+    if !status.success() {
+        anyhow::bail!("git branch --set-upstream-to failed");
+    }
+
+    // GitHub moves open PRs along with a renamed head; pick up whichever PR now tracks it
+    if let Some((rt, client)) = github_client {
+        if let Ok(Some(pr)) = rt.block_on(client.find_pr(new_name)) {
+            if let Some(meta) = BranchMetadata::read(repo.inner(), new_name)? {
+                BranchMetadata {
+                    pr_info: Some(crate::engine::metadata::PrInfo {
+                        number: pr.number,
+                        state: pr.state,
+                        is_draft: Some(pr.is_draft),
+                    }),
+                    ..meta
+                }
+                .write(repo.inner(), new_name)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fast-forward `branch` from the remote, or from its remote-tracking ref
+/// without touching the network when `no_fetch` is set
+fn pull_args(remote_name: &str, branch: &str, no_fetch: bool) -> Vec<String> {
+    if no_fetch {
+        vec![
+            "merge".to_string(),
+            "--ff-only".to_string(),
+            format!("{}/{}", remote_name, branch),
+        ]
+    } else {
+        vec![
+            "pull".to_string(),
+            "--ff-only".to_string(),
+            remote_name.to_string(),
+            branch.to_string(),
+        ]
+    }
+}
+
+fn git_config(workdir: &std::path::Path, key: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "--get", key])
+        .current_dir(workdir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
 /// Refresh CI cache by fetching latest CI states from GitHub
 fn refresh_ci_cache(
     repo: &GitRepo,
@@ -1,12 +1,13 @@
 //! Undo the last stax operation (or a specific one).
 
 use crate::config::Config;
-use crate::git::GitRepo;
+use crate::git::{refs, GitRepo};
 use crate::ops;
 use crate::ops::receipt::{OpReceipt, OpStatus};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::io::IsTerminal;
 
 pub fn run(op_id: Option<String>, yes: bool, no_push: bool, quiet: bool) -> Result<()> {
     let repo = GitRepo::open()?;
@@ -45,42 +46,38 @@ pub fn run(op_id: Option<String>, yes: bool, no_push: bool, quiet: bool) -> Resu
         );
     }
 
-    // Check for rebase in progress
+    // Moving refs under a rebase or uncommitted work would lose track of either
     if repo.rebase_in_progress()? {
-        if !quiet {
-            println!("  {} Aborting in-progress rebase...", "▸".dimmed());
-        }
-        repo.rebase_abort()?;
+        anyhow::bail!(
+            "A rebase is in progress. Finish it with `stax continue` or run `git rebase --abort` before undoing."
+        );
     }
-
-    // Check for dirty working tree
     if repo.is_dirty()? {
-        if quiet {
-            anyhow::bail!("Working tree is dirty. Please stash or commit changes first.");
-        }
+        anyhow::bail!("Working tree is dirty. Please stash or commit changes first.");
+    }
 
-        let stash = if yes {
-            true
-        } else {
-            Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt("Working tree has uncommitted changes. Stash them?")
-                .default(true)
-                .interact()?
-        };
+    let head_branch_before = receipt.head_branch_before.clone();
+    let current = repo.current_branch().ok();
+    let return_to = Some(head_branch_before.as_str())
+        .filter(|b| !b.is_empty() && current.as_deref() != Some(*b));
 
-        if stash {
-            repo.stash_push()?;
-            if !quiet {
-                println!("  {} Stashed working tree changes.", "✓".green());
+    if !quiet {
+        print_plan(&repo, &receipt, return_to);
+
+        if !yes && std::io::stdin().is_terminal() {
+            let confirm = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Move these refs?")
+                .default(true)
+                .interact()?;
+            if !confirm {
+                println!("{}", "Aborted.".red());
+                return Ok(());
             }
-        } else {
-            anyhow::bail!("Cannot undo with dirty working tree");
         }
     }
 
     // Restore local refs
     let mut restored_count = 0;
-    let head_branch_before = receipt.head_branch_before.clone();
 
     if !quiet {
         println!();
@@ -101,6 +98,9 @@ pub fn run(op_id: Option<String>, yes: bool, no_push: bool, quiet: bool) -> Resu
 
             // Update the ref to the before-OID
             repo.update_ref(&entry.refname, oid_before)?;
+            if let Some(json) = &entry.metadata_before {
+                refs::write_metadata(repo.inner(), &entry.branch, json)?;
+            }
 
             if !quiet {
                 println!("{}", "done".green());
@@ -118,33 +118,23 @@ pub fn run(op_id: Option<String>, yes: bool, no_push: bool, quiet: bool) -> Resu
         }
     }
 
-    // If the head branch was modified, reset the working tree
-    if receipt
-        .local_refs
-        .iter()
-        .any(|r| r.branch == head_branch_before)
-    {
-        if !quiet {
-            println!(
-                "  {} Resetting working tree to {}...",
-                "▸".dimmed(),
-                head_branch_before.cyan()
-            );
-        }
-
-        // Make sure we're on that branch
-        repo.checkout(&head_branch_before)?;
-
-        // Reset to the restored ref
-        if let Some(entry) = receipt
+    // The checked-out branch's ref moved underneath the worktree; bring the worktree along
+    if let Some(oid_before) = current.as_ref().and_then(|current| {
+        receipt
             .local_refs
             .iter()
-            .find(|r| r.branch == head_branch_before)
-        {
-            if let Some(oid_before) = &entry.oid_before {
-                repo.reset_hard(oid_before)?;
-            }
+            .find(|r| &r.branch == current)
+            .and_then(|r| r.oid_before.as_ref())
+    }) {
+        repo.reset_hard(oid_before)?;
+    }
+
+    // Put the user back on the branch the operation started from
+    if let Some(branch) = return_to.filter(|b| repo.branch_commit(b).is_ok()) {
+        if !quiet {
+            println!("  {} Checking out {}...", "▸".dimmed(), branch.cyan());
         }
+        repo.checkout(branch)?;
     }
 
     // Handle remote refs
@@ -216,6 +206,34 @@ pub fn run(op_id: Option<String>, yes: bool, no_push: bool, quiet: bool) -> Resu
     Ok(())
 }
 
+/// List the refs undo will move (and the branch it returns to) before touching anything
+fn print_plan(repo: &GitRepo, receipt: &OpReceipt, return_to: Option<&str>) {
+    let short = |oid: &str| oid[..oid.len().min(7)].to_string();
+
+    println!();
+    println!("{}", "Plan:".bold());
+    for entry in &receipt.local_refs {
+        let Some(oid_before) = &entry.oid_before else {
+            continue;
+        };
+        let now = repo
+            .branch_commit(&entry.branch)
+            .map(|oid| short(&oid))
+            .unwrap_or_else(|_| "missing".to_string());
+        println!(
+            "  {} {} {} → {}",
+            "▸".dimmed(),
+            entry.branch.cyan(),
+            now.dimmed(),
+            short(oid_before)
+        );
+    }
+    if let Some(branch) = return_to {
+        println!("  {} then check out {}", "▸".dimmed(), branch.cyan());
+    }
+    println!();
+}
+
 /// Restore remote refs by force-pushing
 fn restore_remote_refs(repo: &GitRepo, receipt: &OpReceipt, quiet: bool) -> Result<()> {
     let config = Config::load()?;
@@ -6,11 +6,21 @@ use crate::ops::tx::{self, Transaction};
 use anyhow::Result;
 use colored::Colorize;
 
-pub fn run(auto_stash_pop: bool) -> Result<()> {
+pub fn run(auto_stash_pop: bool, dry_run: bool) -> Result<()> {
     let repo = GitRepo::open()?;
+    crate::commands::restack::ensure_no_rebase_in_progress(&repo)?;
     let current = repo.current_branch()?;
     let stack = Stack::load(&repo)?;
 
+    if !stack.has_tracked_branches() {
+        println!(
+            "{}",
+            "No tracked branches yet, nothing to restack.".dimmed()
+        );
+        crate::commands::print_empty_stack_hint();
+        return Ok(());
+    }
+
     // Scope is current branch + descendants (excluding trunk); evaluate
     // restack status live per branch while walking this order.
     let mut upstack = vec![current.clone()];
@@ -48,15 +58,6 @@ pub fn run(auto_stash_pop: bool) -> Result<()> {
     } else {
         "branches"
     };
-    println!(
-        "Restacking up to {} {}...",
-        upstack.len().to_string().cyan(),
-        branch_word
-    );
-
-    // Begin transaction
-    let mut tx = Transaction::begin(OpKind::UpstackRestack, &repo, false)?;
-    tx.plan_branches(&repo, &upstack)?;
     let summary = PlanSummary {
         branches_to_rebase: upstack.len(),
         branches_to_push: 0,
@@ -66,6 +67,26 @@ pub fn run(auto_stash_pop: bool) -> Result<()> {
             branch_word
         )],
     };
+
+    if dry_run {
+        return crate::commands::restack::print_dry_run(
+            &repo,
+            &stack,
+            &upstack,
+            OpKind::UpstackRestack,
+            &summary,
+        );
+    }
+
+    println!(
+        "Restacking up to {} {}...",
+        upstack.len().to_string().cyan(),
+        branch_word
+    );
+
+    // Begin transaction
+    let mut tx = Transaction::begin(OpKind::UpstackRestack, &repo, false)?;
+    tx.plan_branches(&repo, &upstack)?;
     tx::print_plan(tx.kind(), &summary, false);
     tx.set_plan_summary(summary);
     tx.snapshot()?;
@@ -94,12 +115,9 @@ pub fn run(auto_stash_pop: bool) -> Result<()> {
 
         match repo.rebase_branch_onto(branch, &meta.parent_branch_name, auto_stash_pop)? {
             RebaseResult::Success => {
-                let new_parent_rev = repo.branch_commit(&meta.parent_branch_name)?;
-                let updated_meta = BranchMetadata {
-                    parent_branch_revision: new_parent_rev,
-                    ..meta
-                };
-                updated_meta.write(repo.inner(), branch)?;
+                let new_parent_rev = repo.parent_commit(&meta.parent_branch_name)?;
+                meta.restacked_onto(new_parent_rev)
+                    .write(repo.inner(), branch)?;
 
                 // Record the after-OID for this branch
                 tx.record_after(&repo, branch)?;
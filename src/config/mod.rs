@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -17,6 +17,8 @@ pub struct Config {
     pub ai: AiConfig,
     #[serde(default)]
     pub auth: AuthConfig,
+    #[serde(default)]
+    pub hooks: HooksConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +41,7 @@ pub struct BranchConfig {
     /// Branch name format template. Placeholders:
     /// - {user}: Git username (from config.branch.user or git user.name)
     /// - {date}: Current date (formatted by date_format)
+    /// - {ticket}: `--ticket` value or a leading ticket ID in the input (e.g. "PROJ-123")
     /// - {message}: The branch name/message input
     ///
     /// Examples: "{message}", "{user}/{message}", "{user}/{date}/{message}"
@@ -47,6 +50,25 @@ pub struct BranchConfig {
     /// Username for branch naming. If not set, uses git config user.name
     #[serde(default)]
     pub user: Option<String>,
+    /// Transliterate non-ASCII text to ASCII before sanitizing (e.g., "Café" -> "Cafe")
+    #[serde(default)]
+    pub transliterate: bool,
+    /// Fold accented Latin letters to ASCII (e.g., "déjà" -> "deja", "ß" -> "ss") and replace
+    /// any other non-ASCII character, so names stay plain ASCII without romanizing other scripts.
+    /// Can't be combined with `transliterate`.
+    #[serde(default)]
+    pub ascii_fold: bool,
+    /// Lowercase generated branch names (e.g., "Fix Login" -> "fix-login")
+    #[serde(default)]
+    pub lowercase: bool,
+    /// Long-lived branches (e.g., "develop", "release") that `stax branch delete` refuses
+    /// to remove unless given `--force --i-really-mean-it`
+    #[serde(default)]
+    pub protected: Vec<String>,
+    /// Key in `branch.<name>.description` naming a branch's parent, e.g. "stax-parent".
+    /// When set, `stax branch track` uses a `<key>: <parent>` line as the default parent.
+    #[serde(default)]
+    pub parent_description_key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -60,6 +82,36 @@ pub struct RemoteConfig {
     /// API base URL (GitHub Enterprise), e.g., https://github.company.com/api/v3
     #[serde(default)]
     pub api_base_url: Option<String>,
+    /// Forge hosting the remote: "github", "gitlab", or "bitbucket" (default: detect from host)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Warn before submitting a branch whose diff changes more lines than this (0 disables)
+    #[serde(default = "default_warn_pr_lines")]
+    pub warn_pr_lines: usize,
+    /// Refuse to submit a branch whose diff changes more lines than this
+    #[serde(default)]
+    pub max_pr_lines: Option<usize>,
+    /// Text (or path to a file) placed above every PR body on submit
+    #[serde(default)]
+    pub pr_body_header: Option<String>,
+    /// Text (or path to a file) appended to every PR body on submit
+    #[serde(default)]
+    pub pr_body_footer: Option<String>,
+    /// Keep a list of the whole stack in each PR body on submit (default: true)
+    #[serde(default = "default_stack_comment")]
+    pub stack_comment: bool,
+    /// Trunk branch name, overriding detection (default: `<remote>/HEAD`, then main/master)
+    #[serde(default)]
+    pub trunk: Option<String>,
+    /// Retries for GitHub API calls failing with 5xx or a rate limit (default: 3)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Request reviews from the CODEOWNERS of changed files when submit gets no --reviewers
+    #[serde(default)]
+    pub auto_request_codeowners: bool,
+    /// Merge method for `submit --auto-merge`: merge, squash, or rebase (default: squash)
+    #[serde(default)]
+    pub merge_method: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -67,9 +119,15 @@ pub struct UiConfig {
     /// Whether to show contextual tips/suggestions (default: true)
     #[serde(default = "default_tips")]
     pub tips: bool,
+    /// Render status as one dense line per branch (default: false)
+    #[serde(default)]
+    pub compact: bool,
+    /// Check crates.io for new stax releases (default: true)
+    #[serde(default = "default_update_check")]
+    pub update_check: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AiConfig {
     /// AI agent to use: "claude", "codex", "gemini", or "opencode" (default: auto-detect)
     #[serde(default)]
@@ -77,6 +135,12 @@ pub struct AiConfig {
     /// Model to use with the AI agent (default: agent's own default)
     #[serde(default)]
     pub model: Option<String>,
+    /// Glob patterns kept out of the diff sent to the agent (default: common lockfiles)
+    #[serde(default = "default_ai_diff_exclude")]
+    pub diff_exclude: Vec<String>,
+    /// Bytes of diff sent to the agent before truncating (default: 80000, capped at 4MB)
+    #[serde(default = "default_ai_max_diff_bytes")]
+    pub max_diff_bytes: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -90,6 +154,24 @@ pub struct AuthConfig {
     /// Optional GitHub hostname for `gh auth token --hostname` (enterprise)
     #[serde(default)]
     pub gh_hostname: Option<String>,
+    /// Sign branch metadata with a local key and warn when it changes outside stax (default: false)
+    #[serde(default)]
+    pub sign_metadata: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Command run before submit pushes anything (e.g., "cargo test"). Skip with --no-verify
+    #[serde(default)]
+    pub pre_submit: Option<String>,
+    /// Run pre_submit once per branch, in a scratch checkout of it, with STAX_BRANCH/STAX_PARENT
+    /// set (default: false, run once)
+    #[serde(default)]
+    pub pre_submit_per_branch: bool,
+    /// When a per-branch run fails, abort the whole submit (default: true).
+    /// If false, only that branch and its descendants are skipped.
+    #[serde(default = "default_pre_submit_abort_all")]
+    pub pre_submit_abort_all: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -132,6 +214,11 @@ impl Default for BranchConfig {
             replacement: default_replacement(),
             format: None,
             user: None,
+            transliterate: false,
+            ascii_fold: false,
+            lowercase: false,
+            protected: Vec::new(),
+            parent_description_key: None,
         }
     }
 }
@@ -140,12 +227,33 @@ fn default_date_format() -> String {
     "%m-%d".to_string()
 }
 
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            agent: None,
+            model: None,
+            diff_exclude: default_ai_diff_exclude(),
+            max_diff_bytes: default_ai_max_diff_bytes(),
+        }
+    }
+}
+
 impl Default for RemoteConfig {
     fn default() -> Self {
         Self {
             name: default_remote_name(),
             base_url: default_remote_base_url(),
             api_base_url: None,
+            provider: None,
+            warn_pr_lines: default_warn_pr_lines(),
+            max_pr_lines: None,
+            pr_body_header: None,
+            pr_body_footer: None,
+            stack_comment: default_stack_comment(),
+            trunk: None,
+            max_retries: default_max_retries(),
+            auto_request_codeowners: false,
+            merge_method: None,
         }
     }
 }
@@ -154,6 +262,8 @@ impl Default for UiConfig {
     fn default() -> Self {
         Self {
             tips: default_tips(),
+            compact: false,
+            update_check: default_update_check(),
         }
     }
 }
@@ -164,6 +274,7 @@ impl Default for AuthConfig {
             use_gh_cli: default_use_gh_cli(),
             allow_github_token_env: default_allow_github_token_env(),
             gh_hostname: None,
+            sign_metadata: false,
         }
     }
 }
@@ -176,6 +287,10 @@ fn default_remote_name() -> String {
     "origin".to_string()
 }
 
+fn default_warn_pr_lines() -> usize {
+    2000
+}
+
 fn default_remote_base_url() -> String {
     "https://github.com".to_string()
 }
@@ -184,6 +299,53 @@ fn default_tips() -> bool {
     true
 }
 
+fn default_update_check() -> bool {
+    true
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            pre_submit: None,
+            pre_submit_per_branch: false,
+            pre_submit_abort_all: default_pre_submit_abort_all(),
+        }
+    }
+}
+
+fn default_pre_submit_abort_all() -> bool {
+    true
+}
+
+fn default_stack_comment() -> bool {
+    true
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_ai_max_diff_bytes() -> usize {
+    80_000
+}
+
+fn default_ai_diff_exclude() -> Vec<String> {
+    [
+        "Cargo.lock",
+        "package-lock.json",
+        "yarn.lock",
+        "pnpm-lock.yaml",
+        "bun.lockb",
+        "Gemfile.lock",
+        "poetry.lock",
+        "composer.lock",
+        "go.sum",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
 fn default_use_gh_cli() -> bool {
     true
 }
@@ -193,8 +355,15 @@ fn default_allow_github_token_env() -> bool {
 }
 
 impl Config {
-    /// Get the config directory (~/.config/stax on all platforms)
+    /// Get the config directory: `$XDG_CONFIG_HOME/stax`, falling back to
+    /// `~/.config/stax` when it is unset, empty, or relative (per the XDG spec)
     pub fn dir() -> Result<PathBuf> {
+        if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+        {
+            return Ok(xdg.join("stax"));
+        }
         let home = dirs::home_dir().context("Could not find home directory")?;
         Ok(home.join(".config").join("stax"))
     }
@@ -205,10 +374,47 @@ impl Config {
     }
 
     /// Get the credentials file path (separate from config, not for dotfiles)
-    fn credentials_path() -> Result<PathBuf> {
+    pub fn credentials_path() -> Result<PathBuf> {
         Ok(Self::dir()?.join(".credentials"))
     }
 
+    /// Get the metadata signing key path (local only, never shared)
+    fn metadata_key_path() -> Result<PathBuf> {
+        Ok(Self::dir()?.join(".metadata-key"))
+    }
+
+    /// Key for signing branch metadata, or None unless `auth.sign_metadata` is set.
+    /// The key is generated on first use.
+    pub fn metadata_signing_key(&self) -> Result<Option<Vec<u8>>> {
+        if !self.auth.sign_metadata {
+            return Ok(None);
+        }
+
+        let path = Self::metadata_key_path()?;
+        if let Ok(key) = fs::read(&path) {
+            if !key.is_empty() {
+                return Ok(Some(key));
+            }
+        }
+
+        let mut key = vec![0u8; 32];
+        getrandom::getrandom(&mut key)
+            .map_err(|e| anyhow::anyhow!("Failed to generate metadata key: {}", e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &key)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let perms = std::fs::Permissions::from_mode(0o600);
+            fs::set_permissions(&path, perms)?;
+        }
+
+        Ok(Some(key))
+    }
+
     /// Ensure config exists, creating default if needed
     /// Call this once at startup
     pub fn ensure_exists() -> Result<()> {
@@ -226,12 +432,26 @@ impl Config {
         if path.exists() {
             let content = fs::read_to_string(&path)?;
             let config: Config = toml::from_str(&content)?;
+            config
+                .validate()
+                .with_context(|| format!("Invalid config {}", path.display()))?;
             Ok(config)
         } else {
             Ok(Config::default())
         }
     }
 
+    /// Reject settings that contradict each other
+    fn validate(&self) -> Result<()> {
+        if self.branch.transliterate && self.branch.ascii_fold {
+            bail!(
+                "branch.transliterate and branch.ascii_fold can't both be enabled: \
+                 transliterate romanizes every script, ascii_fold only folds accented Latin letters"
+            );
+        }
+        Ok(())
+    }
+
     /// Save config to file
     pub fn save(&self) -> Result<()> {
         let path = Self::path()?;
@@ -254,6 +474,11 @@ impl Config {
         Self::resolve_github_auth_with_config(&auth_config).map(|(_, token)| token)
     }
 
+    /// Get GitLab token: STAX_GITLAB_TOKEN, then GITLAB_TOKEN
+    pub fn gitlab_token() -> Option<String> {
+        Self::read_env_token("STAX_GITLAB_TOKEN").or_else(|| Self::read_env_token("GITLAB_TOKEN"))
+    }
+
     pub fn github_auth_status() -> GitHubAuthStatus {
         let auth_config = Self::load().map(|c| c.auth).unwrap_or_default();
 
@@ -390,6 +615,11 @@ impl Config {
         None
     }
 
+    /// Whether `branch` is listed in `branch.protected`
+    pub fn is_protected_branch(&self, branch: &str) -> bool {
+        self.branch.protected.iter().any(|b| b == branch)
+    }
+
     /// Format a branch name according to config settings
     pub fn format_branch_name(&self, name: &str) -> String {
         self.format_branch_name_with_prefix_override(name, None)
@@ -401,9 +631,17 @@ impl Config {
         name: &str,
         prefix_override: Option<&str>,
     ) -> String {
-        // Sanitize the message/name first
-        let sanitized_name = self.sanitize_branch_segment(name);
+        self.format_branch_name_with_ticket(name, prefix_override, None)
+    }
 
+    /// Format a branch name, filling `{ticket}` from `ticket` or, failing that,
+    /// from a leading ticket ID in `name` (e.g. "PROJ-123 fix login")
+    pub fn format_branch_name_with_ticket(
+        &self,
+        name: &str,
+        prefix_override: Option<&str>,
+        ticket: Option<&str>,
+    ) -> String {
         // If format template is set, use it (new behavior)
         if let Some(ref format_template) = self.branch.format {
             if !format_template.contains("{message}") {
@@ -412,9 +650,28 @@ impl Config {
                      The branch name input will not appear in the generated name."
                 );
             }
-            return self.apply_format_template(format_template, &sanitized_name, prefix_override);
+
+            // Keep the ticket out of {message} when it has its own placeholder
+            let (ticket, name) = match ticket.map(str::trim).filter(|t| !t.is_empty()) {
+                Some(ticket) => (Some(ticket), name),
+                None if format_template.contains("{ticket}") => split_leading_ticket(name),
+                None => (None, name),
+            };
+            let ticket = ticket
+                .map(|t| self.sanitize_branch_segment(t))
+                .unwrap_or_default();
+            let sanitized_name = self.sanitize_branch_segment(name);
+            return self.apply_format_template(
+                format_template,
+                &sanitized_name,
+                &ticket,
+                prefix_override,
+            );
         }
 
+        // Sanitize the message/name first
+        let sanitized_name = self.sanitize_branch_segment(name);
+
         // Legacy behavior: use prefix/date fields for backward compatibility
         let replacement = &self.branch.replacement;
         let mut result = sanitized_name;
@@ -434,7 +691,8 @@ impl Config {
             }
         } else {
             self.branch.prefix.clone()
-        };
+        }
+        .map(|prefix| self.apply_case(prefix));
 
         if let Some(prefix) = prefix {
             if !result.starts_with(&prefix) {
@@ -450,6 +708,7 @@ impl Config {
         &self,
         template: &str,
         message: &str,
+        ticket: &str,
         prefix_override: Option<&str>,
     ) -> String {
         let mut result = template.to_string();
@@ -457,6 +716,9 @@ impl Config {
         // Replace {message} placeholder
         result = result.replace("{message}", message);
 
+        // Replace {ticket} placeholder (empty when no ticket was resolved)
+        result = result.replace("{ticket}", ticket);
+
         // Replace {date} placeholder if present
         if result.contains("{date}") {
             let date = chrono::Local::now()
@@ -472,7 +734,7 @@ impl Config {
         }
 
         // Clean up empty segments: collapse repeated separators and trim leading/trailing ones
-        // This handles cases where {user} resolves to "" (e.g., "/02-11/msg" -> "02-11/msg")
+        // This handles cases where {user} or {ticket} resolves to "" (e.g., "/02-11/msg" -> "02-11/msg")
         while result.contains("//") {
             result = result.replace("//", "/");
         }
@@ -482,7 +744,7 @@ impl Config {
         if let Some(override_prefix) = prefix_override {
             let trimmed = override_prefix.trim();
             if !trimmed.is_empty() {
-                let normalized = Self::normalize_prefix_override(trimmed);
+                let normalized = self.apply_case(Self::normalize_prefix_override(trimmed));
                 if !result.starts_with(&normalized) {
                     result = format!("{}{}", normalized, result);
                 }
@@ -495,6 +757,16 @@ impl Config {
     /// Sanitize a segment of the branch name (replace special chars, collapse duplicates)
     fn sanitize_branch_segment(&self, segment: &str) -> String {
         let replacement = &self.branch.replacement;
+        let segment = if self.branch.transliterate {
+            deunicode::deunicode(segment)
+        } else {
+            segment.to_string()
+        };
+        let segment = if self.branch.ascii_fold {
+            ascii_fold(&segment, replacement.chars().next().unwrap_or('-'))
+        } else {
+            segment
+        };
 
         let mut result: String = segment
             .chars()
@@ -519,7 +791,16 @@ impl Config {
             .trim_end_matches(replacement_char)
             .to_string();
 
-        result
+        self.apply_case(result)
+    }
+
+    /// Lowercase `value` when `branch.lowercase` is set
+    fn apply_case(&self, value: String) -> String {
+        if self.branch.lowercase {
+            value.to_lowercase()
+        } else {
+            value
+        }
     }
 
     /// Get the username for branch naming
@@ -568,5 +849,49 @@ impl Config {
     }
 }
 
+/// Fold Latin letters with diacritics to ASCII; any other non-ASCII character
+/// (other scripts, emoji) becomes `replacement`
+fn ascii_fold(segment: &str, replacement: char) -> String {
+    let is_latin =
+        |c: char| matches!(c as u32, 0x00C0..=0x024F | 0x1E00..=0x1EFF) && c != '×' && c != '÷';
+
+    let mut result = String::with_capacity(segment.len());
+    for c in segment.chars() {
+        if c.is_ascii() {
+            result.push(c);
+        } else if let Some(folded) = deunicode::deunicode_char(c).filter(|_| is_latin(c)) {
+            result.push_str(folded);
+        } else {
+            result.push(replacement);
+        }
+    }
+    result
+}
+
+/// Split a leading ticket ID like "PROJ-123" (uppercase project key, dash, number)
+/// off `name`, returning the ticket and the rest of the input
+fn split_leading_ticket(name: &str) -> (Option<&str>, &str) {
+    let name = name.trim_start();
+    let end = name
+        .find(|c: char| c.is_whitespace() || c == ':' || c == '/')
+        .unwrap_or(name.len());
+    let token = &name[..end];
+
+    let is_ticket = token.split_once('-').is_some_and(|(key, number)| {
+        key.starts_with(|c: char| c.is_ascii_uppercase())
+            && key
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+    });
+    if !is_ticket {
+        return (None, name);
+    }
+
+    let rest = name[end..].trim_start_matches(|c: char| c.is_whitespace() || c == ':' || c == '/');
+    (Some(token), rest)
+}
+
 #[cfg(test)]
 mod tests;
@@ -9,6 +9,47 @@ fn env_lock() -> std::sync::MutexGuard<'static, ()> {
     LOCK.get_or_init(|| Mutex::new(())).lock().unwrap()
 }
 
+#[test]
+fn test_config_dir_respects_xdg_config_home() {
+    let _guard = env_lock();
+    let orig_home = env::var_os("HOME");
+    let orig_xdg = env::var_os("XDG_CONFIG_HOME");
+
+    let temp_dir = std::env::temp_dir().join(format!("stax-test-xdg-{}", std::process::id()));
+    let home = temp_dir.join("home");
+    let xdg = temp_dir.join("xdg");
+    env::set_var("HOME", &home);
+
+    env::set_var("XDG_CONFIG_HOME", &xdg);
+    assert_eq!(Config::dir().unwrap(), xdg.join("stax"));
+    assert_eq!(
+        Config::path().unwrap(),
+        xdg.join("stax").join("config.toml")
+    );
+    assert_eq!(
+        Config::credentials_path().unwrap(),
+        xdg.join("stax").join(".credentials")
+    );
+
+    // Empty and relative values are ignored
+    env::set_var("XDG_CONFIG_HOME", "");
+    assert_eq!(Config::dir().unwrap(), home.join(".config").join("stax"));
+    env::set_var("XDG_CONFIG_HOME", "relative/config");
+    assert_eq!(Config::dir().unwrap(), home.join(".config").join("stax"));
+
+    env::remove_var("XDG_CONFIG_HOME");
+    assert_eq!(Config::dir().unwrap(), home.join(".config").join("stax"));
+
+    match orig_home {
+        Some(v) => env::set_var("HOME", v),
+        None => env::remove_var("HOME"),
+    }
+    match orig_xdg {
+        Some(v) => env::set_var("XDG_CONFIG_HOME", v),
+        None => env::remove_var("XDG_CONFIG_HOME"),
+    }
+}
+
 fn write_auth_config(
     home: &Path,
     use_gh_cli: bool,
@@ -59,6 +100,7 @@ fn test_default_config() {
     assert!(config.auth.use_gh_cli);
     assert!(!config.auth.allow_github_token_env);
     assert!(config.auth.gh_hostname.is_none());
+    assert!(!config.auth.sign_metadata);
 }
 
 #[test]
@@ -141,6 +183,85 @@ fn test_format_branch_name_consecutive_replacements_collapsed() {
     assert_eq!(config.format_branch_name("my   feature"), "my-feature");
 }
 
+#[test]
+fn test_format_branch_name_keeps_non_ascii_by_default() {
+    let config = Config::default();
+    assert_eq!(config.format_branch_name("Café déjà"), "Café-déjà");
+}
+
+#[test]
+fn test_format_branch_name_transliterates_accents() {
+    let mut config = Config::default();
+    config.branch.transliterate = true;
+    assert_eq!(config.format_branch_name("Café déjà"), "Cafe-deja");
+    assert_eq!(config.format_branch_name("Crème brûlée!"), "Creme-brulee");
+}
+
+#[test]
+fn test_format_branch_name_preserves_case_by_default() {
+    let config = Config::default();
+    assert_eq!(config.format_branch_name("Fix Login"), "Fix-Login");
+}
+
+#[test]
+fn test_format_branch_name_lowercase() {
+    let mut config = Config::default();
+    config.branch.lowercase = true;
+    assert_eq!(config.format_branch_name("Fix Login"), "fix-login");
+
+    config.branch.prefix = Some("Cesar/".to_string());
+    assert_eq!(config.format_branch_name("Fix Login"), "cesar/fix-login");
+    assert_eq!(
+        config.format_branch_name_with_prefix_override("Fix Login", Some("Hotfix")),
+        "hotfix/fix-login"
+    );
+}
+
+#[test]
+fn test_format_template_lowercase_user_and_message() {
+    let mut config = Config::default();
+    config.branch.format = Some("{user}/{message}".to_string());
+    config.branch.user = Some("Alice".to_string());
+    config.branch.lowercase = true;
+    assert_eq!(config.format_branch_name("Fix Login"), "alice/fix-login");
+}
+
+#[test]
+fn test_format_branch_name_ascii_fold_accents() {
+    let mut config = Config::default();
+    config.branch.ascii_fold = true;
+    assert_eq!(config.format_branch_name("café déjà vu"), "cafe-deja-vu");
+    assert_eq!(config.format_branch_name("Straße öffnen"), "Strasse-offnen");
+}
+
+#[test]
+fn test_format_branch_name_ascii_fold_replaces_non_latin() {
+    let mut config = Config::default();
+    config.branch.ascii_fold = true;
+    assert_eq!(config.format_branch_name("fix 🐛 bug"), "fix-bug");
+    assert_eq!(config.format_branch_name("日本語 fix"), "fix");
+    assert_eq!(config.format_branch_name("Привет fix"), "fix");
+}
+
+#[test]
+fn test_transliterate_and_ascii_fold_are_exclusive() {
+    let mut config = Config::default();
+    config.branch.transliterate = true;
+    assert!(config.validate().is_ok());
+
+    config.branch.ascii_fold = true;
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("branch.transliterate and branch.ascii_fold"));
+}
+
+#[test]
+fn test_format_branch_name_transliterates_cjk() {
+    let mut config = Config::default();
+    config.branch.transliterate = true;
+    assert_eq!(config.format_branch_name("你好世界"), "Ni-Hao-Shi-Jie");
+    assert_eq!(config.format_branch_name("日本語 fix"), "Ri-Ben-Yu-fix");
+}
+
 #[test]
 fn test_token_priority_stax_env_first() {
     let _guard = env_lock();
@@ -300,6 +421,44 @@ fn test_empty_stax_token_falls_back_to_credentials() {
 fn test_default_ui_config() {
     let ui_config = UiConfig::default();
     assert!(ui_config.tips);
+    assert!(!ui_config.compact);
+    assert!(ui_config.update_check);
+}
+
+#[test]
+fn test_hooks_config_defaults_and_parse() {
+    let config = Config::default();
+    assert!(config.hooks.pre_submit.is_none());
+    assert!(!config.hooks.pre_submit_per_branch);
+    assert!(config.hooks.pre_submit_abort_all);
+
+    let parsed: Config = toml::from_str(
+        r#"
+[hooks]
+pre_submit = "make check"
+pre_submit_per_branch = true
+pre_submit_abort_all = false
+"#,
+    )
+    .unwrap();
+    assert_eq!(parsed.hooks.pre_submit.as_deref(), Some("make check"));
+    assert!(parsed.hooks.pre_submit_per_branch);
+    assert!(!parsed.hooks.pre_submit_abort_all);
+}
+
+#[test]
+fn test_ui_compact_deserialization() {
+    let parsed: Config = toml::from_str("[ui]\ncompact = true\n").unwrap();
+    assert!(parsed.ui.compact);
+    assert!(parsed.ui.tips);
+}
+
+#[test]
+fn test_ui_update_check_deserialization() {
+    let parsed: Config = toml::from_str("[ui]\ntips = true\n").unwrap();
+    assert!(parsed.ui.update_check);
+    let parsed: Config = toml::from_str("[ui]\nupdate_check = false\n").unwrap();
+    assert!(!parsed.ui.update_check);
 }
 
 #[test]
@@ -732,6 +891,54 @@ fn test_format_template_user_message() {
     assert_eq!(config.format_branch_name("my-feature"), "alice/my-feature");
 }
 
+#[test]
+fn test_format_template_ticket_from_flag() {
+    let mut config = Config::default();
+    config.branch.format = Some("{user}/{ticket}/{message}".to_string());
+    config.branch.user = Some("alice".to_string());
+    assert_eq!(
+        config.format_branch_name_with_ticket("fix login", None, Some("PROJ-123")),
+        "alice/PROJ-123/fix-login"
+    );
+}
+
+#[test]
+fn test_format_template_ticket_from_message() {
+    let mut config = Config::default();
+    config.branch.format = Some("{user}/{ticket}/{message}".to_string());
+    config.branch.user = Some("alice".to_string());
+    assert_eq!(
+        config.format_branch_name("PROJ-123 fix login"),
+        "alice/PROJ-123/fix-login"
+    );
+    assert_eq!(
+        config.format_branch_name("PROJ-123: fix login"),
+        "alice/PROJ-123/fix-login"
+    );
+}
+
+#[test]
+fn test_format_template_missing_ticket_collapses() {
+    let mut config = Config::default();
+    config.branch.format = Some("{user}/{ticket}/{message}".to_string());
+    config.branch.user = Some("alice".to_string());
+    assert_eq!(config.format_branch_name("fix login"), "alice/fix-login");
+
+    config.branch.user = Some(String::new());
+    assert_eq!(config.format_branch_name("fix login"), "fix-login");
+}
+
+#[test]
+fn test_format_template_without_ticket_keeps_message_intact() {
+    let mut config = Config::default();
+    config.branch.format = Some("{user}/{message}".to_string());
+    config.branch.user = Some("alice".to_string());
+    assert_eq!(
+        config.format_branch_name("PROJ-123 fix login"),
+        "alice/PROJ-123-fix-login"
+    );
+}
+
 #[test]
 fn test_format_template_user_date_message() {
     let mut config = Config::default();
@@ -1,7 +1,12 @@
 use crate::git::refs;
 use anyhow::Result;
+use colored::Colorize;
 use git2::Repository;
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
 
 /// Metadata stored for each tracked branch
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +19,16 @@ pub struct BranchMetadata {
     /// PR information (if submitted)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pr_info: Option<PrInfo>,
+    /// Commit SHA of the branch when it was last pushed by submit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_submitted_revision: Option<String>,
+    /// Commit the branch forked from its parent, used as the base for diffs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_point: Option<String>,
+    /// HMAC-SHA256 over the other fields, written when `auth.sign_metadata` is on.
+    /// Stripped on read and recomputed on write.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -34,6 +49,19 @@ impl BranchMetadata {
             parent_branch_name: parent_name.to_string(),
             parent_branch_revision: parent_revision.to_string(),
             pr_info: None,
+            last_submitted_revision: None,
+            fork_point: None,
+            signature: None,
+        }
+    }
+
+    /// Metadata after rebasing onto `parent_tip`: the recorded parent revision and the
+    /// fork point used as the diff base both move there
+    pub fn restacked_onto(self, parent_tip: String) -> Self {
+        Self {
+            fork_point: Some(parent_tip.clone()),
+            parent_branch_revision: parent_tip,
+            ..self
         }
     }
 
@@ -41,7 +69,15 @@ impl BranchMetadata {
     pub fn read(repo: &Repository, branch: &str) -> Result<Option<Self>> {
         match refs::read_metadata(repo, branch)? {
             Some(json) => {
-                let meta: Self = serde_json::from_str(&json)?;
+                let mut meta: Self = serde_json::from_str(&json)?;
+                let signature = meta.signature.take();
+                if let Some(key) = signing_key() {
+                    if let Some(problem) =
+                        meta.signature_problem(key, branch, signature.as_deref())?
+                    {
+                        warn_untrusted(branch, problem);
+                    }
+                }
                 Ok(Some(meta))
             }
             None => Ok(None),
@@ -50,10 +86,43 @@ impl BranchMetadata {
 
     /// Write metadata for a branch to git refs
     pub fn write(&self, repo: &Repository, branch: &str) -> Result<()> {
-        let json = serde_json::to_string(self)?;
+        let mut meta = self.clone();
+        meta.signature = None;
+        if let Some(key) = signing_key() {
+            meta.signature = Some(sign(key, &meta.signing_payload(branch)?));
+        }
+        let json = serde_json::to_string(&meta)?;
         refs::write_metadata(repo, branch, &json)
     }
 
+    /// What the signature covers: the branch name, so a signed blob can't be copied onto
+    /// another branch's ref, and the canonical JSON of everything but the signature
+    fn signing_payload(&self, branch: &str) -> Result<String> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        Ok(format!("{}\n{}", branch, serde_json::to_string(&unsigned)?))
+    }
+
+    /// Why metadata read from `branch`'s ref can't be trusted under `key`, if at all.
+    /// Unsigned metadata (e.g. written before signing was turned on) is flagged too;
+    /// the next write signs it.
+    fn signature_problem(
+        &self,
+        key: &[u8],
+        branch: &str,
+        signature: Option<&str>,
+    ) -> Result<Option<&'static str>> {
+        Ok(match signature {
+            None => Some("unsigned"),
+            Some(signature) if !verify(key, &self.signing_payload(branch)?, signature) => {
+                Some("signature mismatch")
+            }
+            Some(_) => None,
+        })
+    }
+
     /// Delete metadata for a branch
     pub fn delete(repo: &Repository, branch: &str) -> Result<()> {
         refs::delete_metadata(repo, branch)
@@ -61,10 +130,121 @@ impl BranchMetadata {
 
     /// Check if the branch needs restacking (parent has moved)
     pub fn needs_restack(&self, repo: &Repository) -> Result<bool> {
-        let parent_ref = repo.find_branch(&self.parent_branch_name, git2::BranchType::Local)?;
+        // Parents may be remote-tracking branches (e.g. `origin/integration`)
+        let parent_ref = repo
+            .find_branch(&self.parent_branch_name, git2::BranchType::Local)
+            .or_else(|_| repo.find_branch(&self.parent_branch_name, git2::BranchType::Remote))?;
         let current_parent_rev = parent_ref.get().peel_to_commit()?.id().to_string();
+        // An unusable recorded revision can't prove the branch is up to date
+        if !self.parent_revision_valid(repo) {
+            return Ok(true);
+        }
         Ok(current_parent_rev != self.parent_branch_revision)
     }
+
+    /// Whether the recorded parent revision is a full SHA naming a commit in `repo`
+    pub fn parent_revision_valid(&self, repo: &Repository) -> bool {
+        let rev = &self.parent_branch_revision;
+        matches!(rev.len(), 40 | 64)
+            && rev.chars().all(|c| c.is_ascii_hexdigit())
+            && git2::Oid::from_str(rev)
+                .and_then(|oid| repo.find_commit(oid))
+                .is_ok()
+    }
+
+    /// Why this metadata can't be used as-is, if anything; `stax stack fix` repairs it
+    pub fn repair_reason(&self, repo: &Repository) -> Option<RepairReason> {
+        let parent_exists = repo
+            .find_branch(&self.parent_branch_name, git2::BranchType::Local)
+            .or_else(|_| repo.find_branch(&self.parent_branch_name, git2::BranchType::Remote))
+            .is_ok();
+        if !parent_exists {
+            Some(RepairReason::ParentMissing)
+        } else if !self.parent_revision_valid(repo) {
+            Some(RepairReason::InvalidParentRevision)
+        } else {
+            None
+        }
+    }
+}
+
+/// A problem with stored metadata that needs repair before it can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairReason {
+    /// The recorded parent branch no longer exists
+    ParentMissing,
+    /// The recorded parent revision is empty, malformed, or not in the repository
+    InvalidParentRevision,
+}
+
+impl std::fmt::Display for RepairReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepairReason::ParentMissing => write!(f, "parent branch is missing"),
+            RepairReason::InvalidParentRevision => write!(f, "parent revision is invalid"),
+        }
+    }
+}
+
+static SIGNING_KEY: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// Sign metadata written (and verify metadata read) by this process with `key`.
+/// Callers load it from config (`auth.sign_metadata`); the first key set wins.
+pub fn set_signing_key(key: Vec<u8>) {
+    let _ = SIGNING_KEY.set(key);
+}
+
+fn signing_key() -> Option<&'static [u8]> {
+    SIGNING_KEY.get().map(Vec::as_slice)
+}
+
+fn mac(key: &[u8], payload: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+fn sign(key: &[u8], payload: &str) -> String {
+    mac(key, payload)
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn verify(key: &[u8], payload: &str, signature: &str) -> bool {
+    let Some(bytes) = decode_hex(signature) else {
+        return false;
+    };
+    mac(key, payload).verify_slice(&bytes).is_ok()
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Warn once per branch per process
+fn warn_untrusted(branch: &str, problem: &str) {
+    static WARNED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if warned
+        .get_or_insert_with(HashSet::new)
+        .insert(branch.to_string())
+    {
+        eprintln!(
+            "{} metadata for '{}' may have been modified outside stax ({})",
+            "warning:".yellow().bold(),
+            branch,
+            problem
+        );
+    }
 }
 
 #[cfg(test)]
@@ -113,6 +293,33 @@ mod tests {
         assert_eq!(pr.state, "OPEN");
     }
 
+    #[test]
+    fn test_metadata_last_submitted_revision_roundtrip() {
+        let mut meta = BranchMetadata::new("main", "abc123");
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(!json.contains("lastSubmittedRevision"));
+
+        meta.last_submitted_revision = Some("def456".to_string());
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(json.contains("lastSubmittedRevision"));
+        let parsed: BranchMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.last_submitted_revision.as_deref(), Some("def456"));
+    }
+
+    #[test]
+    fn test_metadata_fork_point_defaults_to_none() {
+        let json = r#"{"parentBranchName":"main","parentBranchRevision":"abc123"}"#;
+        let meta: BranchMetadata = serde_json::from_str(json).unwrap();
+        assert!(meta.fork_point.is_none());
+
+        let mut meta = BranchMetadata::new("main", "abc123");
+        assert!(!serde_json::to_string(&meta).unwrap().contains("forkPoint"));
+        meta.fork_point = Some("abc123".to_string());
+        let json = serde_json::to_string(&meta).unwrap();
+        let parsed: BranchMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.fork_point.as_deref(), Some("abc123"));
+    }
+
     #[test]
     fn test_freephite_compatibility() {
         // This JSON format matches freephite's metadata format
@@ -129,4 +336,49 @@ mod tests {
         assert_eq!(meta.parent_branch_name, "main");
         assert_eq!(meta.parent_branch_revision, "deadbeef1234567890");
     }
+
+    #[test]
+    fn test_signature_roundtrip() {
+        let key = b"test-key";
+        let meta = BranchMetadata::new("main", "abc123");
+        let payload = meta.signing_payload("feature").unwrap();
+        let signature = sign(key, &payload);
+
+        assert_eq!(signature.len(), 64);
+        assert!(verify(key, &payload, &signature));
+        assert!(!verify(b"other-key", &payload, &signature));
+        assert!(!verify(key, &payload, "not-hex"));
+
+        let edited = BranchMetadata::new("feature", "abc123");
+        assert!(!verify(
+            key,
+            &edited.signing_payload("feature").unwrap(),
+            &signature
+        ));
+    }
+
+    #[test]
+    fn test_signing_payload_ignores_signature() {
+        let mut meta = BranchMetadata::new("main", "abc123");
+        let before = meta.signing_payload("feature").unwrap();
+        meta.signature = Some("deadbeef".to_string());
+        assert_eq!(meta.signing_payload("feature").unwrap(), before);
+        assert!(!before.contains("signature"));
+    }
+
+    #[test]
+    fn test_signature_problem_flags_missing_and_moved_signatures() {
+        let key = b"test-key";
+        let meta = BranchMetadata::new("main", "abc123");
+        let signature = sign(key, &meta.signing_payload("feature-a").unwrap());
+
+        let problem = |branch, signature| meta.signature_problem(key, branch, signature).unwrap();
+        assert_eq!(problem("feature-a", Some(signature.as_str())), None);
+        assert_eq!(problem("feature-a", None), Some("unsigned"));
+        // A valid blob copied onto another branch's ref no longer verifies
+        assert_eq!(
+            problem("feature-b", Some(signature.as_str())),
+            Some("signature mismatch")
+        );
+    }
 }
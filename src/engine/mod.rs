@@ -1,5 +1,5 @@
 pub mod metadata;
 pub mod stack;
 
-pub use metadata::{BranchMetadata, PrInfo};
-pub use stack::Stack;
+pub use metadata::{BranchMetadata, PrInfo, RepairReason};
+pub use stack::{BranchSort, Stack};
@@ -1,4 +1,5 @@
 use crate::engine::BranchMetadata;
+use crate::error::StaxError;
 use crate::git::{refs, GitRepo};
 use anyhow::Result;
 use git2::BranchType;
@@ -16,6 +17,31 @@ pub struct StackBranch {
     pub pr_is_draft: Option<bool>,
 }
 
+/// Order of sibling branches, and the stacks under them, in tree listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BranchSort {
+    /// The stack holding the current branch first, then by name
+    #[default]
+    Stack,
+    /// Alphabetical
+    Name,
+    /// Most recently committed-to stack first, by tip commit time
+    Recent,
+}
+
+impl std::str::FromStr for BranchSort {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "stack" => Ok(BranchSort::Stack),
+            "name" => Ok(BranchSort::Name),
+            "recent" => Ok(BranchSort::Recent),
+            _ => anyhow::bail!("Invalid sort: {}. Use: stack, name, or recent", s),
+        }
+    }
+}
+
 /// The full stack structure
 pub struct Stack {
     pub branches: HashMap<String, StackBranch>,
@@ -104,6 +130,21 @@ impl Stack {
         Ok(Self { branches, trunk })
     }
 
+    /// Look up a tracked branch, failing with [`StaxError::NotTracked`]
+    pub fn require(&self, branch: &str) -> Result<&StackBranch> {
+        self.branches
+            .get(branch)
+            .ok_or_else(|| StaxError::NotTracked(branch.to_string()).into())
+    }
+
+    /// The tracked parent of `branch`, failing with [`StaxError::NoParent`]
+    pub fn require_parent(&self, branch: &str) -> Result<String> {
+        self.branches
+            .get(branch)
+            .and_then(|b| b.parent.clone())
+            .ok_or_else(|| StaxError::NoParent(branch.to_string()).into())
+    }
+
     /// Get the ancestors of a branch (up to trunk)
     pub fn ancestors(&self, branch: &str) -> Vec<String> {
         let mut result = Vec::new();
@@ -139,6 +180,10 @@ impl Stack {
     }
 
     /// Get the current stack (ancestors + current + descendants)
+    pub fn has_tracked_branches(&self) -> bool {
+        self.branches.keys().any(|b| b != &self.trunk)
+    }
+
     pub fn current_stack(&self, branch: &str) -> Vec<String> {
         let mut ancestors = self.ancestors(branch);
         ancestors.reverse();
@@ -147,6 +192,37 @@ impl Stack {
         ancestors
     }
 
+    /// The stack containing `branch`, bottom-first and excluding trunk.
+    /// Errors if any branch in it has more than one child.
+    pub fn linear_stack(&self, branch: &str) -> Result<Vec<String>> {
+        // The bottom is the last ancestor before trunk (or the branch itself)
+        let bottom = self
+            .ancestors(branch)
+            .into_iter()
+            .rfind(|a| a != &self.trunk)
+            .unwrap_or_else(|| branch.to_string());
+
+        let mut chain = vec![bottom];
+        loop {
+            let last = chain.last().unwrap();
+            let children = match self.branches.get(last) {
+                Some(b) => &b.children,
+                None => break,
+            };
+            match children.len() {
+                0 => break,
+                1 => chain.push(children[0].clone()),
+                _ => anyhow::bail!(
+                    "'{}' has multiple children ({}); this only works on linear stacks.",
+                    last,
+                    children.join(", ")
+                ),
+            }
+        }
+
+        Ok(chain)
+    }
+
     /// Get branches that need restacking
     pub fn needs_restack(&self) -> Vec<String> {
         self.branches
@@ -156,6 +232,44 @@ impl Stack {
             .collect()
     }
 
+    /// Reorder every branch's children for display. `tip_time` gives a branch's
+    /// tip commit time and is only consulted for [`BranchSort::Recent`].
+    pub fn sort_children(
+        &mut self,
+        sort: BranchSort,
+        current: &str,
+        tip_time: impl Fn(&str) -> i64,
+    ) {
+        // Sort keys cover each branch's whole subtree, so compute them up front
+        let keys: HashMap<String, (bool, i64)> = self
+            .branches
+            .keys()
+            .map(|name| {
+                let mut subtree = self.descendants(name);
+                subtree.push(name.clone());
+                let holds_current = subtree.iter().any(|b| b == current);
+                let newest = match sort {
+                    BranchSort::Recent => subtree.iter().map(|b| tip_time(b)).max().unwrap_or(0),
+                    _ => 0,
+                };
+                (name.clone(), (holds_current, newest))
+            })
+            .collect();
+
+        for branch in self.branches.values_mut() {
+            branch.children.sort_by(|a, b| {
+                let (a_current, a_time) = keys.get(a).copied().unwrap_or_default();
+                let (b_current, b_time) = keys.get(b).copied().unwrap_or_default();
+                match sort {
+                    BranchSort::Stack => b_current.cmp(&a_current),
+                    BranchSort::Name => std::cmp::Ordering::Equal,
+                    BranchSort::Recent => b_time.cmp(&a_time),
+                }
+                .then_with(|| a.cmp(b))
+            });
+        }
+    }
+
     /// Get siblings of a branch (other branches with the same parent)
     #[allow(dead_code)] // Useful utility for future features
     pub fn get_siblings(&self, branch: &str) -> Vec<String> {
@@ -275,6 +389,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sort_children_stack_puts_current_stack_first() {
+        let mut stack = create_test_stack();
+        stack.sort_children(BranchSort::Stack, "feature-b", |_| 0);
+        assert_eq!(
+            stack.branches["main"].children,
+            vec!["feature-b", "feature-a"]
+        );
+
+        stack.sort_children(BranchSort::Name, "feature-b", |_| 0);
+        assert_eq!(
+            stack.branches["main"].children,
+            vec!["feature-a", "feature-b"]
+        );
+    }
+
+    #[test]
+    fn test_sort_children_recent_uses_newest_tip_in_subtree() {
+        let mut stack = create_test_stack();
+        let times: HashMap<&str, i64> = [("feature-a", 1), ("feature-a-2", 30), ("feature-b", 20)]
+            .into_iter()
+            .collect();
+        stack.sort_children(BranchSort::Recent, "main", |b| {
+            times.get(b).copied().unwrap_or(0)
+        });
+        assert_eq!(
+            stack.branches["main"].children,
+            vec!["feature-a", "feature-b"]
+        );
+    }
+
+    #[test]
+    fn test_branch_sort_parses() {
+        assert_eq!("recent".parse::<BranchSort>().unwrap(), BranchSort::Recent);
+        assert_eq!("Name".parse::<BranchSort>().unwrap(), BranchSort::Name);
+        assert!("size".parse::<BranchSort>().is_err());
+    }
+
     #[test]
     fn test_ancestors_from_leaf() {
         let stack = create_test_stack();
@@ -401,6 +553,28 @@ mod tests {
         assert_eq!(siblings, vec!["main"]);
     }
 
+    #[test]
+    fn test_linear_stack_from_middle() {
+        let stack = create_test_stack();
+        assert_eq!(
+            stack.linear_stack("feature-a-1").unwrap(),
+            vec!["feature-a", "feature-a-1", "feature-a-2"]
+        );
+        assert_eq!(stack.linear_stack("feature-b").unwrap(), vec!["feature-b"]);
+    }
+
+    #[test]
+    fn test_linear_stack_rejects_fork() {
+        let mut stack = create_test_stack();
+        stack
+            .branches
+            .get_mut("feature-a")
+            .unwrap()
+            .children
+            .push("feature-c".to_string());
+        assert!(stack.linear_stack("feature-a-2").is_err());
+    }
+
     #[test]
     fn test_get_siblings_nonexistent() {
         let stack = create_test_stack();
@@ -0,0 +1,76 @@
+//! Typed errors for failures callers may want to react to.
+//!
+//! These travel inside `anyhow::Error` like any other error, so the CLI prints
+//! their message unchanged while library users can tell them apart with
+//! `err.downcast_ref::<StaxError>()`. Incidental failures (I/O, git plumbing)
+//! stay plain anyhow errors.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaxError {
+    /// The branch has no stax metadata
+    NotTracked(String),
+    /// The branch has no tracked parent (it is trunk or was never tracked)
+    NoParent(String),
+    /// A rebase of the branch stopped on conflicts and is waiting in the worktree
+    RebaseConflict(String),
+    /// The branch has no PR recorded yet
+    NoPr(String),
+    /// No GitHub token could be found in any supported source
+    AuthMissing,
+}
+
+impl fmt::Display for StaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StaxError::NotTracked(branch) => write!(
+                f,
+                "Branch '{}' is not tracked. Run `stax branch track` first.",
+                branch
+            ),
+            StaxError::NoParent(branch) => {
+                write!(f, "Branch '{}' has no tracked parent.", branch)
+            }
+            StaxError::RebaseConflict(branch) => write!(
+                f,
+                "Rebase of '{}' stopped on conflicts. Resolve them and run `stax continue`.",
+                branch
+            ),
+            StaxError::NoPr(branch) => write!(
+                f,
+                "No PR found for branch '{}'. Run `stax submit` to create one.",
+                branch
+            ),
+            StaxError::AuthMissing => write!(
+                f,
+                "GitHub auth not configured. Use one of: `stax auth`, `stax auth --from-gh`, \
+                 `gh auth login`, or set `STAX_GITHUB_TOKEN`."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StaxError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stax_error_survives_anyhow_context() {
+        let err = anyhow::Error::from(StaxError::NoPr("feature".into())).context("Opening PR");
+
+        assert_eq!(
+            err.downcast_ref::<StaxError>(),
+            Some(&StaxError::NoPr("feature".into()))
+        );
+    }
+
+    #[test]
+    fn test_stax_error_messages_name_the_branch() {
+        let message = StaxError::NotTracked("feature".into()).to_string();
+        assert!(message.contains("'feature'"));
+        assert!(message.contains("stax branch track"));
+    }
+}
@@ -1,4 +1,4 @@
 pub mod refs;
 pub mod repo;
 
-pub use repo::{GitRepo, RebaseResult};
+pub use repo::{GitRepo, RebaseOptions, RebaseResult};
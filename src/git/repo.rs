@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use git2::{BranchType, Repository};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 
@@ -23,7 +24,12 @@ struct BranchParentMetadata {
 impl GitRepo {
     /// Open the repository at the current directory or any parent
     pub fn open() -> Result<Self> {
-        let repo = Repository::discover(".").context("Not in a git repository")?;
+        Self::open_at(Path::new("."))
+    }
+
+    /// Open the repository containing `path` or any of its parents
+    pub fn open_at(path: &Path) -> Result<Self> {
+        let repo = Repository::discover(path).context("Not in a git repository")?;
         Ok(Self { repo })
     }
 
@@ -180,6 +186,17 @@ impl GitRepo {
         Ok(worktrees)
     }
 
+    /// Map of branch name to the path of every other worktree it is checked out in
+    pub fn branches_in_other_worktrees(&self) -> Result<HashMap<String, PathBuf>> {
+        let current_workdir = Self::normalize_path(self.workdir()?);
+        Ok(self
+            .list_worktrees()?
+            .into_iter()
+            .filter(|worktree| worktree.path != current_workdir)
+            .filter_map(|worktree| Some((worktree.branch?, worktree.path)))
+            .collect())
+    }
+
     pub fn branch_worktree_path(&self, branch: &str) -> Result<Option<PathBuf>> {
         for worktree in self.list_worktrees()? {
             if worktree.branch.as_deref() == Some(branch) {
@@ -231,6 +248,26 @@ impl GitRepo {
         Ok(commit.id().to_string())
     }
 
+    /// Get the commit SHA for a tracked parent, which may be a local branch or a
+    /// remote-tracking branch such as `origin/integration`
+    pub fn parent_commit(&self, parent: &str) -> Result<String> {
+        if let Ok(oid) = self.branch_commit(parent) {
+            return Ok(oid);
+        }
+        let reference = self
+            .repo
+            .find_branch(parent, BranchType::Remote)
+            .with_context(|| format!("Branch '{}' not found", parent))?;
+        let commit = reference.get().peel_to_commit()?;
+        Ok(commit.id().to_string())
+    }
+
+    /// Whether `name` is a remote-tracking branch with no local branch of that name
+    pub fn is_remote_only_branch(&self, name: &str) -> bool {
+        self.repo.find_branch(name, BranchType::Local).is_err()
+            && self.repo.find_branch(name, BranchType::Remote).is_ok()
+    }
+
     /// Get commits ahead/behind between two branches (uses libgit2, no subprocess)
     pub fn commits_ahead_behind(&self, base: &str, head: &str) -> Result<(usize, usize)> {
         let base_oid = self.resolve_to_oid(base)?;
@@ -295,7 +332,12 @@ impl GitRepo {
 
     /// Get the trunk branch name (from stored setting or auto-detect main/master)
     pub fn trunk_branch(&self) -> Result<String> {
-        // First check if trunk is stored
+        // An explicit `remote.trunk` wins over whatever init stored
+        let config = crate::config::Config::load().unwrap_or_default();
+        if let Some(trunk) = configured_trunk(&config) {
+            return Ok(trunk);
+        }
+        // Then check if trunk is stored
         if let Some(trunk) = super::refs::read_trunk(&self.repo)? {
             return Ok(trunk);
         }
@@ -303,14 +345,41 @@ impl GitRepo {
         self.detect_trunk()
     }
 
-    /// Auto-detect trunk branch (main or master)
+    /// Auto-detect trunk: `remote.trunk`, then the branch `<remote>/HEAD` points at,
+    /// then `main` or `master`
     pub fn detect_trunk(&self) -> Result<String> {
+        let config = crate::config::Config::load().unwrap_or_default();
+        if let Some(trunk) = configured_trunk(&config) {
+            return Ok(trunk);
+        }
+
+        if let Some(name) = self.remote_default_branch(config.remote_name()) {
+            if self.repo.find_branch(&name, BranchType::Local).is_ok() {
+                return Ok(name);
+            }
+        }
+
         for name in ["main", "master"] {
             if self.repo.find_branch(name, BranchType::Local).is_ok() {
                 return Ok(name.to_string());
             }
         }
-        anyhow::bail!("No trunk branch (main/master) found")
+        anyhow::bail!(
+            "No trunk branch found. Set remote.trunk in the stax config, or create main/master."
+        )
+    }
+
+    /// The branch `refs/remotes/<remote>/HEAD` points at (set by clone or `git remote set-head`)
+    fn remote_default_branch(&self, remote: &str) -> Option<String> {
+        let head = self
+            .repo
+            .find_reference(&format!("refs/remotes/{}/HEAD", remote))
+            .ok()?;
+        let target = head.symbolic_target()?;
+        target
+            .strip_prefix(&format!("refs/remotes/{}/", remote))
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
     }
 
     /// Check if stax has been initialized in this repo
@@ -349,7 +418,20 @@ impl GitRepo {
     }
 
     fn rebase_in_path(&self, cwd: &Path, onto: &str) -> Result<RebaseResult> {
-        let output = self.run_git(cwd, &["rebase", onto])?;
+        self.rebase_in_path_from(cwd, onto, None, &RebaseOptions::default())
+    }
+
+    /// Run `git rebase <onto>`, or `git rebase --onto <onto> <upstream>` when an upstream is given
+    fn rebase_in_path_from(
+        &self,
+        cwd: &Path,
+        onto: &str,
+        upstream: Option<&str>,
+        options: &RebaseOptions,
+    ) -> Result<RebaseResult> {
+        let args = rebase_args(onto, upstream, options);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = self.run_git(cwd, &args)?;
         if output.status.success() {
             return Ok(RebaseResult::Success);
         }
@@ -380,6 +462,30 @@ impl GitRepo {
         branch: &str,
         onto: &str,
         auto_stash_pop: bool,
+    ) -> Result<RebaseResult> {
+        self.rebase_branch_onto_with(branch, onto, auto_stash_pop, &RebaseOptions::default())
+    }
+
+    /// Like `rebase_branch_onto`, with extra options passed through to `git rebase`
+    pub fn rebase_branch_onto_with(
+        &self,
+        branch: &str,
+        onto: &str,
+        auto_stash_pop: bool,
+        options: &RebaseOptions,
+    ) -> Result<RebaseResult> {
+        self.rebase_branch_onto_from(branch, onto, None, auto_stash_pop, options)
+    }
+
+    /// Like `rebase_branch_onto`, but only replays commits after `upstream`
+    /// (`git rebase --onto <onto> <upstream>`). Used when a branch changes parents.
+    pub fn rebase_branch_onto_from(
+        &self,
+        branch: &str,
+        onto: &str,
+        upstream: Option<&str>,
+        auto_stash_pop: bool,
+        options: &RebaseOptions,
     ) -> Result<RebaseResult> {
         let current_workdir = Self::normalize_path(self.workdir()?);
         let target_workdir = self
@@ -418,14 +524,16 @@ Use --auto-stash-pop or stash/commit changes first.",
             stashed = self.stash_push_at(&target_workdir)?;
         }
 
-        let result = match self.rebase_in_path(&target_workdir, onto).with_context(|| {
-            format!(
-                "Failed to rebase '{}' onto '{}' in '{}'",
-                branch,
-                onto,
-                target_workdir.display()
-            )
-        }) {
+        let result = match self
+            .rebase_in_path_from(&target_workdir, onto, upstream, options)
+            .with_context(|| {
+                format!(
+                    "Failed to rebase '{}' onto '{}' in '{}'",
+                    branch,
+                    onto,
+                    target_workdir.display()
+                )
+            }) {
             Ok(result) => result,
             Err(err) => {
                 if stashed {
@@ -451,6 +559,103 @@ Use --auto-stash-pop or stash/commit changes first.",
         Ok(result)
     }
 
+    /// Add a detached worktree under `.git/stax-worktrees/<name>` for background rebases,
+    /// replacing any copy a crashed run left behind
+    pub fn add_scratch_worktree(&self, name: &str) -> Result<PathBuf> {
+        let path = self.git_dir()?.join("stax-worktrees").join(name);
+        if path.exists() {
+            self.remove_worktree(&path)?;
+        }
+        let path_arg = path.to_string_lossy().to_string();
+        let output = self.run_git(self.workdir()?, &["worktree", "add", "--detach", &path_arg])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            anyhow::bail!("git worktree add {} failed: {}", path.display(), stderr);
+        }
+        Ok(path)
+    }
+
+    /// Remove a worktree created by [`GitRepo::add_scratch_worktree`]
+    pub fn remove_worktree(&self, path: &Path) -> Result<()> {
+        let path_arg = path.to_string_lossy().to_string();
+        let output = self.run_git(
+            self.workdir()?,
+            &["worktree", "remove", "--force", &path_arg],
+        )?;
+        if !output.status.success() && path.exists() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            anyhow::bail!("git worktree remove {} failed: {}", path.display(), stderr);
+        }
+        let _ = self.run_git(self.workdir()?, &["worktree", "prune"]);
+        Ok(())
+    }
+
+    /// Check out `branch` detached in the scratch worktree at `path`, so it can be
+    /// checked out there even while a branch worktree has it
+    pub fn checkout_detached_in_worktree(path: &Path, branch: &str) -> Result<()> {
+        let output = Command::new("git")
+            .args(["checkout", "--quiet", "--detach", branch])
+            .current_dir(path)
+            .output()
+            .context("Failed to run git checkout")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            anyhow::bail!(
+                "git checkout {} failed in '{}': {}",
+                branch,
+                path.display(),
+                stderr
+            );
+        }
+        Ok(())
+    }
+
+    /// Check out `branch` in the scratch worktree at `path` and rebase it onto `onto`.
+    /// A conflicting rebase is aborted there, leaving the branch untouched.
+    /// Takes no `&self` so parallel restacks can call it from worker threads.
+    pub fn rebase_in_worktree(
+        path: &Path,
+        branch: &str,
+        onto: &str,
+        options: &RebaseOptions,
+    ) -> Result<RebaseResult> {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(path)
+                .output()
+                .with_context(|| format!("Failed to run git {}", args.join(" ")))
+        };
+
+        let output = git(&["checkout", "--quiet", branch])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            anyhow::bail!(
+                "git checkout {} failed in '{}': {}",
+                branch,
+                path.display(),
+                stderr
+            );
+        }
+
+        let args = rebase_args(onto, None, options);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        if git(&args)?.status.success() {
+            return Ok(RebaseResult::Success);
+        }
+
+        let abort = git(&["rebase", "--abort"])?;
+        if !abort.status.success() {
+            anyhow::bail!(
+                "Failed to rebase '{}' onto '{}' in '{}'",
+                branch,
+                onto,
+                path.display()
+            );
+        }
+        Ok(RebaseResult::Conflict)
+    }
+
     /// Continue a rebase after resolving conflicts
     pub fn rebase_continue(&self) -> Result<RebaseResult> {
         let status = Command::new("git")
@@ -474,6 +679,15 @@ Use --auto-stash-pop or stash/commit changes first.",
         self.rebase_in_progress_at(self.workdir()?)
     }
 
+    /// The branch an in-progress rebase is rewriting, if any
+    pub fn rebasing_branch(&self) -> Option<String> {
+        let git_dir = self.git_dir_in_path(self.workdir().ok()?).ok()?;
+        ["rebase-merge", "rebase-apply"].iter().find_map(|dir| {
+            let head = std::fs::read_to_string(git_dir.join(dir).join("head-name")).ok()?;
+            Some(head.trim().trim_start_matches("refs/heads/").to_string())
+        })
+    }
+
     /// Create a new branch at HEAD
     pub fn create_branch(&self, name: &str) -> Result<()> {
         let head = self.repo.head()?;
@@ -505,11 +719,13 @@ Use --auto-stash-pop or stash/commit changes first.",
         Ok(())
     }
 
-    /// Find merge-base commit between two local branches
+    /// Find merge-base commit between two local branches (`left` may also be a
+    /// remote-tracking parent)
     pub fn merge_base(&self, left: &str, right: &str) -> Result<String> {
         let left_commit = self
             .repo
-            .find_branch(left, BranchType::Local)?
+            .find_branch(left, BranchType::Local)
+            .or_else(|_| self.repo.find_branch(left, BranchType::Remote))?
             .get()
             .peel_to_commit()?;
         let right_commit = self
@@ -604,6 +820,42 @@ Use --auto-stash-pop or stash/commit changes first.",
         Ok(commits)
     }
 
+    /// Commits on `branch` not reachable from any of `bases`; bases that don't resolve are skipped
+    pub fn commits_not_reachable_from(
+        &self,
+        branch: &str,
+        bases: &[String],
+    ) -> Result<Vec<CommitInfo>> {
+        let branch_ref = self.repo.find_branch(branch, BranchType::Local)?;
+        let branch_commit = branch_ref.get().peel_to_commit()?;
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(branch_commit.id())?;
+        for base in bases {
+            if let Ok(oid) = self.resolve_to_oid(base) {
+                revwalk.hide(oid)?;
+            }
+        }
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let oid = oid?;
+            let commit = self.repo.find_commit(oid)?;
+            commits.push(CommitInfo {
+                short_hash: oid.to_string()[..10].to_string(),
+                message: commit.summary().unwrap_or("").to_string(),
+            });
+        }
+
+        Ok(commits)
+    }
+
+    /// Commit time (seconds since the epoch) of a branch's tip
+    pub fn branch_commit_time(&self, branch: &str) -> Result<i64> {
+        let branch_ref = self.repo.find_branch(branch, BranchType::Local)?;
+        Ok(branch_ref.get().peel_to_commit()?.time().seconds())
+    }
+
     /// Get time since last commit on a branch
     pub fn branch_age(&self, branch: &str) -> Result<String> {
         let branch_ref = self.repo.find_branch(branch, BranchType::Local)?;
@@ -822,11 +1074,10 @@ Use --auto-stash-pop or stash/commit changes first.",
         Ok(Vec::new())
     }
 
-    /// Get files modified in a branch compared to its parent
-    #[allow(dead_code)] // Reserved for future conflict detection improvements
+    /// Files changed on a branch since it forked from its parent
     pub fn files_modified(&self, branch: &str, parent: &str) -> Result<Vec<String>> {
         let output = Command::new("git")
-            .args(["diff", "--name-only", parent, branch])
+            .args(["diff", "--name-only", &format!("{}...{}", parent, branch)])
             .current_dir(self.workdir()?)
             .output()
             .context("Failed to get modified files")?;
@@ -965,6 +1216,59 @@ pub enum RebaseResult {
     Conflict,
 }
 
+/// `remote.trunk` from the config, if set
+fn configured_trunk(config: &crate::config::Config) -> Option<String> {
+    config
+        .remote
+        .trunk
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+}
+
+/// Arguments for `git rebase <onto>`, or `git rebase --onto <onto> <upstream>`
+fn rebase_args(onto: &str, upstream: Option<&str>, options: &RebaseOptions) -> Vec<String> {
+    let mut args: Vec<String> = Vec::new();
+    if options.no_hooks {
+        // --no-verify only covers pre-rebase; pointing hooksPath at nothing silences the rest
+        args.extend(["-c".to_string(), "core.hooksPath=/dev/null".to_string()]);
+    }
+    args.push("rebase".to_string());
+    if options.no_hooks {
+        args.push("--no-verify".to_string());
+    }
+    if let Some(strategy) = &options.strategy {
+        args.push(format!("--strategy={}", strategy));
+    }
+    for option in &options.strategy_options {
+        args.push(format!("--strategy-option={}", option));
+    }
+    if options.update_refs {
+        args.push("--update-refs".to_string());
+    }
+    match upstream {
+        Some(upstream) => {
+            args.extend(["--onto".to_string(), onto.to_string(), upstream.to_string()])
+        }
+        None => args.push(onto.to_string()),
+    }
+    args
+}
+
+/// Extra flags passed through to `git rebase`
+#[derive(Debug, Clone, Default)]
+pub struct RebaseOptions {
+    /// Run with git hooks disabled
+    pub no_hooks: bool,
+    /// Merge strategy (`--strategy`)
+    pub strategy: Option<String>,
+    /// Strategy options (`--strategy-option`, e.g. `ours`)
+    pub strategy_options: Vec<String>,
+    /// Move other local branches that point into the rebased range (`--update-refs`)
+    pub update_refs: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct CommitInfo {
     pub short_hash: String,
@@ -1,15 +1,58 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use octocrab::params::repos::Reference;
-use octocrab::Octocrab;
+use octocrab::service::middleware::retry::RetryConfig;
+use octocrab::{Octocrab, Page};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 use crate::config::Config;
+use crate::error::StaxError;
+
+/// Upper bound on items collected from one paginated list endpoint
+pub const MAX_PAGINATED_ITEMS: usize = 1000;
+
+/// Standup only looks at recent activity, so its searches stop earlier
+const STANDUP_SEARCH_LIMIT: usize = 100;
+
+/// Longest stax waits between two attempts, even when `Retry-After` asks for more
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
 
 pub struct GitHubClient {
     pub octocrab: Octocrab,
+    /// Same session with octocrab's own retries turned off; used by [`GitHubClient::request`],
+    /// which retries with backoff itself
+    unretried: Octocrab,
     pub owner: String,
     pub repo: String,
+    pub retry: RetryPolicy,
+}
+
+/// How [`GitHubClient::request`] backs off on transient failures
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Attempts after the first one (`remote.max_retries`)
+    pub max_retries: u32,
+    /// Wait before the first retry; doubles on each following one
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (1-based), preferring the server's `Retry-After`
+    fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .unwrap_or_else(|| self.base_delay.saturating_mul(1 << (retry - 1).min(16)))
+            .min(MAX_RETRY_DELAY)
+    }
+}
+
+/// HTTP methods [`GitHubClient::request`] sends
+#[derive(Debug, Clone, Copy)]
+pub enum ApiMethod {
+    Get,
+    Post,
+    Patch,
 }
 
 impl Clone for GitHubClient {
@@ -18,8 +61,10 @@ impl Clone for GitHubClient {
         // This is only used in tests where we create fresh clients anyway
         Self {
             octocrab: self.octocrab.clone(),
+            unretried: self.unretried.clone(),
             owner: self.owner.clone(),
             repo: self.repo.clone(),
+            retry: self.retry,
         }
     }
 }
@@ -37,6 +82,14 @@ struct CheckRun {
     conclusion: Option<String>,
 }
 
+/// The account behind a token, as reported by `GET /user`
+#[derive(Debug, Clone)]
+pub struct TokenIdentity {
+    pub login: String,
+    /// Classic OAuth scopes, or `None` when the token doesn't report them
+    pub scopes: Option<Vec<String>>,
+}
+
 /// PR activity for standup reports
 #[derive(Debug, Clone, Serialize)]
 pub struct PrActivity {
@@ -80,12 +133,7 @@ struct Review {
     user: Option<ReviewUser>,
 }
 
-/// Response from GitHub search issues API
-#[derive(Debug, Deserialize)]
-struct SearchIssuesResponse {
-    items: Vec<SearchIssue>,
-}
-
+/// Item from the GitHub search issues API
 #[derive(Debug, Deserialize)]
 struct SearchIssue {
     number: u64,
@@ -98,24 +146,32 @@ struct SearchIssue {
 impl GitHubClient {
     /// Create a new GitHub client from config
     pub fn new(owner: &str, repo: &str, api_base_url: Option<String>) -> Result<Self> {
-        let token = Config::github_token().context(
-            "GitHub auth not configured. Use one of: `stax auth`, `stax auth --from-gh`, \
-             `gh auth login`, or set `STAX_GITHUB_TOKEN`.",
-        )?;
-
-        let mut builder = Octocrab::builder().personal_token(token.to_string());
-        if let Some(api_base) = api_base_url {
-            builder = builder
-                .base_uri(api_base)
-                .context("Failed to set GitHub API base URL")?;
-        }
-
-        let octocrab = builder.build().context("Failed to create GitHub client")?;
+        let token = Config::github_token().ok_or(StaxError::AuthMissing)?;
+        let config = Config::load().unwrap_or_default();
+
+        // Direct octocrab calls keep its built-in retries; `request` backs off on its own
+        let build = |retry: Option<RetryConfig>| -> Result<Octocrab> {
+            let mut builder = Octocrab::builder().personal_token(token.to_string());
+            if let Some(retry) = retry {
+                builder = builder.add_retry_config(retry);
+            }
+            if let Some(api_base) = &api_base_url {
+                builder = builder
+                    .base_uri(api_base.as_str())
+                    .context("Failed to set GitHub API base URL")?;
+            }
+            builder.build().context("Failed to create GitHub client")
+        };
 
         Ok(Self {
-            octocrab,
+            octocrab: build(None)?,
+            unretried: build(Some(RetryConfig::None))?,
             owner: owner.to_string(),
             repo: repo.to_string(),
+            retry: RetryPolicy {
+                max_retries: config.remote.max_retries,
+                base_delay: Duration::from_secs(1),
+            },
         })
     }
 
@@ -123,12 +179,128 @@ impl GitHubClient {
     #[cfg(test)]
     pub fn with_octocrab(octocrab: Octocrab, owner: &str, repo: &str) -> Self {
         Self {
+            unretried: octocrab.clone(),
             octocrab,
             owner: owner.to_string(),
             repo: repo.to_string(),
+            retry: RetryPolicy {
+                max_retries: 3,
+                base_delay: Duration::from_millis(1),
+            },
+        }
+    }
+
+    /// Send a request to `route` (e.g. `/repos/o/r/pulls/1`) and parse the JSON reply.
+    /// 5xx responses, and 403/429 responses carrying `Retry-After`, are retried up to
+    /// `retry.max_retries` times with exponential backoff; the final error says how many
+    /// attempts were made.
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        method: ApiMethod,
+        route: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<T> {
+        self.send(method, route, body, self.retry.max_retries).await
+    }
+
+    /// Like [`GitHubClient::request`], but sent exactly once. For non-idempotent calls
+    /// (creating a PR), where a retry after a lost response would act twice.
+    pub async fn request_once<T: DeserializeOwned>(
+        &self,
+        method: ApiMethod,
+        route: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<T> {
+        self.send(method, route, body, 0).await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: ApiMethod,
+        route: &str,
+        body: Option<&serde_json::Value>,
+        max_retries: u32,
+    ) -> Result<T> {
+        let mut attempt: u32 = 0;
+        loop {
+            attempt += 1;
+            let can_retry = attempt <= max_retries;
+            let failed = || format!("{:?} {} failed after {} attempts", method, route, attempt);
+
+            let sent = match method {
+                ApiMethod::Get => self.unretried._get(route).await,
+                ApiMethod::Post => self.unretried._post(route, body).await,
+                ApiMethod::Patch => self.unretried._patch(route, body).await,
+            };
+            let response = match sent {
+                Ok(response) => response,
+                Err(_) if can_retry => {
+                    tokio::time::sleep(self.retry.delay(attempt, None)).await;
+                    continue;
+                }
+                Err(err) if attempt > 1 => return Err(err).with_context(failed),
+                Err(err) => return Err(err.into()),
+            };
+
+            let status = response.status();
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let transient = status.is_server_error()
+                || (matches!(status.as_u16(), 403 | 429) && retry_after.is_some());
+            if transient && can_retry {
+                tokio::time::sleep(self.retry.delay(attempt, retry_after)).await;
+                continue;
+            }
+
+            let response = match octocrab::map_github_error(response).await {
+                Ok(response) => response,
+                Err(err) if attempt > 1 => {
+                    return Err(err).with_context(|| format!("{} ({})", failed(), status))
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let text = self.unretried.body_to_string(response).await?;
+            return serde_json::from_str(&text)
+                .with_context(|| format!("Unexpected response from {}", route));
         }
     }
 
+    /// GET a list endpoint and follow its `Link: rel="next"` pages, up to `limit` items.
+    /// Works for both plain array responses and search-style `{ "items": [...] }` bodies.
+    pub async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        limit: usize,
+    ) -> Result<Vec<T>> {
+        let first: Page<T> = self.octocrab.get(url, None::<&()>).await?;
+        self.collect_pages(first, limit).await
+    }
+
+    /// Collect `first` and every following page, up to `limit` items
+    pub async fn collect_pages<T: DeserializeOwned>(
+        &self,
+        first: Page<T>,
+        limit: usize,
+    ) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut page = Some(first);
+
+        while let Some(mut current) = page {
+            items.append(&mut current.take_items());
+            if items.len() >= limit {
+                items.truncate(limit);
+                break;
+            }
+            page = self.octocrab.get_page(&current.next).await?;
+        }
+
+        Ok(items)
+    }
+
     /// Get combined CI status from both commit statuses AND check runs (GitHub Actions)
     pub async fn combined_status_state(&self, commit_sha: &str) -> Result<Option<String>> {
         // First, check legacy commit statuses
@@ -156,7 +328,7 @@ impl GitHubClient {
     /// Get status from GitHub Actions check runs
     async fn get_check_runs_status(&self, commit_sha: &str) -> Result<Option<String>> {
         let url = format!(
-            "/repos/{}/{}/commits/{}/check-runs",
+            "/repos/{}/{}/commits/{}/check-runs?per_page=100",
             self.owner, self.repo, commit_sha
         );
 
@@ -213,6 +385,36 @@ impl GitHubClient {
         Ok(user.login)
     }
 
+    /// Look up who the token authenticates as and which classic scopes it carries
+    pub async fn token_identity(&self) -> Result<TokenIdentity> {
+        #[derive(Deserialize)]
+        struct User {
+            login: String,
+        }
+
+        let response = self.octocrab._get("/user").await?;
+        let response = octocrab::map_github_error(response).await?;
+        // Fine-grained and app tokens omit the header entirely
+        let scopes = response
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|scope| scope.trim().to_string())
+                    .filter(|scope| !scope.is_empty())
+                    .collect()
+            });
+        let body = self.octocrab.body_to_string(response).await?;
+        let user: User = serde_json::from_str(&body).context("Unexpected /user response")?;
+
+        Ok(TokenIdentity {
+            login: user.login,
+            scopes,
+        })
+    }
+
     /// Get PRs merged by the user in the last N hours
     pub async fn get_recent_merged_prs(
         &self,
@@ -226,10 +428,9 @@ impl GitHubClient {
             self.owner, self.repo, username
         );
 
-        let response: SearchIssuesResponse = self.octocrab.get(&url, None::<&()>).await?;
+        let items: Vec<SearchIssue> = self.get_all_pages(&url, STANDUP_SEARCH_LIMIT).await?;
 
-        let merged: Vec<PrActivity> = response
-            .items
+        let merged: Vec<PrActivity> = items
             .into_iter()
             .filter_map(|issue| {
                 let closed_at = issue.closed_at?;
@@ -262,10 +463,9 @@ impl GitHubClient {
             self.owner, self.repo, username
         );
 
-        let response: SearchIssuesResponse = self.octocrab.get(&url, None::<&()>).await?;
+        let items: Vec<SearchIssue> = self.get_all_pages(&url, STANDUP_SEARCH_LIMIT).await?;
 
-        let opened: Vec<PrActivity> = response
-            .items
+        let opened: Vec<PrActivity> = items
             .into_iter()
             .filter(|issue| issue.created_at >= since)
             .map(|issue| PrActivity {
@@ -293,19 +493,18 @@ impl GitHubClient {
             "/search/issues?q=repo:{}/{}+author:{}+is:pr+is:open&per_page=20",
             self.owner, self.repo, username
         );
-        let response: SearchIssuesResponse = self.octocrab.get(&url, None::<&()>).await?;
+        let items: Vec<SearchIssue> = self.get_all_pages(&url, STANDUP_SEARCH_LIMIT).await?;
 
         let mut reviews = Vec::new();
 
         // Only check reviews on user's own PRs (small list, few API calls)
-        for issue in response.items {
+        for issue in items {
             let reviews_url = format!(
-                "/repos/{}/{}/pulls/{}/reviews",
+                "/repos/{}/{}/pulls/{}/reviews?per_page=100",
                 self.owner, self.repo, issue.number
             );
             let pr_reviews: Vec<Review> = self
-                .octocrab
-                .get(&reviews_url, None::<&()>)
+                .get_all_pages(&reviews_url, MAX_PAGINATED_ITEMS)
                 .await
                 .unwrap_or_default();
 
@@ -354,16 +553,15 @@ impl GitHubClient {
             self.owner, self.repo, username
         );
 
-        let response: SearchIssuesResponse = self
-            .octocrab
-            .get(&url, None::<&()>)
+        let items: Vec<SearchIssue> = self
+            .get_all_pages(&url, MAX_PAGINATED_ITEMS)
             .await
             .context("Failed to search PRs")?;
 
         // For each PR from search, we need to get the branch info
         // Search API doesn't include head/base branch refs, so we fetch each PR
         let mut results = Vec::new();
-        for issue in response.items {
+        for issue in items {
             // Fetch full PR details to get branch info
             let pr = self
                 .octocrab
@@ -403,6 +601,52 @@ mod tests {
         GitHubClient::with_octocrab(octocrab, "test-owner", "test-repo")
     }
 
+    #[tokio::test]
+    async fn test_token_identity_reads_classic_scopes() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-oauth-scopes", "repo, read:org, gist")
+                    .set_body_json(serde_json::json!({ "login": "octocat", "id": 1 })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let identity = client.token_identity().await.unwrap();
+        assert_eq!(identity.login, "octocat");
+        assert_eq!(
+            identity.scopes,
+            Some(vec![
+                "repo".to_string(),
+                "read:org".to_string(),
+                "gist".to_string()
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_identity_without_scopes_header() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "login": "octocat", "id": 1 })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let identity = client.token_identity().await.unwrap();
+        assert_eq!(identity.login, "octocat");
+        assert!(identity.scopes.is_none());
+    }
+
     #[tokio::test]
     async fn test_check_runs_all_success() {
         let mock_server = MockServer::start().await;
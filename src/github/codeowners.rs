@@ -0,0 +1,214 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Where GitHub looks for CODEOWNERS, in the order it checks them
+const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Parsed CODEOWNERS file; later rules take precedence over earlier ones
+#[derive(Debug, Default)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    pattern: Regex,
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Load the repository's CODEOWNERS file, if it has one
+    pub fn load(workdir: &Path) -> Option<Self> {
+        LOCATIONS
+            .iter()
+            .find_map(|location| fs::read_to_string(workdir.join(location)).ok())
+            .map(|content| Self::parse(&content))
+    }
+
+    /// Parse CODEOWNERS content, skipping lines whose pattern can't be used
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut tokens = line.split_whitespace();
+                let pattern = tokens.next()?;
+                let owners = tokens
+                    .take_while(|token| !token.starts_with('#'))
+                    .map(String::from)
+                    .collect();
+                Some(Rule {
+                    pattern: pattern_regex(pattern)?,
+                    owners,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Owners of `path` (relative to the repo root): the last matching rule wins, and
+    /// a matching rule without owners leaves the path unowned
+    pub fn owners_for(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.pattern.is_match(path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Reviewer handles for a set of changed paths: users as `login`, teams as
+    /// `org/team`. Email owners are dropped since reviews can't be requested from them.
+    pub fn reviewers_for<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let mut reviewers: Vec<String> = Vec::new();
+        for path in paths {
+            for owner in self.owners_for(path) {
+                let Some(handle) = owner.strip_prefix('@') else {
+                    continue;
+                };
+                if !reviewers.iter().any(|r| r.eq_ignore_ascii_case(handle)) {
+                    reviewers.push(handle.to_string());
+                }
+            }
+        }
+        reviewers
+    }
+}
+
+/// Translate a CODEOWNERS pattern (gitignore-style) into a regex over repo-relative paths.
+///
+/// - A leading or inner `/` anchors the pattern at the root; otherwise it matches at any depth
+/// - `*` and `?` stay within one path segment, `**` spans segments
+/// - A pattern naming a directory owns everything below it, except `dir/*`, which
+///   only covers the files directly inside
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let pattern = pattern.strip_prefix('\\').unwrap_or(pattern);
+    let (pattern, dir_only) = match pattern.strip_suffix('/') {
+        Some(dir) => (dir, true),
+        None => (pattern, false),
+    };
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    regex.push_str(if dir_only {
+        "/.*$"
+    } else if pattern.ends_with("/*") {
+        "$"
+    } else {
+        "(?:/.*)?$"
+    });
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        pattern_regex(pattern).unwrap().is_match(path)
+    }
+
+    #[test]
+    fn test_unanchored_patterns_match_at_any_depth() {
+        assert!(matches("*.js", "app.js"));
+        assert!(matches("*.js", "src/web/app.js"));
+        assert!(!matches("*.js", "app.jsx"));
+        assert!(matches("docs", "docs/guide.md"));
+        assert!(matches("docs", "src/docs/guide.md"));
+        assert!(matches("Makefile", "tools/Makefile"));
+    }
+
+    #[test]
+    fn test_anchored_and_directory_patterns() {
+        assert!(matches("/build/logs/", "build/logs/today.log"));
+        assert!(matches("/build/logs/", "build/logs/old/today.log"));
+        assert!(!matches("/build/logs/", "src/build/logs/today.log"));
+        assert!(!matches("/build/logs/", "build/logs"));
+        assert!(matches("apps/", "services/apps/main.rs"));
+        assert!(matches("src/api", "src/api/handler.rs"));
+        assert!(!matches("src/api", "lib/src/api/handler.rs"));
+    }
+
+    #[test]
+    fn test_single_star_stays_in_one_directory() {
+        assert!(matches("docs/*", "docs/getting-started.md"));
+        assert!(!matches("docs/*", "docs/build-app/troubleshooting.md"));
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(!matches("src/*.rs", "src/commands/mod.rs"));
+        assert!(matches("file?.txt", "file1.txt"));
+        assert!(!matches("file?.txt", "file10.txt"));
+    }
+
+    #[test]
+    fn test_double_star_spans_directories() {
+        assert!(matches("**/logs", "logs/today.log"));
+        assert!(matches("**/logs", "deep/nested/logs/today.log"));
+        assert!(matches("/src/**/tests", "src/a/b/tests/it.rs"));
+        assert!(matches("/src/**/tests", "src/tests/it.rs"));
+        assert!(matches("/scripts/**", "scripts/ci/run.sh"));
+    }
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let owners = CodeOwners::parse(
+            "# Default owners\n\
+             *       @octo/everyone\n\
+             *.rs    @rustacean @octo/core  # inline comment\n\
+             /src/generated/\n\
+             /docs/  docs@example.com @writer\n",
+        );
+
+        assert_eq!(owners.owners_for("README.md"), ["@octo/everyone"]);
+        assert_eq!(
+            owners.owners_for("src/main.rs"),
+            ["@rustacean", "@octo/core"]
+        );
+        assert!(owners.owners_for("src/generated/schema.rs").is_empty());
+        assert_eq!(
+            owners.owners_for("docs/intro.md"),
+            ["docs@example.com", "@writer"]
+        );
+    }
+
+    #[test]
+    fn test_reviewers_for_dedupes_and_skips_emails() {
+        let owners = CodeOwners::parse(
+            "*.rs @rustacean @octo/core\n\
+             *.md @writer\n\
+             /docs/ docs@example.com @Writer\n",
+        );
+
+        let reviewers =
+            owners.reviewers_for(["src/main.rs", "src/lib.rs", "docs/intro.md", "README.md"]);
+        assert_eq!(reviewers, ["rustacean", "octo/core", "Writer"]);
+        assert!(CodeOwners::default()
+            .reviewers_for(["src/main.rs"])
+            .is_empty());
+    }
+}
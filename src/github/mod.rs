@@ -1,5 +1,7 @@
 pub mod client;
+pub mod codeowners;
 pub mod pr;
+pub mod pr_body;
 pub mod pr_template;
 
-pub use client::{GitHubClient, PrActivity, ReviewActivity};
+pub use client::{GitHubClient, PrActivity, ReviewActivity, TokenIdentity};
@@ -1,10 +1,13 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use octocrab::models::pulls::PullRequest;
 use octocrab::params::pulls::Sort;
 use octocrab::params::State;
 use serde::Deserialize;
 use std::collections::HashMap;
 
+use super::client::{ApiMethod, MAX_PAGINATED_ITEMS};
+use super::pr_body;
 use super::GitHubClient;
 use crate::remote::RemoteInfo;
 
@@ -70,6 +73,8 @@ pub struct PrInfo {
     pub state: String,
     pub is_draft: bool,
     pub base: String,
+    /// "pass", "fail", or "pending"; only filled in by calls that read CI
+    pub checks_state: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -79,6 +84,18 @@ pub struct PrInfoWithHead {
     pub head_label: Option<String>,
 }
 
+/// PR state plus mergeability and CI, as fetched for `status`
+#[derive(Debug, Clone)]
+pub struct PrInfoWithChecks {
+    pub info: PrInfo,
+    /// "mergeable", "conflicting", or "unknown"
+    #[allow(dead_code)] // Fetched with the batch; not shown by status yet
+    pub mergeable: Option<String>,
+    /// Check rollup of the head commit ("success", "failure", "error", "pending");
+    /// `info.checks_state` holds it collapsed to pass/fail/pending
+    pub checks: Option<String>,
+}
+
 /// Merge method for PRs
 #[derive(Debug, Clone, Copy, Default)]
 pub enum MergeMethod {
@@ -268,6 +285,44 @@ struct ReviewNode {
     state: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct PrBatchData {
+    /// One entry per `pr<number>` alias; null when that PR doesn't exist
+    repository: Option<HashMap<String, Option<PrBatchNode>>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrBatchNode {
+    number: u64,
+    state: String,
+    is_draft: bool,
+    base_ref_name: String,
+    mergeable: Option<String>,
+    commits: PrBatchCommits,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrBatchCommits {
+    nodes: Vec<PrBatchCommitNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrBatchCommitNode {
+    commit: PrBatchCommit,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrBatchCommit {
+    status_check_rollup: Option<PrBatchRollup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrBatchRollup {
+    state: String,
+}
+
 impl GitHubClient {
     /// Find existing open PR for a branch
     ///
@@ -309,6 +364,7 @@ impl GitHubClient {
                             .unwrap_or_default(),
                         is_draft: pr.draft.unwrap_or(false),
                         base: pr.base.ref_field.clone(),
+                        checks_state: None,
                     }));
                 }
             }
@@ -362,6 +418,7 @@ impl GitHubClient {
                                 .unwrap_or_default(),
                             is_draft: pr.draft.unwrap_or(false),
                             base: pr.base.ref_field.clone(),
+                            checks_state: None,
                         },
                         head: pr.head.ref_field.clone(),
                     },
@@ -378,6 +435,32 @@ impl GitHubClient {
         Ok(prs_by_head)
     }
 
+    /// `/repos/{owner}/{repo}/pulls` followed by `suffix`
+    fn pulls_route(&self, suffix: &str) -> String {
+        format!("/repos/{}/{}/pulls{}", self.owner, self.repo, suffix)
+    }
+
+    /// Fetch a PR by number
+    async fn fetch_pr(&self, pr_number: u64) -> Result<PullRequest> {
+        self.request(
+            ApiMethod::Get,
+            &self.pulls_route(&format!("/{}", pr_number)),
+            None,
+        )
+        .await
+        .context("Failed to get PR")
+    }
+
+    /// PATCH a PR with the given fields
+    async fn patch_pr(&self, pr_number: u64, fields: serde_json::Value) -> Result<PullRequest> {
+        self.request(
+            ApiMethod::Patch,
+            &self.pulls_route(&format!("/{}", pr_number)),
+            Some(&fields),
+        )
+        .await
+    }
+
     /// Create a new PR
     pub async fn create_pr(
         &self,
@@ -387,13 +470,17 @@ impl GitHubClient {
         body: &str,
         draft: bool,
     ) -> Result<PrInfo> {
-        let pr = self
-            .octocrab
-            .pulls(&self.owner, &self.repo)
-            .create(title, branch, base)
-            .body(body)
-            .draft(Some(draft))
-            .send()
+        let fields = serde_json::json!({
+            "title": title,
+            "head": branch,
+            "base": base,
+            "body": body,
+            "draft": draft,
+        });
+        // Not retried: if GitHub created the PR but the reply was lost, a retry would
+        // only fail with "already exists"
+        let pr: PullRequest = self
+            .request_once(ApiMethod::Post, &self.pulls_route(""), Some(&fields))
             .await
             .context("Failed to create PR")?;
 
@@ -406,17 +493,13 @@ impl GitHubClient {
                 .unwrap_or_default(),
             is_draft: pr.draft.unwrap_or(false),
             base: pr.base.ref_field.clone(),
+            checks_state: None,
         })
     }
 
     /// Get a PR by number
     pub async fn get_pr(&self, pr_number: u64) -> Result<PrInfo> {
-        let pr = self
-            .octocrab
-            .pulls(&self.owner, &self.repo)
-            .get(pr_number)
-            .await
-            .context("Failed to get PR")?;
+        let pr = self.fetch_pr(pr_number).await?;
 
         Ok(PrInfo {
             number: pr.number,
@@ -427,17 +510,42 @@ impl GitHubClient {
                 .unwrap_or_default(),
             is_draft: pr.draft.unwrap_or(false),
             base: pr.base.ref_field.clone(),
+            checks_state: None,
         })
     }
 
+    /// Get a PR by number, with CI read from the statuses and check runs of its head commit
+    pub async fn get_pr_with_checks(&self, pr_number: u64) -> Result<PrInfoWithChecks> {
+        let pr = self.fetch_pr(pr_number).await?;
+        let checks = self.combined_status_state(&pr.head.sha).await?;
+
+        Ok(PrInfoWithChecks {
+            info: PrInfo {
+                number: pr.number,
+                state: pr
+                    .state
+                    .as_ref()
+                    .map(|s| format!("{:?}", s))
+                    .unwrap_or_default(),
+                is_draft: pr.draft.unwrap_or(false),
+                base: pr.base.ref_field.clone(),
+                checks_state: checks.as_deref().and_then(checks_state).map(String::from),
+            },
+            mergeable: None,
+            checks,
+        })
+    }
+
+    /// Current PR description (empty if none)
+    pub async fn get_pr_body(&self, pr_number: u64) -> Result<String> {
+        let pr = self.fetch_pr(pr_number).await?;
+
+        Ok(pr.body.unwrap_or_default())
+    }
+
     /// Get a PR by number, including head branch name
     pub async fn get_pr_with_head(&self, pr_number: u64) -> Result<PrInfoWithHead> {
-        let pr = self
-            .octocrab
-            .pulls(&self.owner, &self.repo)
-            .get(pr_number)
-            .await
-            .context("Failed to get PR")?;
+        let pr = self.fetch_pr(pr_number).await?;
 
         Ok(PrInfoWithHead {
             head: pr.head.ref_field.clone(),
@@ -451,17 +559,20 @@ impl GitHubClient {
                     .unwrap_or_default(),
                 is_draft: pr.draft.unwrap_or(false),
                 base: pr.base.ref_field.clone(),
+                checks_state: None,
             },
         })
     }
 
+    /// Get the commit SHA a PR's head currently points at
+    pub async fn get_pr_head_sha(&self, pr_number: u64) -> Result<String> {
+        let pr = self.fetch_pr(pr_number).await?;
+        Ok(pr.head.sha)
+    }
+
     /// Update PR base branch
     pub async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<()> {
-        self.octocrab
-            .pulls(&self.owner, &self.repo)
-            .update(pr_number)
-            .base(new_base)
-            .send()
+        self.patch_pr(pr_number, serde_json::json!({ "base": new_base }))
             .await
             .context("Failed to update PR base")?;
         Ok(())
@@ -469,54 +580,78 @@ impl GitHubClient {
 
     /// Update PR body text
     pub async fn update_pr_body(&self, pr_number: u64, body: &str) -> Result<()> {
-        self.octocrab
-            .pulls(&self.owner, &self.repo)
-            .update(pr_number)
-            .body(body)
-            .send()
+        self.patch_pr(pr_number, serde_json::json!({ "body": body }))
             .await
             .context("Failed to update PR body")?;
         Ok(())
     }
 
-    /// Add or update the stack comment on a PR
+    /// Replace the PR title
+    pub async fn update_pr_title(&self, pr_number: u64, title: &str) -> Result<()> {
+        self.patch_pr(pr_number, serde_json::json!({ "title": title }))
+            .await
+            .context("Failed to update PR title")?;
+        Ok(())
+    }
+
+    /// Close a PR without merging
+    pub async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        self.patch_pr(pr_number, serde_json::json!({ "state": "closed" }))
+            .await
+            .context("Failed to close PR")?;
+        Ok(())
+    }
+
+    /// Reopen a closed PR
+    pub async fn reopen_pr(&self, pr_number: u64) -> Result<()> {
+        self.patch_pr(pr_number, serde_json::json!({ "state": "open" }))
+            .await
+            .context("Failed to reopen PR")?;
+        Ok(())
+    }
+
+    /// Add or update the stack list in a PR's body, replacing the previous copy
     pub async fn update_stack_comment(&self, pr_number: u64, stack_comment: &str) -> Result<()> {
-        let comments = self
+        let current = self.get_pr_body(pr_number).await?;
+        let body = pr_body::with_stack_section(&current, stack_comment);
+        if body != current {
+            self.update_pr_body(pr_number, &body).await?;
+        }
+
+        self.delete_legacy_stack_comment(pr_number).await
+    }
+
+    /// Older versions posted the stack as a separate comment; drop it now that it lives in the body
+    async fn delete_legacy_stack_comment(&self, pr_number: u64) -> Result<()> {
+        let first = self
             .octocrab
             .issues(&self.owner, &self.repo)
             .list_comments(pr_number)
+            .per_page(100u8)
             .send()
             .await
             .context("Failed to list comments")?;
+        let comments = self
+            .collect_pages(first, MAX_PAGINATED_ITEMS)
+            .await
+            .context("Failed to list comments")?;
 
-        // Look for existing stax comment
         let marker = "<!-- stax-stack-comment -->";
-        let full_comment = format!("{}\n{}", marker, stack_comment);
-
-        for comment in comments.items {
+        for comment in comments {
             if comment
                 .body
                 .as_ref()
                 .map(|b| b.contains(marker))
                 .unwrap_or(false)
             {
-                // Update existing comment
                 self.octocrab
                     .issues(&self.owner, &self.repo)
-                    .update_comment(comment.id, &full_comment)
+                    .delete_comment(comment.id)
                     .await
-                    .context("Failed to update comment")?;
-                return Ok(());
+                    .context("Failed to delete old stack comment")?;
             }
         }
 
-        // Create new comment
-        self.octocrab
-            .issues(&self.owner, &self.repo)
-            .create_comment(pr_number, &full_comment)
-            .await
-            .context("Failed to create comment")?;
-
         Ok(())
     }
 
@@ -525,9 +660,18 @@ impl GitHubClient {
             return Ok(());
         }
 
+        // `org/team` handles go in as team reviewers (by slug)
+        let (teams, users): (Vec<&String>, Vec<&String>) =
+            reviewers.iter().partition(|r| r.contains('/'));
+        let team_slugs: Vec<String> = teams
+            .iter()
+            .filter_map(|team| team.rsplit_once('/').map(|(_, slug)| slug.to_string()))
+            .collect();
+        let users: Vec<String> = users.into_iter().cloned().collect();
+
         self.octocrab
             .pulls(&self.owner, &self.repo)
-            .request_reviews(pr_number, reviewers.to_vec(), Vec::<String>::new())
+            .request_reviews(pr_number, users, team_slugs)
             .await
             .context("Failed to request reviewers")?;
 
@@ -593,6 +737,61 @@ impl GitHubClient {
         Ok(())
     }
 
+    /// Turn on GitHub auto-merge so the PR merges with `method` once its required
+    /// checks and reviews pass
+    pub async fn enable_auto_merge(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        let pr = self.fetch_pr(pr_number).await?;
+        let node_id = pr
+            .node_id
+            .context(format!("GitHub returned no node id for PR #{}", pr_number))?;
+
+        let query = r#"
+            mutation($id: ID!, $method: PullRequestMergeMethod!) {
+                enablePullRequestAutoMerge(input: { pullRequestId: $id, mergeMethod: $method }) {
+                    clientMutationId
+                }
+            }
+        "#;
+        let response: GraphQLResponse<serde_json::Value> = self
+            .request(
+                ApiMethod::Post,
+                "/graphql",
+                Some(&serde_json::json!({
+                    "query": query,
+                    "variables": {
+                        "id": node_id,
+                        "method": method.as_str().to_uppercase(),
+                    },
+                })),
+            )
+            .await
+            .context("Failed to enable auto-merge")?;
+
+        let Some(error) = response.errors.unwrap_or_default().into_iter().next() else {
+            return Ok(());
+        };
+        let message = error.message.to_lowercase();
+        if message.contains("not allowed") {
+            anyhow::bail!(
+                "Auto-merge is not enabled for {}/{}. Turn on \"Allow auto-merge\" in the repository settings.",
+                self.owner,
+                self.repo
+            );
+        }
+        if message.contains("clean status") || message.contains("unstable status") {
+            anyhow::bail!(
+                "PR #{} has no pending required checks, so GitHub won't queue an auto-merge.\n\
+                 Configure required status checks on its base branch, or merge it with `stax merge`.",
+                pr_number
+            );
+        }
+        anyhow::bail!(
+            "Failed to enable auto-merge on PR #{}: {}",
+            pr_number,
+            error.message
+        )
+    }
+
     /// Get detailed merge status for a PR
     pub async fn get_pr_merge_status(&self, pr_number: u64) -> Result<PrMergeStatus> {
         // Get basic PR info
@@ -697,6 +896,94 @@ impl GitHubClient {
         Ok((review_decision, approvals, changes_requested))
     }
 
+    /// State, draft flag, mergeability, and head-commit CI of several PRs in a single
+    /// GraphQL query. PRs that no longer exist are left out of the result.
+    pub async fn fetch_prs_batch(&self, numbers: &[u64]) -> Result<HashMap<u64, PrInfoWithChecks>> {
+        let mut numbers = numbers.to_vec();
+        numbers.sort_unstable();
+        numbers.dedup();
+        if numbers.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let fields: String = numbers
+            .iter()
+            .map(|n| format!("pr{n}: pullRequest(number: {n}) {{ ...PrStatus }}\n"))
+            .collect();
+        let query = format!(
+            r#"
+            query {{
+                repository(owner: "{}", name: "{}") {{
+                    {}
+                }}
+            }}
+            fragment PrStatus on PullRequest {{
+                number
+                state
+                isDraft
+                baseRefName
+                mergeable
+                commits(last: 1) {{
+                    nodes {{
+                        commit {{
+                            statusCheckRollup {{
+                                state
+                            }}
+                        }}
+                    }}
+                }}
+            }}
+            "#,
+            self.owner, self.repo, fields
+        );
+
+        let response: GraphQLResponse<PrBatchData> = self
+            .request(
+                ApiMethod::Post,
+                "/graphql",
+                Some(&serde_json::json!({ "query": query })),
+            )
+            .await
+            .context("Failed to query PR statuses")?;
+
+        // A missing PR nulls its alias and adds an error; only fail when nothing came back
+        let Some(prs) = response.data.and_then(|d| d.repository) else {
+            match response.errors.as_ref().and_then(|errors| errors.first()) {
+                Some(error) => anyhow::bail!("GraphQL error: {}", error.message),
+                None => return Ok(HashMap::new()),
+            }
+        };
+
+        Ok(prs
+            .into_values()
+            .flatten()
+            .map(|pr| {
+                let checks = pr
+                    .commits
+                    .nodes
+                    .last()
+                    .and_then(|node| node.commit.status_check_rollup.as_ref())
+                    .map(|rollup| match rollup.state.as_str() {
+                        "EXPECTED" => "pending".to_string(),
+                        state => state.to_lowercase(),
+                    });
+                let info = PrInfo {
+                    number: pr.number,
+                    state: title_case(&pr.state),
+                    is_draft: pr.is_draft,
+                    base: pr.base_ref_name,
+                    checks_state: checks.as_deref().and_then(checks_state).map(String::from),
+                };
+                let checks = PrInfoWithChecks {
+                    info,
+                    mergeable: pr.mergeable.map(|m| m.to_lowercase()),
+                    checks,
+                };
+                (pr.number, checks)
+            })
+            .collect())
+    }
+
     /// Check if a PR is already merged
     pub async fn is_pr_merged(&self, pr_number: u64) -> Result<bool> {
         let pr = self
@@ -711,16 +998,20 @@ impl GitHubClient {
 
     /// List all issue comments (conversation comments) on a PR
     pub async fn list_issue_comments(&self, pr_number: u64) -> Result<Vec<IssueComment>> {
-        let comments = self
+        let first = self
             .octocrab
             .issues(&self.owner, &self.repo)
             .list_comments(pr_number)
+            .per_page(100u8)
             .send()
             .await
             .context("Failed to list issue comments")?;
+        let comments = self
+            .collect_pages(first, MAX_PAGINATED_ITEMS)
+            .await
+            .context("Failed to list issue comments")?;
 
         Ok(comments
-            .items
             .into_iter()
             .map(|c| IssueComment {
                 id: c.id.into_inner(),
@@ -734,7 +1025,7 @@ impl GitHubClient {
     /// List all review comments (inline code comments) on a PR
     pub async fn list_review_comments(&self, pr_number: u64) -> Result<Vec<ReviewComment>> {
         let url = format!(
-            "/repos/{}/{}/pulls/{}/comments",
+            "/repos/{}/{}/pulls/{}/comments?per_page=100",
             self.owner, self.repo, pr_number
         );
 
@@ -756,8 +1047,7 @@ impl GitHubClient {
         }
 
         let comments: Vec<ApiReviewComment> = self
-            .octocrab
-            .get(&url, None::<&()>)
+            .get_all_pages(&url, MAX_PAGINATED_ITEMS)
             .await
             .context("Failed to list review comments")?;
 
@@ -800,6 +1090,26 @@ impl GitHubClient {
     }
 }
 
+/// Collapse a CI state ("success", "failure", "error", "pending", ...) to pass/fail/pending
+pub fn checks_state(ci_state: &str) -> Option<&'static str> {
+    match ci_state.to_lowercase().as_str() {
+        "success" => Some("pass"),
+        "failure" | "error" => Some("fail"),
+        "pending" | "expected" => Some("pending"),
+        _ => None,
+    }
+}
+
+/// GraphQL enum value (`OPEN`) in the REST client's spelling (`Open`)
+fn title_case(value: &str) -> String {
+    let lower = value.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
 /// PR info for stack comment generation
 #[derive(Debug, Clone)]
 pub struct StackPrInfo {
@@ -838,7 +1148,7 @@ pub fn generate_stack_comment(
 
     lines.push("".to_string());
     lines.push(
-        "This comment was autogenerated by [stax](https://github.com/cesarferreira/stax)"
+        "This stack list was autogenerated by [stax](https://github.com/cesarferreira/stax)"
             .to_string(),
     );
 
@@ -1159,6 +1469,7 @@ mod tests {
             repo: "repo".to_string(),
             base_url: "https://github.com".to_string(),
             api_base_url: Some("https://api.github.com".to_string()),
+            provider: crate::provider::ProviderKind::GitHub,
         };
 
         let prs = vec![StackPrInfo {
@@ -1183,6 +1494,7 @@ mod tests {
             repo: "repo".to_string(),
             base_url: "https://github.com".to_string(),
             api_base_url: Some("https://api.github.com".to_string()),
+            provider: crate::provider::ProviderKind::GitHub,
         };
 
         let prs = vec![
@@ -1217,6 +1529,7 @@ mod tests {
             repo: "repo".to_string(),
             base_url: "https://github.com".to_string(),
             api_base_url: Some("https://api.github.com".to_string()),
+            provider: crate::provider::ProviderKind::GitHub,
         };
 
         let prs = vec![
@@ -1243,6 +1556,7 @@ mod tests {
             state: "Open".to_string(),
             is_draft: false,
             base: "main".to_string(),
+            checks_state: None,
         };
         let debug_str = format!("{:?}", pr);
         assert!(debug_str.contains("42"));
@@ -1305,6 +1619,7 @@ mod tests {
             .base_uri(server.uri())
             .unwrap()
             .personal_token("test-token".to_string())
+            .add_retry_config(octocrab::service::middleware::retry::RetryConfig::None)
             .build()
             .unwrap();
 
@@ -1381,6 +1696,136 @@ mod tests {
         assert!(!pr.info.is_draft);
     }
 
+    #[tokio::test]
+    async fn test_close_pr_sends_closed_state() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test-owner/test-repo/pulls/11"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({ "state": "closed" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "url": "https://api.github.com/repos/test-owner/test-repo/pulls/11",
+                "id": 11,
+                "number": 11,
+                "state": "closed",
+                "head": { "ref": "feature-a", "sha": "aaaa" },
+                "base": { "ref": "main", "sha": "bbbb" }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        client.close_pr(11).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reopen_pr_sends_open_state() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test-owner/test-repo/pulls/11"))
+            .and(wiremock::matchers::body_partial_json(
+                serde_json::json!({ "state": "open" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "url": "https://api.github.com/repos/test-owner/test-repo/pulls/11",
+                "id": 11,
+                "number": 11,
+                "state": "open",
+                "head": { "ref": "feature-a", "sha": "aaaa" },
+                "base": { "ref": "main", "sha": "bbbb" }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        client.reopen_pr(11).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_review_comments_follows_link_pages() {
+        let mock_server = MockServer::start().await;
+        let comments_path = "/repos/test-owner/test-repo/pulls/7/comments";
+
+        let comment = |id: u64| {
+            serde_json::json!({
+                "id": id,
+                "body": format!("comment {}", id),
+                "user": { "login": "reviewer" },
+                "path": "src/lib.rs",
+                "line": 1,
+                "created_at": "2026-01-01T00:00:00Z"
+            })
+        };
+
+        // Mounted first so it wins over the unfiltered first-page mock
+        Mock::given(method("GET"))
+            .and(path(comments_path))
+            .and(wiremock::matchers::query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([comment(3)])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(comments_path))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "link",
+                        format!(
+                            "<{}{}?per_page=100&page=2>; rel=\"next\"",
+                            mock_server.uri(),
+                            comments_path
+                        )
+                        .as_str(),
+                    )
+                    .set_body_json(serde_json::json!([comment(1), comment(2)])),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let comments = client.list_review_comments(7).await.unwrap();
+
+        let ids: Vec<u64> = comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pages_respects_limit() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/search/issues"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "link",
+                        format!("<{}/search/issues?page=2>; rel=\"next\"", mock_server.uri())
+                            .as_str(),
+                    )
+                    .set_body_json(serde_json::json!({
+                        "total_count": 4,
+                        "items": [1, 2, 3]
+                    })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let items: Vec<u64> = client.get_all_pages("/search/issues", 2).await.unwrap();
+
+        // The cap is reached on the first page, so the next page is never requested
+        assert_eq!(items, vec![1, 2]);
+    }
+
     // Note: The find_pr function now validates that the returned PR's head branch
     // matches the requested branch name. This is critical because the GitHub API's
     // head filter can fail silently (e.g., with long branch names or URL encoding
@@ -1402,4 +1847,237 @@ mod tests {
     // - Should only return OPEN PRs
     // - Should validate head branch matches before returning
     // - Should return None if no matching open PR exists
+
+    #[tokio::test]
+    async fn test_get_pr_retries_server_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/12"))
+            .respond_with(ResponseTemplate::new(502))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/12"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "url": "https://api.github.com/repos/test-owner/test-repo/pulls/12",
+                "id": 12,
+                "number": 12,
+                "state": "open",
+                "head": { "ref": "feature-b", "sha": "cccc" },
+                "base": { "ref": "main", "sha": "bbbb" }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let pr = client.get_pr(12).await.unwrap();
+        assert_eq!(pr.number, 12);
+        assert_eq!(pr.base, "main");
+    }
+
+    #[tokio::test]
+    async fn test_get_pr_reports_attempts_when_retries_run_out() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/13"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let err = client.get_pr(13).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("after 4 attempts"));
+    }
+
+    #[tokio::test]
+    async fn test_create_pr_is_not_retried() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/repos/test-owner/test-repo/pulls"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let err = client
+            .create_pr("feature", "main", "Title", "Body", false)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to create PR"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prs_batch_skips_missing_prs() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(wiremock::matchers::body_string_contains(
+                "pr21: pullRequest(number: 21)",
+            ))
+            .and(wiremock::matchers::body_string_contains(
+                "pr22: pullRequest(number: 22)",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "repository": {
+                        "pr21": {
+                            "number": 21,
+                            "state": "OPEN",
+                            "isDraft": true,
+                            "baseRefName": "main",
+                            "mergeable": "MERGEABLE",
+                            "commits": { "nodes": [
+                                { "commit": { "statusCheckRollup": { "state": "FAILURE" } } }
+                            ] }
+                        },
+                        "pr22": null
+                    }
+                },
+                "errors": [
+                    { "message": "Could not resolve to a PullRequest with the number of 22." }
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let prs = client.fetch_prs_batch(&[22, 21, 21]).await.unwrap();
+
+        assert_eq!(prs.len(), 1);
+        let pr = &prs[&21];
+        assert_eq!(pr.info.state, "Open");
+        assert!(pr.info.is_draft);
+        assert_eq!(pr.info.base, "main");
+        assert_eq!(pr.mergeable.as_deref(), Some("mergeable"));
+        assert_eq!(pr.checks.as_deref(), Some("failure"));
+        assert_eq!(pr.info.checks_state.as_deref(), Some("fail"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prs_batch_without_numbers_makes_no_request() {
+        let mock_server = MockServer::start().await;
+        let client = create_test_client(&mock_server).await;
+
+        assert!(client.fetch_prs_batch(&[]).await.unwrap().is_empty());
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_pr_with_checks_reads_head_commit_ci() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/14"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "url": "https://api.github.com/repos/test-owner/test-repo/pulls/14",
+                "id": 14,
+                "number": 14,
+                "state": "open",
+                "head": { "ref": "feature-c", "sha": "dddd" },
+                "base": { "ref": "main", "sha": "bbbb" }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/commits/dddd/check-runs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 1,
+                "check_runs": [{ "status": "in_progress", "conclusion": null }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let pr = client.get_pr_with_checks(14).await.unwrap();
+        assert_eq!(pr.info.number, 14);
+        assert_eq!(pr.checks.as_deref(), Some("pending"));
+        assert_eq!(pr.info.checks_state.as_deref(), Some("pending"));
+    }
+
+    #[test]
+    fn test_checks_state_collapses_ci_states() {
+        assert_eq!(checks_state("success"), Some("pass"));
+        assert_eq!(checks_state("FAILURE"), Some("fail"));
+        assert_eq!(checks_state("error"), Some("fail"));
+        assert_eq!(checks_state("pending"), Some("pending"));
+        assert_eq!(checks_state("unknown"), None);
+    }
+
+    fn mock_pr_with_node_id(number: u64) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "url": format!("https://api.github.com/repos/test-owner/test-repo/pulls/{}", number),
+            "id": number,
+            "node_id": format!("PR_node{}", number),
+            "number": number,
+            "state": "open",
+            "head": { "ref": "feature-a", "sha": "aaaa" },
+            "base": { "ref": "main", "sha": "bbbb" }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_enable_auto_merge_sends_node_id_and_method() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/15"))
+            .respond_with(mock_pr_with_node_id(15))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(wiremock::matchers::body_partial_json(serde_json::json!({
+                "variables": { "id": "PR_node15", "method": "REBASE" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "enablePullRequestAutoMerge": { "clientMutationId": null } }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        client
+            .enable_auto_merge(15, MergeMethod::Rebase)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_enable_auto_merge_explains_disabled_repo_setting() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/16"))
+            .respond_with(mock_pr_with_node_id(16))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "enablePullRequestAutoMerge": null },
+                "errors": [
+                    { "message": "Pull request Auto merge is not allowed for this repository" }
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_test_client(&mock_server).await;
+        let err = client
+            .enable_auto_merge(16, MergeMethod::Squash)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Allow auto-merge"));
+    }
 }
@@ -0,0 +1,273 @@
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HEADER_START: &str = "<!-- stax-pr-header -->";
+const HEADER_END: &str = "<!-- /stax-pr-header -->";
+const FOOTER_START: &str = "<!-- stax-pr-footer -->";
+const FOOTER_END: &str = "<!-- /stax-pr-footer -->";
+const LINKS_START: &str = "<!-- stax-issue-links -->";
+const LINKS_END: &str = "<!-- /stax-issue-links -->";
+const STACK_START: &str = "<!-- stax-stack -->";
+const STACK_END: &str = "<!-- /stax-stack -->";
+
+/// GitHub keywords that close an issue when the PR merges
+const CLOSING_KEYWORDS: &[&str] = &[
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+/// Header/footer text from `remote.pr_body_header` and `remote.pr_body_footer`,
+/// wrapped in markers so a later submit replaces it instead of appending again
+#[derive(Debug, Default, Clone)]
+pub struct PrBodyBoilerplate {
+    header: Option<String>,
+    footer: Option<String>,
+}
+
+impl PrBodyBoilerplate {
+    /// Resolve the configured values; each may be literal text or a path to a file
+    /// (relative paths are resolved against the repository root)
+    pub fn from_config(config: &Config, workdir: &Path) -> Result<Self> {
+        Ok(Self {
+            header: resolve(config.remote.pr_body_header.as_deref(), workdir)?,
+            footer: resolve(config.remote.pr_body_footer.as_deref(), workdir)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.header.is_none() && self.footer.is_none()
+    }
+
+    /// Wrap `body` with the header and footer, replacing any previously injected copies
+    pub fn apply(&self, body: &str) -> String {
+        if self.is_empty() {
+            return body.to_string();
+        }
+
+        let body = strip_section(body, HEADER_START, HEADER_END);
+        let body = strip_section(&body, FOOTER_START, FOOTER_END);
+
+        let mut parts = Vec::new();
+        if let Some(header) = &self.header {
+            parts.push(format!("{}\n{}\n{}", HEADER_START, header, HEADER_END));
+        }
+        if !body.trim().is_empty() {
+            parts.push(body.trim().to_string());
+        }
+        if let Some(footer) = &self.footer {
+            parts.push(format!("{}\n{}\n{}", FOOTER_START, footer, FOOTER_END));
+        }
+        parts.join("\n\n")
+    }
+}
+
+/// Normalize an issue argument (`123`, `#123`, or `owner/repo#123`) to a GitHub reference
+pub fn issue_reference(issue: &str) -> Result<String> {
+    let issue = issue.trim();
+    let (repo, number) = match issue.rsplit_once('#') {
+        Some((repo, number)) => (repo, number),
+        None => ("", issue),
+    };
+    let valid_repo = repo.is_empty() || repo.split('/').filter(|p| !p.is_empty()).count() == 2;
+    if !valid_repo || number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        bail!(
+            "Invalid issue '{}'. Use a number like 123, #123, or owner/repo#123.",
+            issue
+        );
+    }
+    Ok(format!("{}#{}", repo, number))
+}
+
+/// Add a `Closes <reference>` line to the managed issue-links block of `body`.
+/// Returns None when the body already closes that issue.
+pub fn add_issue_link(body: &str, reference: &str) -> Option<String> {
+    let already_linked = body.lines().any(|line| {
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (Some(keyword), Some(target), None) => {
+                let keyword = keyword.trim_end_matches(':').to_lowercase();
+                CLOSING_KEYWORDS.contains(&keyword.as_str())
+                    && target.eq_ignore_ascii_case(reference)
+            }
+            _ => false,
+        }
+    });
+    if already_linked {
+        return None;
+    }
+
+    let line = format!("Closes {}", reference);
+    let mut body = body.to_string();
+    if let Some(end) = body.find(LINKS_END) {
+        body.insert_str(end, &format!("{}\n", line));
+        return Some(body);
+    }
+
+    let block = format!("{}\n{}\n{}", LINKS_START, line, LINKS_END);
+    // Keep the configured footer last
+    match body.find(FOOTER_START) {
+        Some(footer) => {
+            body.insert_str(footer, &format!("{}\n\n", block));
+            Some(body)
+        }
+        None if body.trim().is_empty() => Some(block),
+        None => Some(format!("{}\n\n{}", body.trim_end(), block)),
+    }
+}
+
+/// Put `stack` in the managed stack block of `body`, replacing any previous copy
+pub fn with_stack_section(body: &str, stack: &str) -> String {
+    let body = strip_section(body, STACK_START, STACK_END);
+    let block = format!("{}\n{}\n{}", STACK_START, stack.trim(), STACK_END);
+
+    // Keep the configured footer last
+    let (main, footer) = match body.find(FOOTER_START) {
+        Some(footer) => body.split_at(footer),
+        None => (body.as_str(), ""),
+    };
+    let mut parts = Vec::new();
+    if !main.trim().is_empty() {
+        parts.push(main.trim());
+    }
+    parts.push(&block);
+    if !footer.is_empty() {
+        parts.push(footer.trim());
+    }
+    parts.join("\n\n")
+}
+
+fn resolve(value: Option<&str>, workdir: &Path) -> Result<Option<String>> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+
+    let path = match value.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+        None => Some(PathBuf::from(value)),
+    }
+    .map(|path| workdir.join(path));
+
+    match path.filter(|path| path.is_file()) {
+        Some(path) => {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            Ok(Some(content.trim().to_string()).filter(|c| !c.is_empty()))
+        }
+        None => Ok(Some(value.to_string())),
+    }
+}
+
+/// Remove every `start ... end` block from `body`
+fn strip_section(body: &str, start: &str, end: &str) -> String {
+    let mut body = body.to_string();
+    while let Some(from) = body.find(start) {
+        let Some(to) = body[from..].find(end).map(|i| from + i + end.len()) else {
+            break;
+        };
+        body.replace_range(from..to, "");
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn boilerplate(header: Option<&str>, footer: Option<&str>) -> PrBodyBoilerplate {
+        PrBodyBoilerplate {
+            header: header.map(String::from),
+            footer: footer.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_apply_wraps_body_and_is_idempotent() {
+        let boilerplate = boilerplate(Some("Part of a stack"), Some("Compliance: OK"));
+
+        let once = boilerplate.apply("## Summary\nChanges");
+        assert!(once.starts_with(HEADER_START));
+        assert!(once.contains("## Summary\nChanges"));
+        assert!(once.trim_end().ends_with(FOOTER_END));
+
+        assert_eq!(boilerplate.apply(&once), once);
+        assert_eq!(once.matches("Compliance: OK").count(), 1);
+    }
+
+    #[test]
+    fn test_apply_replaces_stale_footer() {
+        let old = boilerplate(None, Some("Old footer")).apply("Body");
+        let new = boilerplate(None, Some("New footer")).apply(&old);
+
+        assert!(new.starts_with("Body"));
+        assert!(new.contains("New footer"));
+        assert!(!new.contains("Old footer"));
+    }
+
+    #[test]
+    fn test_issue_reference_formats() {
+        assert_eq!(issue_reference("123").unwrap(), "#123");
+        assert_eq!(issue_reference("#123").unwrap(), "#123");
+        assert_eq!(issue_reference("octo/repo#7").unwrap(), "octo/repo#7");
+        assert!(issue_reference("abc").is_err());
+        assert!(issue_reference("repo#7").is_err());
+    }
+
+    #[test]
+    fn test_add_issue_link_is_idempotent_and_keeps_footer_last() {
+        let body = boilerplate(None, Some("Compliance: OK")).apply("Body");
+
+        let linked = add_issue_link(&body, "#12").unwrap();
+        let linked = add_issue_link(&linked, "#34").unwrap();
+        assert!(add_issue_link(&linked, "#12").is_none());
+        assert_eq!(linked.matches(LINKS_START).count(), 1);
+
+        let links = linked.find("Closes #12").unwrap();
+        assert!(links < linked.find("Closes #34").unwrap());
+        assert!(linked.find("Compliance: OK").unwrap() > links);
+        assert!(linked.starts_with("Body"));
+    }
+
+    #[test]
+    fn test_add_issue_link_respects_handwritten_keywords() {
+        assert!(add_issue_link("Fixes: #9\nMore text", "#9").is_none());
+        assert!(add_issue_link("Mentions #9 in passing", "#9").is_some());
+    }
+
+    #[test]
+    fn test_with_stack_section_replaces_block_and_keeps_footer_last() {
+        let body = boilerplate(None, Some("Compliance: OK")).apply("Body");
+
+        let once = with_stack_section(&body, "* PR #1");
+        let twice = with_stack_section(&once, "* PR #1\n* PR #2");
+        assert_eq!(twice.matches(STACK_START).count(), 1);
+        assert!(!twice.contains("* PR #1\n<!-- /stax-stack"));
+        assert!(twice.starts_with("Body"));
+        assert!(twice.find("PR #2").unwrap() < twice.find("Compliance: OK").unwrap());
+
+        assert_eq!(with_stack_section(&twice, "* PR #1\n* PR #2"), twice);
+        assert_eq!(
+            with_stack_section("", "* PR #1"),
+            format!("{}\n* PR #1\n{}", STACK_START, STACK_END)
+        );
+    }
+
+    #[test]
+    fn test_resolve_reads_file_or_uses_literal() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("FOOTER.md"), "From file\n").unwrap();
+
+        assert_eq!(
+            resolve(Some("FOOTER.md"), dir.path()).unwrap().as_deref(),
+            Some("From file")
+        );
+        assert_eq!(
+            resolve(Some("Literal footer"), dir.path())
+                .unwrap()
+                .as_deref(),
+            Some("Literal footer")
+        );
+        assert_eq!(resolve(Some("  "), dir.path()).unwrap(), None);
+    }
+}
@@ -1,7 +1,7 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use dialoguer::{theme::ColorfulTheme, FuzzySelect};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Represents a discovered PR template
 #[derive(Debug, Clone)]
@@ -25,46 +25,10 @@ pub struct PrTemplate {
 /// 5. docs/pull_request_template.md
 #[allow(dead_code)] // Will be used in future tasks
 pub fn discover_pr_templates(workdir: &Path) -> Result<Vec<PrTemplate>> {
-    let mut templates = Vec::new();
-
     // Check directory first (multiple templates)
-    let template_dir = workdir.join(".github/PULL_REQUEST_TEMPLATE");
-    if template_dir.is_dir() {
-        let mut entries: Vec<_> = fs::read_dir(&template_dir)
-            .context("Failed to read PR template directory")?
-            .filter_map(|entry| entry.ok())
-            .filter(|entry| {
-                entry
-                    .path()
-                    .extension()
-                    .map(|ext| ext == "md")
-                    .unwrap_or(false)
-            })
-            .collect();
-
-        entries.sort_by_key(|entry| entry.path());
-
-        for entry in entries {
-            let path = entry.path();
-            let name = path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("template")
-                .to_string();
-
-            let content = fs::read_to_string(&path)
-                .context(format!("Failed to read PR template: {}", path.display()))?;
-
-            templates.push(PrTemplate {
-                name,
-                path,
-                content,
-            });
-        }
-
-        if !templates.is_empty() {
-            return Ok(templates);
-        }
+    let templates = templates_in_dir(&workdir.join(".github/PULL_REQUEST_TEMPLATE"))?;
+    if !templates.is_empty() {
+        return Ok(templates);
     }
 
     // Check single template locations
@@ -78,18 +42,71 @@ pub fn discover_pr_templates(workdir: &Path) -> Result<Vec<PrTemplate>> {
     for candidate in &single_template_candidates {
         let path = workdir.join(candidate);
         if path.is_file() {
-            let content = fs::read_to_string(&path)
-                .context(format!("Failed to read PR template: {}", path.display()))?;
-            templates.push(PrTemplate {
-                name: "Default".to_string(),
-                path,
-                content,
-            });
-            return Ok(templates);
+            return Ok(vec![read_template("Default".to_string(), path)?]);
         }
     }
 
-    Ok(templates)
+    Ok(Vec::new())
+}
+
+/// Discover PR templates, honoring a `--template-dir` override
+///
+/// The override may be a directory (every .md file becomes a template) or a
+/// single file (named "Default"). Relative paths resolve against `workdir`.
+/// Without an override this is the standard-location search.
+pub fn discover_pr_templates_with(
+    workdir: &Path,
+    template_dir: Option<&Path>,
+) -> Result<Vec<PrTemplate>> {
+    let Some(dir) = template_dir else {
+        return discover_pr_templates(workdir);
+    };
+
+    let path = workdir.join(dir);
+    if path.is_file() {
+        Ok(vec![read_template("Default".to_string(), path)?])
+    } else if path.is_dir() {
+        templates_in_dir(&path)
+    } else {
+        bail!("PR template path '{}' does not exist", path.display())
+    }
+}
+
+/// Every .md file in `dir`, sorted by path; empty when `dir` is missing
+fn templates_in_dir(dir: &Path) -> Result<Vec<PrTemplate>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .context("Failed to read PR template directory")?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map(|ext| ext == "md").unwrap_or(false))
+        .collect();
+    entries.sort();
+
+    entries
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("template")
+                .to_string();
+            read_template(name, path)
+        })
+        .collect()
+}
+
+fn read_template(name: String, path: PathBuf) -> Result<PrTemplate> {
+    let content = fs::read_to_string(&path)
+        .context(format!("Failed to read PR template: {}", path.display()))?;
+    Ok(PrTemplate {
+        name,
+        path,
+        content,
+    })
 }
 
 /// Build selection options list: ["No template", ...template names sorted]
@@ -225,4 +242,36 @@ mod tests {
         assert!(selected.is_some());
         assert_eq!(selected.unwrap().name, "Default");
     }
+
+    #[test]
+    fn test_template_dir_override_replaces_standard_search() {
+        let dir = TempDir::new().unwrap();
+        let github_dir = dir.path().join(".github");
+        fs::create_dir(&github_dir).unwrap();
+        fs::write(github_dir.join("PULL_REQUEST_TEMPLATE.md"), "# Standard").unwrap();
+        let custom = dir.path().join("tools/pr-templates");
+        fs::create_dir_all(&custom).unwrap();
+        fs::write(custom.join("release.md"), "# Release").unwrap();
+        fs::write(custom.join("notes.txt"), "ignored").unwrap();
+
+        let templates =
+            discover_pr_templates_with(dir.path(), Some(Path::new("tools/pr-templates"))).unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].name, "release");
+
+        let templates = discover_pr_templates_with(dir.path(), None).unwrap();
+        assert_eq!(templates[0].name, "Default");
+    }
+
+    #[test]
+    fn test_template_dir_override_accepts_file_and_rejects_missing_path() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("PR.md"), "# Custom").unwrap();
+
+        let templates = discover_pr_templates_with(dir.path(), Some(Path::new("PR.md"))).unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].name, "Default");
+
+        assert!(discover_pr_templates_with(dir.path(), Some(Path::new("missing"))).is_err());
+    }
 }
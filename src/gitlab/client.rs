@@ -0,0 +1,471 @@
+use anyhow::{bail, Context, Result};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::config::Config;
+use crate::github::pr::{MergeMethod, PrInfo, PrInfoWithHead};
+use crate::github::pr_body;
+
+/// Merge requests fetched per page when listing
+const PER_PAGE: usize = 100;
+
+/// Upper bound on merge requests collected from one list call
+const MAX_LISTED_MRS: usize = 1000;
+
+/// Merge request operations against the GitLab REST API (v4)
+pub struct GitLabClient {
+    http: reqwest::Client,
+    /// e.g. `https://gitlab.com/api/v4`
+    api_base_url: String,
+    /// URL-encoded `namespace/repo`, as GitLab expects in `/projects/:id`
+    project: String,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: u64,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    source_branch: String,
+    target_branch: String,
+    description: Option<String>,
+}
+
+impl MergeRequest {
+    fn info(&self) -> PrInfo {
+        PrInfo {
+            number: self.iid,
+            state: mr_state(&self.state).to_string(),
+            is_draft: self.draft,
+            base: self.target_branch.clone(),
+            checks_state: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    id: u64,
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    last_pipeline: Option<Pipeline>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pipeline {
+    status: String,
+}
+
+impl GitLabClient {
+    /// Create a client for `namespace/repo`, authenticating with `STAX_GITLAB_TOKEN`
+    /// or `GITLAB_TOKEN`
+    pub fn new(namespace: &str, repo: &str, api_base_url: &str) -> Result<Self> {
+        let token = Config::gitlab_token().context(
+            "GitLab auth not configured.\n\
+             Set STAX_GITLAB_TOKEN (or GITLAB_TOKEN) to a personal access token with the `api` scope.",
+        )?;
+        Ok(Self::with_token(namespace, repo, api_base_url, &token))
+    }
+
+    pub fn with_token(namespace: &str, repo: &str, api_base_url: &str, token: &str) -> Self {
+        let project = format!("{}/{}", namespace, repo).replace('/', "%2F");
+        Self {
+            http: reqwest::Client::new(),
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
+            project,
+            token: token.to_string(),
+        }
+    }
+
+    /// Request to `route` under `/projects/:id`
+    fn project_request(&self, method: Method, route: &str) -> RequestBuilder {
+        let url = format!("{}/projects/{}{}", self.api_base_url, self.project, route);
+        self.http.request(method, url)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request
+            .header("PRIVATE-TOKEN", &self.token)
+            .send()
+            .await
+            .context("GitLab request failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("GitLab API returned {}: {}", status, body.trim());
+        }
+        response
+            .json()
+            .await
+            .context("Unexpected response from GitLab")
+    }
+
+    async fn get_mr(&self, iid: u64) -> Result<MergeRequest> {
+        let request = self.project_request(Method::GET, &format!("/merge_requests/{}", iid));
+        self.send(request)
+            .await
+            .context("Failed to get merge request")
+    }
+
+    async fn update_mr(&self, iid: u64, fields: serde_json::Value) -> Result<MergeRequest> {
+        let request = self
+            .project_request(Method::PUT, &format!("/merge_requests/{}", iid))
+            .json(&fields);
+        self.send(request).await
+    }
+
+    async fn user_ids(&self, usernames: &[String]) -> Result<Vec<u64>> {
+        let mut ids = Vec::new();
+        for username in usernames {
+            if username.contains('/') {
+                bail!(
+                    "GitLab can't request reviews from groups ('{}'); list users instead.",
+                    username
+                );
+            }
+            let request = self
+                .http
+                .get(format!("{}/users", self.api_base_url))
+                .query(&[("username", username.trim_start_matches('@'))]);
+            let users: Vec<User> = self.send(request).await?;
+            let user = users
+                .first()
+                .with_context(|| format!("GitLab user '{}' not found", username))?;
+            ids.push(user.id);
+        }
+        Ok(ids)
+    }
+
+    /// Open a merge request; drafts get GitLab's `Draft:` title prefix
+    pub async fn create_pr(
+        &self,
+        branch: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PrInfo> {
+        let title = if draft {
+            format!("Draft: {}", title)
+        } else {
+            title.to_string()
+        };
+        let request =
+            self.project_request(Method::POST, "/merge_requests")
+                .json(&serde_json::json!({
+                    "source_branch": branch,
+                    "target_branch": base,
+                    "title": title,
+                    "description": body,
+                }));
+        let mr: MergeRequest = self
+            .send(request)
+            .await
+            .context("Failed to create merge request")?;
+        Ok(mr.info())
+    }
+
+    pub async fn get_pr(&self, iid: u64) -> Result<PrInfo> {
+        Ok(self.get_mr(iid).await?.info())
+    }
+
+    pub async fn get_pr_with_head(&self, iid: u64) -> Result<PrInfoWithHead> {
+        let mr = self.get_mr(iid).await?;
+        Ok(PrInfoWithHead {
+            info: mr.info(),
+            head: mr.source_branch,
+            head_label: None,
+        })
+    }
+
+    /// Find the open merge request whose source is `branch`
+    pub async fn find_pr(&self, branch: &str) -> Result<Option<PrInfo>> {
+        let request = self
+            .project_request(Method::GET, "/merge_requests")
+            .query(&[("state", "opened"), ("source_branch", branch)]);
+        let mrs: Vec<MergeRequest> = self
+            .send(request)
+            .await
+            .context("Failed to find merge request")?;
+        Ok(mrs.first().map(MergeRequest::info))
+    }
+
+    /// All open merge requests, keyed by source branch
+    pub async fn list_open_prs_by_head(&self) -> Result<HashMap<String, PrInfoWithHead>> {
+        let mut results = HashMap::new();
+        for page in 1.. {
+            let request = self
+                .project_request(Method::GET, "/merge_requests")
+                .query(&[
+                    ("state", "opened".to_string()),
+                    ("per_page", PER_PAGE.to_string()),
+                    ("page", page.to_string()),
+                ]);
+            let mrs: Vec<MergeRequest> = self
+                .send(request)
+                .await
+                .context("Failed to list merge requests")?;
+            let last_page = mrs.len() < PER_PAGE;
+            for mr in mrs {
+                let pr = PrInfoWithHead {
+                    info: mr.info(),
+                    head: mr.source_branch.clone(),
+                    head_label: None,
+                };
+                results.entry(mr.source_branch).or_insert(pr);
+            }
+            if last_page || results.len() >= MAX_LISTED_MRS {
+                break;
+            }
+        }
+        Ok(results)
+    }
+
+    pub async fn update_pr_base(&self, iid: u64, new_base: &str) -> Result<()> {
+        self.update_mr(iid, serde_json::json!({ "target_branch": new_base }))
+            .await
+            .context("Failed to update merge request target")?;
+        Ok(())
+    }
+
+    pub async fn get_pr_body(&self, iid: u64) -> Result<String> {
+        Ok(self.get_mr(iid).await?.description.unwrap_or_default())
+    }
+
+    pub async fn update_pr_body(&self, iid: u64, body: &str) -> Result<()> {
+        self.update_mr(iid, serde_json::json!({ "description": body }))
+            .await
+            .context("Failed to update merge request description")?;
+        Ok(())
+    }
+
+    pub async fn update_pr_title(&self, iid: u64, title: &str) -> Result<()> {
+        self.update_mr(iid, serde_json::json!({ "title": title }))
+            .await
+            .context("Failed to update merge request title")?;
+        Ok(())
+    }
+
+    pub async fn close_pr(&self, iid: u64) -> Result<()> {
+        self.update_mr(iid, serde_json::json!({ "state_event": "close" }))
+            .await
+            .context("Failed to close merge request")?;
+        Ok(())
+    }
+
+    /// Put the stack list in the merge request description
+    pub async fn update_stack_comment(&self, iid: u64, stack_comment: &str) -> Result<()> {
+        let current = self.get_pr_body(iid).await?;
+        let body = pr_body::with_stack_section(&current, stack_comment);
+        if body != current {
+            self.update_pr_body(iid, &body).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn request_reviewers(&self, iid: u64, reviewers: &[String]) -> Result<()> {
+        if reviewers.is_empty() {
+            return Ok(());
+        }
+        let ids = self.user_ids(reviewers).await?;
+        self.update_mr(iid, serde_json::json!({ "reviewer_ids": ids }))
+            .await
+            .context("Failed to request reviewers")?;
+        Ok(())
+    }
+
+    pub async fn add_labels(&self, iid: u64, labels: &[String]) -> Result<()> {
+        if labels.is_empty() {
+            return Ok(());
+        }
+        self.update_mr(iid, serde_json::json!({ "add_labels": labels.join(",") }))
+            .await
+            .context("Failed to add labels")?;
+        Ok(())
+    }
+
+    pub async fn add_assignees(&self, iid: u64, assignees: &[String]) -> Result<()> {
+        if assignees.is_empty() {
+            return Ok(());
+        }
+        let ids = self.user_ids(assignees).await?;
+        self.update_mr(iid, serde_json::json!({ "assignee_ids": ids }))
+            .await
+            .context("Failed to add assignees")?;
+        Ok(())
+    }
+
+    /// Merge once the pipeline succeeds
+    pub async fn enable_auto_merge(&self, iid: u64, method: MergeMethod) -> Result<()> {
+        let squash = match method {
+            MergeMethod::Squash => true,
+            MergeMethod::Merge => false,
+            MergeMethod::Rebase => {
+                bail!("GitLab auto-merge supports merge and squash, not rebase.")
+            }
+        };
+        let request = self
+            .project_request(Method::PUT, &format!("/merge_requests/{}/merge", iid))
+            .json(&serde_json::json!({
+                "merge_when_pipeline_succeeds": true,
+                "squash": squash,
+            }));
+        let _: MergeRequest = self
+            .send(request)
+            .await
+            .context("Failed to enable auto-merge")?;
+        Ok(())
+    }
+
+    /// Username of the account the token authenticates as
+    pub async fn current_user(&self) -> Result<String> {
+        let request = self.http.get(format!("{}/user", self.api_base_url));
+        let user: User = self
+            .send(request)
+            .await
+            .context("Failed to look up GitLab user")?;
+        Ok(user.username)
+    }
+
+    /// State of the latest pipeline for a commit, in GitHub's terms
+    pub async fn combined_status_state(&self, commit_sha: &str) -> Result<Option<String>> {
+        let request =
+            self.project_request(Method::GET, &format!("/repository/commits/{}", commit_sha));
+        let commit: Commit = self.send(request).await?;
+        Ok(commit
+            .last_pipeline
+            .and_then(|pipeline| pipeline_state(&pipeline.status))
+            .map(String::from))
+    }
+}
+
+/// GitLab merge request state in the spelling stax stores for GitHub PRs
+fn mr_state(state: &str) -> &str {
+    match state {
+        "opened" => "Open",
+        "closed" | "locked" => "Closed",
+        "merged" => "Merged",
+        other => other,
+    }
+}
+
+/// Map a pipeline status onto success/failure/pending
+fn pipeline_state(status: &str) -> Option<&'static str> {
+    match status {
+        "success" => Some("success"),
+        "failed" | "canceled" => Some("failure"),
+        "created" | "waiting_for_resource" | "preparing" | "pending" | "running" | "scheduled" => {
+            Some("pending")
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> GitLabClient {
+        GitLabClient::with_token("group/sub", "app", &server.uri(), "glpat-test")
+    }
+
+    fn mr_json(iid: u64, state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "iid": iid,
+            "state": state,
+            "draft": false,
+            "source_branch": "feature-a",
+            "target_branch": "main",
+            "description": "Old body"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_pr_posts_merge_request() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/projects/group%2Fsub%2Fapp/merge_requests"))
+            .and(header("PRIVATE-TOKEN", "glpat-test"))
+            .and(body_partial_json(serde_json::json!({
+                "source_branch": "feature-a",
+                "target_branch": "main",
+                "title": "Draft: Add feature",
+                "description": "Body"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(mr_json(7, "opened")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let pr = client(&server)
+            .create_pr("feature-a", "main", "Add feature", "Body", true)
+            .await
+            .unwrap();
+        assert_eq!(pr.number, 7);
+        assert_eq!(pr.state, "Open");
+        assert_eq!(pr.base, "main");
+    }
+
+    #[tokio::test]
+    async fn test_update_pr_body_puts_description() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/projects/group%2Fsub%2Fapp/merge_requests/7"))
+            .and(body_partial_json(
+                serde_json::json!({ "description": "New body" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mr_json(7, "opened")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client(&server).update_pr_body(7, "New body").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_find_pr_filters_by_source_branch() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/projects/group%2Fsub%2Fapp/merge_requests"))
+            .and(query_param("source_branch", "feature-a"))
+            .and(query_param("state", "opened"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([mr_json(9, "opened")])),
+            )
+            .mount(&server)
+            .await;
+
+        let pr = client(&server).find_pr("feature-a").await.unwrap().unwrap();
+        assert_eq!(pr.number, 9);
+    }
+
+    #[tokio::test]
+    async fn test_api_errors_include_status_and_message() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/projects/group%2Fsub%2Fapp/merge_requests/404"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(serde_json::json!({ "message": "404 Not found" })),
+            )
+            .mount(&server)
+            .await;
+
+        let err = client(&server).get_pr(404).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("404 Not found"));
+    }
+}
@@ -1,17 +1,24 @@
 //! stax library interface
 //!
-//! This module exposes internal functionality for integration testing.
-//! The main binary is in main.rs.
+//! [`api`] is the stable surface for embedding stax in other tools, and
+//! [`error::StaxError`] the typed failures it can report. The `github` module
+//! is exposed for integration testing. The main binary is in main.rs.
 
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
-// Internal modules needed by github module
+// Internal modules needed by the public modules
 mod cache;
 mod config;
 mod engine;
 mod git;
+mod gitlab;
+mod ops;
+mod provider;
 mod remote;
 
+pub mod api;
+pub mod error;
+
 // Expose github module for tests
 pub mod github;
@@ -3,16 +3,20 @@ mod ci;
 mod commands;
 mod config;
 mod engine;
+mod error;
 mod git;
 mod github;
+mod gitlab;
 mod ops;
+mod provider;
 mod remote;
 mod tui;
 mod update;
 
 use anyhow::Result;
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use config::Config;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "stax")]
@@ -21,6 +25,9 @@ use config::Config;
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+    /// Skip checking for new stax releases
+    #[arg(long, global = true)]
+    no_update_check: bool,
 }
 
 #[derive(Args, Clone)]
@@ -31,6 +38,9 @@ struct SubmitOptions {
     /// Only push, don't create/update PRs
     #[arg(long)]
     no_pr: bool,
+    /// Skip pushing; create/update PRs from existing remote branches
+    #[arg(long, conflicts_with = "no_pr")]
+    no_push: bool,
     /// Skip restack check and submit anyway
     #[arg(short, long)]
     force: bool,
@@ -61,12 +71,33 @@ struct SubmitOptions {
     /// Skip template selection (no template)
     #[arg(long)]
     no_template: bool,
+    /// Look for PR templates in this directory (or file) instead of the standard locations
+    #[arg(long, value_name = "PATH", conflicts_with = "no_template")]
+    template_dir: Option<PathBuf>,
     /// Always open editor for PR body
     #[arg(long)]
     edit: bool,
-    /// Generate PR body using AI (claude, codex, or gemini)
-    #[arg(long)]
+    /// Generate PR body using AI, filling in the PR template if one is selected
+    #[arg(long, visible_alias = "template-from-ai")]
     ai_body: bool,
+    /// Skip the hooks.pre_submit command and the remote.max_pr_lines size check
+    #[arg(long)]
+    no_verify: bool,
+    /// Ignore progress from an interrupted submit and start over
+    #[arg(long)]
+    restart: bool,
+    /// Also assign yourself (the authenticated GitHub user)
+    #[arg(long)]
+    assign_me: bool,
+    /// Only refresh the stack list on existing PRs (no push, no other PR updates)
+    #[arg(long, conflicts_with_all = ["no_pr", "no_push", "draft", "restart"])]
+    comment_only: bool,
+    /// Push and open PRs without recording them in stax branch metadata
+    #[arg(long)]
+    no_track_pr: bool,
+    /// Enable GitHub auto-merge on every non-draft PR (method from remote.merge_method)
+    #[arg(long, conflicts_with_all = ["no_pr", "draft", "comment_only"])]
+    auto_merge: bool,
 }
 
 #[derive(Subcommand)]
@@ -86,9 +117,30 @@ enum Commands {
         /// Compact output for scripts
         #[arg(long)]
         compact: bool,
+        /// One dense line per branch (same as `ui.compact = true`)
+        #[arg(long, conflicts_with_all = ["json", "compact"])]
+        dense: bool,
         /// Suppress extra output
         #[arg(long)]
         quiet: bool,
+        /// Show commits added since each branch was last submitted
+        #[arg(long)]
+        since_last_submit: bool,
+        /// Mark branches with local commits not yet pushed to their remote ref
+        #[arg(long)]
+        ahead_of_remote: bool,
+        /// Order sibling stacks: stack (current stack first), name, or recent
+        #[arg(long, default_value = "stack", value_name = "stack|name|recent")]
+        sort: String,
+        /// Fetch each PR's base branch and flag ones that differ from the stax parent
+        #[arg(long)]
+        check_pr_base: bool,
+        /// Fetch CI for each PR and show a pass/fail/pending dot next to it
+        #[arg(long)]
+        checks: bool,
+        /// Only show branches matching all of: needs-restack, has-pr, no-pr, dirty, merged
+        #[arg(long, value_delimiter = ',', value_name = "FILTER")]
+        filter: Vec<String>,
     },
 
     /// Show all stacks with PR URLs and full details
@@ -106,9 +158,30 @@ enum Commands {
         /// Compact output for scripts
         #[arg(long)]
         compact: bool,
+        /// One dense line per branch (same as `ui.compact = true`)
+        #[arg(long, conflicts_with_all = ["json", "compact"])]
+        dense: bool,
         /// Suppress extra output
         #[arg(long)]
         quiet: bool,
+        /// Show commits added since each branch was last submitted
+        #[arg(long)]
+        since_last_submit: bool,
+        /// Mark branches with local commits not yet pushed to their remote ref
+        #[arg(long)]
+        ahead_of_remote: bool,
+        /// Order sibling stacks: stack (current stack first), name, or recent
+        #[arg(long, default_value = "stack", value_name = "stack|name|recent")]
+        sort: String,
+        /// Fetch each PR's base branch and flag ones that differ from the stax parent
+        #[arg(long)]
+        check_pr_base: bool,
+        /// Fetch CI for each PR and show a pass/fail/pending dot next to it
+        #[arg(long)]
+        checks: bool,
+        /// Only show branches matching all of: needs-restack, has-pr, no-pr, dirty, merged
+        #[arg(long, value_delimiter = ',', value_name = "FILTER")]
+        filter: Vec<String>,
     },
 
     /// Show all stacks with commits and PR info
@@ -129,6 +202,12 @@ enum Commands {
         /// Suppress extra output
         #[arg(long)]
         quiet: bool,
+        /// Print trunk first and each stack from the bottom up
+        #[arg(long)]
+        reverse: bool,
+        /// Order sibling stacks at each fork: stack, name, or recent (default: largest first)
+        #[arg(long, value_name = "stack|name|recent")]
+        sort: Option<String>,
     },
 
     /// Submit stack - push branches and create/update PRs
@@ -136,6 +215,10 @@ enum Commands {
     Submit {
         #[command(flatten)]
         submit: SubmitOptions,
+        /// Submit only the current branch and its ancestors, skipping branches above it
+        /// (same as `stax downstack submit`)
+        #[arg(long, visible_alias = "up-to-here")]
+        stack_up: bool,
     },
 
     /// Merge PRs from bottom of stack up to current branch
@@ -193,6 +276,9 @@ enum Commands {
         /// Auto-stash and auto-pop dirty target worktrees during restack operations
         #[arg(long)]
         auto_stash_pop: bool,
+        /// Skip fetching and use remote-tracking refs from the last fetch
+        #[arg(long)]
+        no_fetch: bool,
     },
 
     /// Restack (rebase) the current branch onto its parent
@@ -206,9 +292,36 @@ enum Commands {
         /// Suppress extra output
         #[arg(long)]
         quiet: bool,
+        /// Print how long each branch took to restack, plus the total
+        #[arg(short, long, conflicts_with = "quiet")]
+        verbose: bool,
         /// Auto-stash and auto-pop dirty target worktrees during restack operations
         #[arg(long)]
         auto_stash_pop: bool,
+        /// Edit the combined rebase todo for the whole stack (linear stacks only)
+        #[arg(short, long, conflicts_with_all = ["all", "continue", "auto_stash_pop"])]
+        interactive: bool,
+        /// Run rebases with git hooks disabled (skips any checks those hooks enforce)
+        #[arg(long, conflicts_with = "interactive")]
+        no_hooks: bool,
+        /// Merge strategy passed to git rebase (--strategy)
+        #[arg(short = 's', long, conflicts_with = "interactive")]
+        strategy: Option<String>,
+        /// Strategy option passed to git rebase, repeatable (e.g. -X ours)
+        #[arg(short = 'X', long, conflicts_with = "interactive")]
+        strategy_option: Vec<String>,
+        /// Restack a linear stack with one `git rebase --update-refs` of its tip (git 2.38+)
+        #[arg(long, conflicts_with_all = ["interactive", "all"])]
+        update_refs: bool,
+        /// First rebase the current branch onto its fetched remote, keeping commits others pushed
+        #[arg(long, conflicts_with = "interactive")]
+        onto_remote: bool,
+        /// With --all, rebase up to N independent stacks in parallel using scratch worktrees
+        #[arg(short, long, default_value_t = 1, conflicts_with_all = ["interactive", "update_refs"])]
+        jobs: usize,
+        /// Show which branches would be rebased onto which parents, without changing anything
+        #[arg(long, conflicts_with_all = ["continue", "interactive", "onto_remote"])]
+        dry_run: bool,
     },
 
     /// Restack from the bottom and submit updates
@@ -238,6 +351,9 @@ enum Commands {
         /// Jump to child branch by index (1-based)
         #[arg(long)]
         child: Option<usize>,
+        /// Create and track the branch on the current one if it doesn't exist
+        #[arg(short = 'c', long, requires = "branch")]
+        create: bool,
     },
 
     /// Continue after resolving conflicts
@@ -268,7 +384,10 @@ enum Commands {
     },
 
     /// Show config file path and contents
-    Config,
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigSubcommand>,
+    },
 
     /// Show diffs for each branch vs parent plus an aggregate stack diff
     Diff {
@@ -278,6 +397,24 @@ enum Commands {
         /// Show all stacks
         #[arg(long)]
         all: bool,
+        /// Show per-file change counts (default)
+        #[arg(long, conflicts_with = "name_only")]
+        stat: bool,
+        /// Show only the names of changed files
+        #[arg(long)]
+        name_only: bool,
+        /// Show the full patch with changed words marked inline
+        #[arg(long, conflicts_with_all = ["stat", "name_only", "color_words"])]
+        word_diff: bool,
+        /// Show the full patch with changed words highlighted by color
+        #[arg(long, conflicts_with_all = ["stat", "name_only"])]
+        color_words: bool,
+        /// Diff an explicit <a>..<b> range instead of each branch against its parent
+        #[arg(long, value_name = "A..B", conflicts_with_all = ["stack", "all"])]
+        range: Option<String>,
+        /// Diff two branches (or any refs) directly, ignoring stack parents
+        #[arg(long, num_args = 2, value_names = ["A", "B"], conflicts_with_all = ["stack", "all", "range"])]
+        between: Option<Vec<String>>,
     },
 
     /// Show range-diff for branches that need restack
@@ -293,6 +430,13 @@ enum Commands {
     /// Check stax configuration and repo health
     Doctor,
 
+    /// Print a shell completion script (e.g. `stax completions zsh > _stax`)
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+
     /// Switch to the trunk branch
     #[command(visible_alias = "t")]
     Trunk,
@@ -321,6 +465,13 @@ enum Commands {
     #[command(visible_alias = "p")]
     Prev,
 
+    /// Check out a branch by selector: top, bottom, trunk, parent, child, or a branch name
+    #[command(visible_alias = "navigate")]
+    Go {
+        /// top, bottom, trunk, parent, child, or a branch name
+        selector: String,
+    },
+
     /// Branch management commands
     #[command(subcommand, visible_alias = "b")]
     Branch(BranchCommands),
@@ -333,6 +484,13 @@ enum Commands {
     #[command(subcommand, visible_alias = "ds")]
     Downstack(DownstackCommands),
 
+    /// Whole-stack commands
+    #[command(subcommand)]
+    Stack(StackCommands),
+
+    /// Open the interactive stack view (also what `stax` with no command does)
+    Tui,
+
     /// Create a new branch stacked on current
     #[command(visible_alias = "c")]
     Create {
@@ -344,16 +502,40 @@ enum Commands {
         /// Commit message (also used as branch name if no name provided)
         #[arg(short, long)]
         message: Option<String>,
-        /// Base branch to create from (defaults to current)
-        #[arg(long)]
+        /// Parent branch to stack on without checking it out (defaults to current)
+        #[arg(long, visible_alias = "parent")]
         from: Option<String>,
         /// Override branch prefix (e.g. "feature/")
         #[arg(long)]
         prefix: Option<String>,
+        /// Ticket ID for the {ticket} placeholder in branch.format (e.g. "PROJ-123")
+        #[arg(long)]
+        ticket: Option<String>,
+        /// Create an empty commit on the new branch (message derived from the name)
+        #[arg(long, conflicts_with = "all")]
+        empty: bool,
+        /// Commit the currently staged changes on the new branch, leaving the parent clean
+        #[arg(long, conflicts_with_all = ["all", "empty", "from"])]
+        commit: bool,
+        /// With --commit, fail instead of creating a bare branch when nothing is staged
+        #[arg(long, requires = "commit")]
+        require_staged: bool,
+        /// Don't track an untracked parent that was branched off trunk
+        #[arg(long)]
+        no_auto_track: bool,
+        /// Print the branch name that would be created, without creating anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Track an existing branch on the current one instead of creating it
+        #[arg(long, requires = "name", conflicts_with_all = ["message", "all", "empty", "commit"])]
+        track_only: bool,
     },
 
     /// Open the PR for the current branch in browser
-    Pr,
+    Pr {
+        #[command(subcommand)]
+        command: Option<PrSubcommand>,
+    },
 
     /// Open the repository in browser
     Open,
@@ -385,7 +567,11 @@ enum Commands {
     },
 
     /// Split the current branch into multiple stacked branches (interactive)
-    Split,
+    Split {
+        /// End a new branch at a commit, as <commit>:<name> (repeatable; skips the picker)
+        #[arg(long, value_name = "COMMIT:NAME")]
+        at: Vec<String>,
+    },
 
     /// Copy branch name or PR URL to clipboard
     Copy {
@@ -412,6 +598,12 @@ enum Commands {
         /// Generate PR body from diff and update the PR
         #[arg(long)]
         pr_body: bool,
+        /// Generate a one-line PR title from the diff and update the PR
+        #[arg(
+            long,
+            conflicts_with_all = ["pr_body", "stack", "since_last_submit", "template_dir"]
+        )]
+        title: bool,
         /// Open editor to review before updating
         #[arg(long)]
         edit: bool,
@@ -421,6 +613,18 @@ enum Commands {
         /// Model to use with the AI agent. Defaults to config or agent's default
         #[arg(long)]
         model: Option<String>,
+        /// Only describe changes pushed since the last submit
+        #[arg(long)]
+        since_last_submit: bool,
+        /// Generate PR bodies for every branch with a PR in the current stack
+        #[arg(long)]
+        stack: bool,
+        /// Update each PR without confirming (with --stack)
+        #[arg(long, requires = "stack", conflicts_with = "edit")]
+        yes: bool,
+        /// Look for the PR template in this directory (or file) instead of the standard locations
+        #[arg(long, value_name = "PATH")]
+        template_dir: Option<PathBuf>,
     },
 
     /// Generate changelog between two refs
@@ -492,12 +696,33 @@ enum Commands {
         all: bool,
         #[arg(short, long)]
         message: Option<String>,
-        /// Base branch to create from (defaults to current)
-        #[arg(long)]
+        /// Parent branch to stack on without checking it out (defaults to current)
+        #[arg(long, visible_alias = "parent")]
         from: Option<String>,
         /// Override branch prefix (e.g. "feature/")
         #[arg(long)]
         prefix: Option<String>,
+        /// Ticket ID for the {ticket} placeholder in branch.format (e.g. "PROJ-123")
+        #[arg(long)]
+        ticket: Option<String>,
+        /// Create an empty commit on the new branch (message derived from the name)
+        #[arg(long, conflicts_with = "all")]
+        empty: bool,
+        /// Commit the currently staged changes on the new branch, leaving the parent clean
+        #[arg(long, conflicts_with_all = ["all", "empty", "from"])]
+        commit: bool,
+        /// With --commit, fail instead of creating a bare branch when nothing is staged
+        #[arg(long, requires = "commit")]
+        require_staged: bool,
+        /// Don't track an untracked parent that was branched off trunk
+        #[arg(long)]
+        no_auto_track: bool,
+        /// Print the branch name that would be created, without creating anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Track an existing branch on the current one instead of creating it
+        #[arg(long, requires = "name", conflicts_with_all = ["message", "all", "empty", "commit"])]
+        track_only: bool,
     },
     #[command(hide = true)]
     Bu {
@@ -519,7 +744,52 @@ enum Commands {
 #[derive(Subcommand, Clone)]
 enum AuthSubcommand {
     /// Show which auth source is currently active
-    Status,
+    Status {
+        /// Ask GitHub which account the token belongs to and check its scopes
+        #[arg(long)]
+        verify: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum ConfigSubcommand {
+    /// Print the config and credentials file paths
+    Path,
+    /// Open the config in $EDITOR and validate it before saving
+    Edit,
+    /// Replace deprecated branch.prefix/branch.date with a branch.format template
+    Migrate,
+    /// Print a config value by dotted key (e.g. branch.format)
+    Get {
+        /// Dotted key, e.g. "auth.use_gh_cli"
+        key: String,
+    },
+    /// Set a config value by dotted key, validating its type
+    Set {
+        /// Dotted key, e.g. "branch.format"
+        key: String,
+        /// New value
+        value: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum PrSubcommand {
+    /// List each check run on the PR head with its conclusion and logs link
+    Checks {
+        /// Output JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
+    /// Close the current branch's PR without merging
+    Close,
+    /// Reopen the current branch's closed PR
+    Reopen,
+    /// Add a "Closes #N" line to the PR body so merging it closes the issue
+    Link {
+        /// Issue to close: 123, #123, or owner/repo#123
+        issue: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -535,12 +805,33 @@ enum BranchCommands {
         /// Commit message (also used as branch name if no name provided)
         #[arg(short, long)]
         message: Option<String>,
-        /// Base branch to create from (defaults to current)
-        #[arg(long)]
+        /// Parent branch to stack on without checking it out (defaults to current)
+        #[arg(long, visible_alias = "parent")]
         from: Option<String>,
         /// Override branch prefix (e.g. "feature/")
         #[arg(long)]
         prefix: Option<String>,
+        /// Ticket ID for the {ticket} placeholder in branch.format (e.g. "PROJ-123")
+        #[arg(long)]
+        ticket: Option<String>,
+        /// Create an empty commit on the new branch (message derived from the name)
+        #[arg(long, conflicts_with = "all")]
+        empty: bool,
+        /// Commit the currently staged changes on the new branch, leaving the parent clean
+        #[arg(long, conflicts_with_all = ["all", "empty", "from"])]
+        commit: bool,
+        /// With --commit, fail instead of creating a bare branch when nothing is staged
+        #[arg(long, requires = "commit")]
+        require_staged: bool,
+        /// Don't track an untracked parent that was branched off trunk
+        #[arg(long)]
+        no_auto_track: bool,
+        /// Print the branch name that would be created, without creating anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Track an existing branch on the current one instead of creating it
+        #[arg(long, requires = "name", conflicts_with_all = ["message", "all", "empty", "commit"])]
+        track_only: bool,
     },
 
     /// Checkout a branch in the stack
@@ -557,6 +848,9 @@ enum BranchCommands {
         /// Jump to child branch by index (1-based)
         #[arg(long)]
         child: Option<usize>,
+        /// Create and track the branch on the current one if it doesn't exist
+        #[arg(short = 'c', long, requires = "branch")]
+        create: bool,
     },
 
     /// Track an existing branch (set its parent)
@@ -567,6 +861,12 @@ enum BranchCommands {
         /// Track all open PRs authored by you
         #[arg(long)]
         all_prs: bool,
+        /// Overwrite existing tracking with the new parent (does not rebase)
+        #[arg(short, long, conflicts_with = "all_prs")]
+        force: bool,
+        /// Print the result as JSON (branch, parent, parent_revision, status)
+        #[arg(long, conflicts_with = "all_prs")]
+        json: bool,
     },
 
     /// Stop tracking a branch (remove stax metadata only)
@@ -574,6 +874,18 @@ enum BranchCommands {
     Untrack {
         /// Branch to untrack (defaults to current branch)
         branch: Option<String>,
+        /// Print the result as JSON (branch, parent, parent_revision, status)
+        #[arg(long, conflicts_with = "all")]
+        json: bool,
+        /// Remove stax metadata for every branch (git branches are kept)
+        #[arg(long, conflicts_with = "branch")]
+        all: bool,
+        /// With --all, list what would be removed without removing it
+        #[arg(long, requires = "all")]
+        dry_run: bool,
+        /// With --all, skip the confirmation prompt
+        #[arg(short, long, requires = "all")]
+        yes: bool,
     },
 
     /// Change the parent of a tracked branch
@@ -586,6 +898,19 @@ enum BranchCommands {
         parent: Option<String>,
     },
 
+    /// Move a branch to the top or bottom of its stack
+    Move {
+        /// Branch to move (defaults to current)
+        #[arg(short, long)]
+        branch: Option<String>,
+        /// Restack the branch onto the current stack tip
+        #[arg(long, conflicts_with = "to_bottom")]
+        to_top: bool,
+        /// Restack the branch directly onto trunk, below the rest of its stack
+        #[arg(long)]
+        to_bottom: bool,
+    },
+
     /// Rename the current branch
     #[command(visible_alias = "r")]
     Rename {
@@ -610,6 +935,15 @@ enum BranchCommands {
         /// Force delete even if not merged
         #[arg(short, long)]
         force: bool,
+        /// With --force, also delete a branch listed in branch.protected
+        #[arg(long = "i-really-mean-it", requires = "force")]
+        i_really_mean_it: bool,
+        /// Also delete the branch on the configured remote
+        #[arg(long)]
+        remote: bool,
+        /// Close the branch's open PR (requires --remote)
+        #[arg(long, requires = "remote")]
+        close_pr: bool,
     },
 
     /// Squash all commits on current branch into one
@@ -621,6 +955,19 @@ enum BranchCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         yes: bool,
+        /// Open the combined commit message in git's editor
+        #[arg(long, conflicts_with = "no_edit")]
+        edit: bool,
+        /// Use the default message without prompting
+        #[arg(long)]
+        no_edit: bool,
+    },
+
+    /// Split the current branch into multiple stacked branches
+    Split {
+        /// End a new branch at a commit, as <commit>:<name> (repeatable; skips the picker)
+        #[arg(long, value_name = "COMMIT:NAME")]
+        at: Vec<String>,
     },
 
     /// Fold current branch into its parent
@@ -632,6 +979,15 @@ enum BranchCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         yes: bool,
+        /// Fold even if the branch has an open PR
+        #[arg(short, long)]
+        force: bool,
+        /// Open the fold commit message in git's editor
+        #[arg(long, conflicts_with = "no_edit")]
+        edit: bool,
+        /// Never open the editor, even after resolving conflicts
+        #[arg(long)]
+        no_edit: bool,
     },
 
     /// Move up the stack (to child branch)
@@ -667,6 +1023,9 @@ enum UpstackCommands {
         /// Auto-stash and auto-pop dirty target worktrees during restack operations
         #[arg(long)]
         auto_stash_pop: bool,
+        /// Show which branches would be rebased onto which parents, without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Submit current branch and descendants
@@ -688,11 +1047,64 @@ enum DownstackCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum StackCommands {
+    /// Print the stack topology as a graph
+    Graph {
+        /// Emit a Graphviz DOT digraph (pipe to `dot -Tpng`)
+        #[arg(long)]
+        dot: bool,
+    },
+    /// Collapse a linear stack into its bottom branch, deleting the branches above it
+    SquashAll {
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Move the current stack on top of another stack's tip and restack it
+    Onto {
+        /// Any branch in the stack to build on
+        target: String,
+    },
+    /// Open the interactive stack view (same as `stax tui`)
+    Tui,
+    /// Repair branch metadata with an invalid parent revision
+    Fix,
+    /// Show the cumulative diff of the whole stack against trunk
+    Diff {
+        /// Stack tip to diff (defaults to the tips above the current branch)
+        #[arg(long)]
+        tip: Option<String>,
+        /// Show per-file change counts instead of the full patch
+        #[arg(long, conflicts_with = "name_only")]
+        stat: bool,
+        /// Show only the names of changed files
+        #[arg(long)]
+        name_only: bool,
+        /// Show the full patch with changed words highlighted by color
+        #[arg(long, conflicts_with_all = ["stat", "name_only"])]
+        color_words: bool,
+        /// Print inline instead of through git's pager
+        #[arg(long)]
+        no_pager: bool,
+    },
+}
+
+/// Collapse an `--edit`/`--no-edit` pair into an optional override
+fn edit_flag(edit: bool, no_edit: bool) -> Option<bool> {
+    match (edit, no_edit) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
 fn run_submit(submit: SubmitOptions, scope: commands::submit::SubmitScope) -> Result<()> {
     commands::submit::run(
         scope,
         submit.draft,
         submit.no_pr,
+        submit.no_push,
         submit.force,
         submit.yes,
         submit.no_prompt,
@@ -705,6 +1117,13 @@ fn run_submit(submit: SubmitOptions, scope: commands::submit::SubmitScope) -> Re
         submit.no_template,
         submit.edit,
         submit.ai_body,
+        submit.no_verify,
+        submit.restart,
+        submit.assign_me,
+        submit.comment_only,
+        submit.template_dir,
+        submit.no_track_pr,
+        submit.auto_merge,
     )
 }
 
@@ -713,6 +1132,13 @@ fn main() -> Result<()> {
     let _ = Config::ensure_exists();
 
     let cli = Cli::parse();
+    let update_check = update::is_enabled(cli.no_update_check);
+    if let Some(key) = Config::load()
+        .ok()
+        .and_then(|config| config.metadata_signing_key().ok().flatten())
+    {
+        engine::metadata::set_signing_key(key);
+    }
 
     // No command = launch TUI
     let command = match cli.command {
@@ -721,8 +1147,7 @@ fn main() -> Result<()> {
             // TUI requires initialized repo
             commands::init::ensure_initialized()?;
             let result = tui::run();
-            update::show_update_notification();
-            update::check_in_background();
+            update::notify(update_check);
             return result;
         }
     };
@@ -738,23 +1163,45 @@ fn main() -> Result<()> {
                 anyhow::bail!("`stax auth status` cannot be combined with --token or --from-gh.");
             }
             let result = match command {
-                Some(AuthSubcommand::Status) => commands::auth::status(),
+                Some(AuthSubcommand::Status { verify }) => commands::auth::status(*verify),
                 None => commands::auth::run(token.clone(), *from_gh),
             };
-            update::show_update_notification();
-            update::check_in_background();
+            update::notify(update_check);
             return result;
         }
-        Commands::Config => {
-            let result = commands::config::run();
-            update::show_update_notification();
-            update::check_in_background();
+        Commands::Config { command } => {
+            let result = match command {
+                Some(ConfigSubcommand::Path) => commands::config::path(),
+                Some(ConfigSubcommand::Edit) => commands::config::edit(),
+                Some(ConfigSubcommand::Migrate) => commands::config::migrate(),
+                Some(ConfigSubcommand::Get { key }) => commands::config::get(key),
+                Some(ConfigSubcommand::Set { key, value }) => commands::config::set(key, value),
+                None => commands::config::run(),
+            };
+            update::notify(update_check);
             return result;
         }
+        Commands::Completions { shell } => {
+            // Completions are for whichever binary was invoked (`stax` or `st`)
+            let bin_name = std::env::args()
+                .next()
+                .and_then(|arg0| {
+                    std::path::Path::new(&arg0)
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().into_owned())
+                })
+                .unwrap_or_else(|| "stax".to_string());
+            clap_complete::generate(
+                *shell,
+                &mut Cli::command(),
+                bin_name,
+                &mut std::io::stdout(),
+            );
+            return Ok(());
+        }
         Commands::Doctor => {
             let result = commands::doctor::run();
-            update::show_update_notification();
-            update::check_in_background();
+            update::notify(update_check);
             return result;
         }
         _ => {}
@@ -769,23 +1216,82 @@ fn main() -> Result<()> {
             stack,
             current,
             compact,
+            dense,
+            quiet,
+            since_last_submit,
+            ahead_of_remote,
+            sort,
+            check_pr_base,
+            checks,
+            filter,
+        } => commands::status::run(commands::status::StatusOptions {
+            json,
+            stack_filter: stack,
+            current_only: current,
+            compact,
+            dense,
             quiet,
-        } => commands::status::run(json, stack, current, compact, quiet, false),
+            verbose: false,
+            since_last_submit,
+            ahead_of_remote,
+            sort: sort.parse()?,
+            check_pr_base,
+            checks,
+            filters: filter.iter().map(|f| f.parse()).collect::<Result<_>>()?,
+        }),
         Commands::Ll {
             json,
             stack,
             current,
             compact,
+            dense,
             quiet,
-        } => commands::status::run(json, stack, current, compact, quiet, true),
+            since_last_submit,
+            ahead_of_remote,
+            sort,
+            check_pr_base,
+            checks,
+            filter,
+        } => commands::status::run(commands::status::StatusOptions {
+            json,
+            stack_filter: stack,
+            current_only: current,
+            compact,
+            dense,
+            quiet,
+            verbose: true,
+            since_last_submit,
+            ahead_of_remote,
+            sort: sort.parse()?,
+            check_pr_base,
+            checks,
+            filters: filter.iter().map(|f| f.parse()).collect::<Result<_>>()?,
+        }),
         Commands::Log {
             json,
             stack,
             current,
             compact,
             quiet,
-        } => commands::log::run(json, stack, current, compact, quiet),
-        Commands::Submit { submit } => run_submit(submit, commands::submit::SubmitScope::Stack),
+            reverse,
+            sort,
+        } => commands::log::run(
+            json,
+            stack,
+            current,
+            compact,
+            quiet,
+            reverse,
+            sort.map(|s| s.parse()).transpose()?,
+        ),
+        Commands::Submit { submit, stack_up } => {
+            let scope = if stack_up {
+                commands::submit::SubmitScope::Downstack
+            } else {
+                commands::submit::SubmitScope::Stack
+            };
+            run_submit(submit, scope)
+        }
         Commands::Merge {
             all,
             dry_run,
@@ -817,6 +1323,7 @@ fn main() -> Result<()> {
             quiet,
             verbose,
             auto_stash_pop,
+            no_fetch,
         } => commands::sync::run(
             restack,
             !no_delete,
@@ -826,13 +1333,45 @@ fn main() -> Result<()> {
             quiet,
             verbose,
             auto_stash_pop,
+            no_fetch,
         ),
         Commands::Restack {
             all,
             r#continue,
             quiet,
+            verbose,
             auto_stash_pop,
-        } => commands::restack::run(all, r#continue, quiet, auto_stash_pop),
+            interactive,
+            no_hooks,
+            strategy,
+            strategy_option,
+            update_refs,
+            onto_remote,
+            jobs,
+            dry_run,
+        } => {
+            if interactive {
+                commands::restack::run_interactive()
+            } else {
+                let rebase_options = git::RebaseOptions {
+                    no_hooks,
+                    strategy,
+                    strategy_options: strategy_option,
+                    update_refs,
+                };
+                commands::restack::run(
+                    all,
+                    r#continue,
+                    quiet,
+                    verbose,
+                    jobs.max(1),
+                    dry_run,
+                    auto_stash_pop,
+                    onto_remote,
+                    rebase_options,
+                )
+            }
+        }
         Commands::Cascade {
             no_pr,
             no_submit,
@@ -843,28 +1382,78 @@ fn main() -> Result<()> {
             trunk,
             parent,
             child,
-        } => commands::checkout::run(branch, trunk, parent, child),
+            create,
+        } => commands::checkout::run(branch, trunk, parent, child, create),
         Commands::Continue => commands::continue_cmd::run(),
         Commands::Modify { message, quiet } => commands::modify::run(message, quiet),
         Commands::Auth { .. } => unreachable!(), // Handled above
-        Commands::Config => unreachable!(),      // Handled above
-        Commands::Diff { stack, all } => commands::diff::run(stack, all),
+        Commands::Config { .. } => unreachable!(), // Handled above
+        Commands::Diff {
+            stack,
+            all,
+            stat: _,
+            name_only,
+            word_diff,
+            color_words,
+            range,
+            between,
+        } => {
+            let mode = if name_only {
+                commands::diff::DiffMode::NameOnly
+            } else if word_diff {
+                commands::diff::DiffMode::WordDiff
+            } else if color_words {
+                commands::diff::DiffMode::ColorWords
+            } else {
+                commands::diff::DiffMode::Stat
+            };
+            let between = between.map(|refs| (refs[0].clone(), refs[1].clone()));
+            commands::diff::run(stack, all, mode, range, between)
+        }
         Commands::RangeDiff { stack, all } => commands::range_diff::run(stack, all),
         Commands::Doctor => unreachable!(), // Handled above
-        Commands::Trunk => commands::checkout::run(None, true, false, None),
+        Commands::Completions { .. } => unreachable!(), // Handled above
+        Commands::Trunk => commands::checkout::run(None, true, false, None, false),
         Commands::Up { count } => commands::navigate::up(count),
         Commands::Down { count } => commands::navigate::down(count),
         Commands::Top => commands::navigate::top(),
         Commands::Bottom => commands::navigate::bottom(),
         Commands::Prev => commands::navigate::prev(),
+        Commands::Go { selector } => commands::navigate::go(&selector),
         Commands::Create {
             name,
             all,
             message,
             from,
             prefix,
-        } => commands::branch::create::run(name, message, from, prefix, all),
-        Commands::Pr => commands::pr::run(),
+            ticket,
+            empty,
+            commit,
+            require_staged,
+            no_auto_track,
+            dry_run,
+            track_only,
+        } => commands::branch::create::run(
+            name,
+            message,
+            from,
+            prefix,
+            ticket,
+            all,
+            empty,
+            commit,
+            require_staged,
+            !no_auto_track,
+            dry_run,
+            track_only,
+        ),
+        Commands::Pr { command } => match command {
+            Some(PrSubcommand::Checks { json }) => commands::pr::checks(json),
+            Some(PrSubcommand::Close) => commands::pr::close(),
+            Some(PrSubcommand::Reopen) => commands::pr::reopen(),
+            Some(PrSubcommand::Link { issue }) => commands::pr::link(&issue),
+            None => commands::pr::run(),
+        },
         Commands::Open => commands::open::run(),
         Commands::Comments { plain } => commands::comments::run(plain),
         Commands::Ci {
@@ -874,7 +1463,7 @@ fn main() -> Result<()> {
             watch,
             interval,
         } => commands::ci::run(all, json, refresh, watch, interval),
-        Commands::Split => commands::split::run(),
+        Commands::Split { at } => commands::split::run(at),
         Commands::Copy { pr } => {
             let target = if pr {
                 commands::copy::CopyTarget::Pr
@@ -886,14 +1475,30 @@ fn main() -> Result<()> {
         Commands::Standup { json, all, hours } => commands::standup::run(json, all, hours),
         Commands::Generate {
             pr_body,
+            title,
             edit,
             agent,
             model,
+            since_last_submit,
+            stack,
+            yes,
+            template_dir,
         } => {
-            if !pr_body {
-                anyhow::bail!("Please specify what to generate. Usage: stax generate --pr-body");
+            if !pr_body && !title && !stack {
+                anyhow::bail!(
+                    "Please specify what to generate. Usage: stax generate --pr-body or stax generate --title"
+                );
             }
-            commands::generate::run(edit, agent, model)
+            commands::generate::run(
+                edit,
+                agent,
+                model,
+                since_last_submit,
+                stack,
+                title,
+                yes,
+                template_dir,
+            )
         }
         Commands::Changelog {
             from,
@@ -926,31 +1531,82 @@ fn main() -> Result<()> {
                 message,
                 from,
                 prefix,
-            } => commands::branch::create::run(name, message, from, prefix, all),
+                ticket,
+                empty,
+                commit,
+                require_staged,
+                no_auto_track,
+                dry_run,
+                track_only,
+            } => commands::branch::create::run(
+                name,
+                message,
+                from,
+                prefix,
+                ticket,
+                all,
+                empty,
+                commit,
+                require_staged,
+                !no_auto_track,
+                dry_run,
+                track_only,
+            ),
             BranchCommands::Checkout {
                 branch,
                 trunk,
                 parent,
                 child,
-            } => commands::checkout::run(branch, trunk, parent, child),
-            BranchCommands::Track { parent, all_prs } => {
-                commands::branch::track::run(parent, all_prs)
-            }
-            BranchCommands::Untrack { branch } => commands::branch::untrack::run(branch),
+                create,
+            } => commands::checkout::run(branch, trunk, parent, child, create),
+            BranchCommands::Track {
+                parent,
+                all_prs,
+                force,
+                json,
+            } => commands::branch::track::run(parent, all_prs, force, json),
+            BranchCommands::Untrack {
+                branch,
+                json,
+                all,
+                dry_run,
+                yes,
+            } => commands::branch::untrack::run(branch, json, all, dry_run, yes),
             BranchCommands::Reparent { branch, parent } => {
                 commands::branch::reparent::run(branch, parent)
             }
+            BranchCommands::Move {
+                branch,
+                to_top,
+                to_bottom,
+            } => commands::branch::move_cmd::run(branch, to_top, to_bottom),
             BranchCommands::Rename {
                 name,
                 edit,
                 push,
                 literal,
             } => commands::branch::rename::run(name, edit, push, literal),
-            BranchCommands::Delete { branch, force } => {
-                commands::branch::delete::run(branch, force)
-            }
-            BranchCommands::Squash { message, yes } => commands::branch::squash::run(message, yes),
-            BranchCommands::Fold { keep, yes } => commands::branch::fold::run(keep, yes),
+            BranchCommands::Delete {
+                branch,
+                force,
+                i_really_mean_it,
+                remote,
+                close_pr,
+            } => commands::branch::delete::run(branch, force, i_really_mean_it, remote, close_pr),
+            BranchCommands::Squash {
+                message,
+                yes,
+                edit,
+                no_edit,
+            } => commands::branch::squash::run(message, yes, edit_flag(edit, no_edit)),
+            BranchCommands::Split { at } => commands::split::run(at),
+            BranchCommands::Fold {
+                keep,
+                yes,
+                force,
+                edit,
+                no_edit,
+            } => commands::branch::fold::run(keep, yes, force, edit_flag(edit, no_edit)),
             BranchCommands::Up { count } => commands::navigate::up(count),
             BranchCommands::Down { count } => commands::navigate::down(count),
             BranchCommands::Top => commands::navigate::top(),
@@ -960,21 +1616,46 @@ fn main() -> Result<()> {
             }
         },
         Commands::Upstack(cmd) => match cmd {
-            UpstackCommands::Restack { auto_stash_pop } => {
-                commands::upstack::restack::run(auto_stash_pop)
-            }
+            UpstackCommands::Restack {
+                auto_stash_pop,
+                dry_run,
+            } => commands::upstack::restack::run(auto_stash_pop, dry_run),
             UpstackCommands::Submit { submit } => {
                 run_submit(submit, commands::submit::SubmitScope::Upstack)
             }
         },
         Commands::Downstack(cmd) => match cmd {
-            DownstackCommands::Get => {
-                commands::status::run(false, None, false, false, false, false)
-            }
+            DownstackCommands::Get => commands::status::run(Default::default()),
             DownstackCommands::Submit { submit } => {
                 run_submit(submit, commands::submit::SubmitScope::Downstack)
             }
         },
+        Commands::Tui => tui::run(),
+        Commands::Stack(cmd) => match cmd {
+            StackCommands::Graph { dot } => commands::stack::graph::run(dot),
+            StackCommands::SquashAll { yes } => commands::stack::squash_all::run(yes),
+            StackCommands::Onto { target } => commands::stack::onto::run(target),
+            StackCommands::Tui => tui::run(),
+            StackCommands::Fix => commands::stack::fix::run(),
+            StackCommands::Diff {
+                tip,
+                stat,
+                name_only,
+                color_words,
+                no_pager,
+            } => {
+                let mode = if stat {
+                    commands::diff::DiffMode::Stat
+                } else if name_only {
+                    commands::diff::DiffMode::NameOnly
+                } else if color_words {
+                    commands::diff::DiffMode::ColorWords
+                } else {
+                    commands::diff::DiffMode::Patch
+                };
+                commands::stack::diff::run(tip, mode, no_pager)
+            }
+        },
         // Hidden shortcuts
         Commands::Bc {
             name,
@@ -982,15 +1663,34 @@ fn main() -> Result<()> {
             message,
             from,
             prefix,
-        } => commands::branch::create::run(name, message, from, prefix, all),
+            ticket,
+            empty,
+            commit,
+            require_staged,
+            no_auto_track,
+            dry_run,
+            track_only,
+        } => commands::branch::create::run(
+            name,
+            message,
+            from,
+            prefix,
+            ticket,
+            all,
+            empty,
+            commit,
+            require_staged,
+            !no_auto_track,
+            dry_run,
+            track_only,
+        ),
         Commands::Bu { count } => commands::navigate::up(count),
         Commands::Bd { count } => commands::navigate::down(count),
         Commands::Bs { submit } => run_submit(submit, commands::submit::SubmitScope::Branch),
     };
 
     // Show update notification (from cache, instant) and spawn background check for next run
-    update::show_update_notification();
-    update::check_in_background();
+    update::notify(update_check);
 
     result
 }
@@ -4,6 +4,7 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Status of an operation
@@ -25,6 +26,10 @@ pub enum OpKind {
     Submit,
     Reorder,
     Split,
+    SquashAll,
+    StackOnto,
+    Squash,
+    Fold,
 }
 
 impl OpKind {
@@ -36,10 +41,41 @@ impl OpKind {
             OpKind::Submit => "submit",
             OpKind::Reorder => "reorder",
             OpKind::Split => "split",
+            OpKind::SquashAll => "stack squash-all",
+            OpKind::StackOnto => "stack onto",
+            OpKind::Squash => "branch squash",
+            OpKind::Fold => "branch fold",
         }
     }
 }
 
+/// What `stax continue` still has to do for an operation stopped on conflicts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ResumeState {
+    Fold(FoldResume),
+    Squash(SquashResume),
+}
+
+/// A fold whose squash-merge into the parent awaits the commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FoldResume {
+    pub branch: String,
+    pub parent: String,
+    pub keep_branch: bool,
+    /// `Some(true)` for `--edit`, `Some(false)` for `--no-edit`
+    pub edit: Option<bool>,
+    pub message: String,
+}
+
+/// A squashed commit being rebased onto its moved parent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SquashResume {
+    pub branch: String,
+    pub parent: String,
+    pub commit_count: usize,
+}
+
 /// Information about a local ref that was modified
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalRefEntry {
@@ -53,6 +89,9 @@ pub struct LocalRefEntry {
     pub oid_before: Option<String>,
     /// OID after the operation (filled in on success)
     pub oid_after: Option<String>,
+    /// Branch metadata JSON before the operation (restored by undo)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_before: Option<String>,
 }
 
 /// Information about a remote ref that was modified (for submit)
@@ -89,6 +128,23 @@ pub struct PlanSummary {
     pub description: Vec<String>,
 }
 
+/// Steps a submit has completed, so an interrupted run can pick up where it stopped
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct SubmitProgress {
+    /// Branches pushed so far, with the OID that was pushed
+    #[serde(default)]
+    pub pushed: BTreeMap<String, String>,
+    /// PRs created so far, keyed by head branch
+    #[serde(default)]
+    pub created_prs: BTreeMap<String, u64>,
+}
+
+impl SubmitProgress {
+    pub fn is_empty(&self) -> bool {
+        self.pushed.is_empty() && self.created_prs.is_empty()
+    }
+}
+
 /// Operation receipt - persisted to `.git/stax/ops/<op-id>.json`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpReceipt {
@@ -116,6 +172,12 @@ pub struct OpReceipt {
     pub plan_summary: PlanSummary,
     /// Error information if failed
     pub error: Option<OpError>,
+    /// Completed submit steps (submit only)
+    #[serde(default, skip_serializing_if = "SubmitProgress::is_empty")]
+    pub submit_progress: SubmitProgress,
+    /// Remaining steps when the operation stopped on conflicts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume: Option<ResumeState>,
 }
 
 impl OpReceipt {
@@ -142,6 +204,8 @@ impl OpReceipt {
             remote_refs: Vec::new(),
             plan_summary: PlanSummary::default(),
             error: None,
+            submit_progress: SubmitProgress::default(),
+            resume: None,
         }
     }
 
@@ -153,6 +217,7 @@ impl OpReceipt {
             existed_before: oid_before.is_some(),
             oid_before: oid_before.map(|s| s.to_string()),
             oid_after: None,
+            metadata_before: None,
         });
     }
 
@@ -240,6 +305,20 @@ impl OpReceipt {
         }
     }
 
+    /// Remaining steps if this operation stopped on conflicts and awaits `stax continue`
+    pub fn paused(&self) -> Option<&ResumeState> {
+        self.resume
+            .as_ref()
+            .filter(|_| self.status == OpStatus::Failed)
+    }
+
+    /// Whether this is an unfinished submit with progress worth resuming
+    pub fn is_resumable_submit(&self) -> bool {
+        self.kind == OpKind::Submit
+            && self.status != OpStatus::Success
+            && !self.submit_progress.is_empty()
+    }
+
     /// Check if this receipt can be undone
     pub fn can_undo(&self) -> bool {
         // Can undo if we have local refs with before-OIDs
@@ -545,6 +624,7 @@ mod tests {
             existed_before: true,
             oid_before: Some("abc123".to_string()),
             oid_after: Some("def456".to_string()),
+            metadata_before: None,
         };
         let cloned = entry.clone();
         assert_eq!(cloned.branch, "feature");
@@ -575,4 +655,58 @@ mod tests {
         let cloned = error.clone();
         assert_eq!(cloned.message, "Test error");
     }
+
+    #[test]
+    fn test_local_ref_entry_without_metadata_deserializes() {
+        let json = r#"{"branch":"feature","refname":"refs/heads/feature","existed_before":true,"oid_before":"abc123","oid_after":null}"#;
+        let entry: LocalRefEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.oid_before, Some("abc123".to_string()));
+        assert!(entry.metadata_before.is_none());
+    }
+
+    #[test]
+    fn test_submit_progress_roundtrip_and_resumable() {
+        let mut receipt = OpReceipt::new(
+            "test".to_string(),
+            OpKind::Submit,
+            "/tmp".to_string(),
+            "main".to_string(),
+            "feature".to_string(),
+        );
+        assert!(!receipt.is_resumable_submit());
+
+        receipt
+            .submit_progress
+            .pushed
+            .insert("feature".to_string(), "abc123".to_string());
+        receipt
+            .submit_progress
+            .created_prs
+            .insert("feature".to_string(), 7);
+        receipt.mark_failed("Push failed", Some("push"), Some("feature-2"));
+        assert!(receipt.is_resumable_submit());
+
+        let json = serde_json::to_string(&receipt).unwrap();
+        let loaded: OpReceipt = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.submit_progress, receipt.submit_progress);
+
+        receipt.mark_success();
+        assert!(!receipt.is_resumable_submit());
+    }
+
+    #[test]
+    fn test_receipt_without_submit_progress_deserializes() {
+        let receipt = OpReceipt::new(
+            "test".to_string(),
+            OpKind::Restack,
+            "/tmp".to_string(),
+            "main".to_string(),
+            "feature".to_string(),
+        );
+        let json = serde_json::to_string(&receipt).unwrap();
+        assert!(!json.contains("submit_progress"));
+
+        let loaded: OpReceipt = serde_json::from_str(&json).unwrap();
+        assert!(loaded.submit_progress.is_empty());
+    }
 }
@@ -14,8 +14,8 @@
 //! tx.finish_ok()?;  // Or tx.finish_err("message")?;
 //! ```
 
-use super::receipt::{OpKind, OpReceipt, PlanSummary};
-use crate::git::GitRepo;
+use super::receipt::{OpKind, OpReceipt, PlanSummary, ResumeState, SubmitProgress};
+use crate::git::{refs, GitRepo};
 use anyhow::Result;
 use colored::Colorize;
 use std::path::PathBuf;
@@ -70,6 +70,9 @@ impl Transaction {
     pub fn plan_branch(&mut self, repo: &GitRepo, branch: &str) -> Result<()> {
         let oid = repo.branch_commit(branch).ok();
         self.receipt.add_local_ref(branch, oid.as_deref());
+        if let Some(entry) = self.receipt.local_refs.last_mut() {
+            entry.metadata_before = refs::read_metadata(repo.inner(), branch)?;
+        }
         Ok(())
     }
 
@@ -169,6 +172,36 @@ impl Transaction {
             .update_remote_ref_after(remote, branch, local_oid);
     }
 
+    /// Carry over the progress of an interrupted submit this one resumes
+    pub fn resume_submit_progress(&mut self, progress: SubmitProgress) {
+        self.receipt.submit_progress = progress;
+    }
+
+    /// Record a pushed branch, saving right away so an interrupted submit can resume
+    pub fn record_pushed(&mut self, branch: &str, oid: &str) -> Result<()> {
+        self.receipt
+            .submit_progress
+            .pushed
+            .insert(branch.to_string(), oid.to_string());
+        self.save_progress()
+    }
+
+    /// Record a created PR, saving right away so an interrupted submit can resume
+    pub fn record_created_pr(&mut self, branch: &str, pr_number: u64) -> Result<()> {
+        self.receipt
+            .submit_progress
+            .created_prs
+            .insert(branch.to_string(), pr_number);
+        self.save_progress()
+    }
+
+    fn save_progress(&self) -> Result<()> {
+        if self.snapshotted {
+            self.receipt.save(&self.git_dir)?;
+        }
+        Ok(())
+    }
+
     /// Finish the transaction successfully
     pub fn finish_ok(mut self) -> Result<()> {
         self.receipt.mark_success();
@@ -196,6 +229,19 @@ impl Transaction {
         Ok(())
     }
 
+    /// Stop on conflicts, recording what `stax continue` must do to finish
+    pub fn pause(mut self, resume: ResumeState, failed_branch: &str) -> Result<()> {
+        self.receipt.resume = Some(resume);
+        self.receipt.mark_failed(
+            "Stopped on conflicts",
+            Some("conflict"),
+            Some(failed_branch),
+        );
+        self.receipt.save(&self.git_dir)?;
+        self.finished = true;
+        Ok(())
+    }
+
     /// Print the recovery hint after a failure
     fn print_recovery_hint(&self) {
         println!();
@@ -226,6 +272,22 @@ impl Drop for Transaction {
     }
 }
 
+/// The latest operation, if it stopped on conflicts and awaits `stax continue`
+pub fn load_paused(repo: &GitRepo) -> Result<Option<OpReceipt>> {
+    Ok(OpReceipt::load_latest(repo.git_dir()?)?.filter(|r| r.paused().is_some()))
+}
+
+/// Mark a paused operation finished once `stax continue` has completed it
+pub fn finish_resumed(repo: &GitRepo, mut receipt: OpReceipt) -> Result<()> {
+    for entry in &mut receipt.local_refs {
+        entry.oid_after = repo.branch_commit(&entry.branch).ok();
+    }
+    receipt.resume = None;
+    receipt.error = None;
+    receipt.mark_success();
+    receipt.save(repo.git_dir()?)
+}
+
 /// Print the plan before executing
 pub fn print_plan(_kind: &OpKind, summary: &PlanSummary, quiet: bool) {
     if quiet {
@@ -0,0 +1,532 @@
+//! Forge-agnostic interface for pull request operations.
+//!
+//! Commands program against [`RemoteProvider`] instead of a concrete API
+//! client. [`RemoteInfo::provider`](crate::remote::RemoteInfo::provider)
+//! picks the implementation based on the remote host or `remote.provider`.
+
+use crate::github::pr::{MergeMethod, PrInfo, PrInfoWithChecks, PrInfoWithHead};
+use crate::github::GitHubClient;
+use crate::gitlab::GitLabClient;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// Which forge hosts the remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    GitHub,
+    GitLab,
+    Bitbucket,
+}
+
+impl ProviderKind {
+    /// Parse a `remote.provider` config value
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "github" => Ok(ProviderKind::GitHub),
+            "gitlab" => Ok(ProviderKind::GitLab),
+            "bitbucket" => Ok(ProviderKind::Bitbucket),
+            other => bail!(
+                "Unknown remote.provider '{}'. Expected one of: github, gitlab, bitbucket",
+                other
+            ),
+        }
+    }
+
+    /// Pick a provider from config, falling back to the remote host name
+    pub fn detect(host: &str, configured: Option<&str>) -> Result<Self> {
+        if let Some(value) = configured.filter(|v| !v.trim().is_empty()) {
+            return Self::parse(value);
+        }
+
+        let host = host.to_lowercase();
+        Ok(if host.contains("gitlab") {
+            ProviderKind::GitLab
+        } else if host.contains("bitbucket") {
+            ProviderKind::Bitbucket
+        } else {
+            // GitHub.com and GitHub Enterprise hosts
+            ProviderKind::GitHub
+        })
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ProviderKind::GitHub => "GitHub",
+            ProviderKind::GitLab => "GitLab",
+            ProviderKind::Bitbucket => "Bitbucket",
+        }
+    }
+}
+
+/// Pull request operations a forge must support for submit/generate/status
+#[async_trait]
+pub trait RemoteProvider: Send + Sync {
+    /// Create a new PR
+    async fn create_pr(
+        &self,
+        branch: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PrInfo>;
+
+    /// Get a PR by number
+    async fn get_pr(&self, pr_number: u64) -> Result<PrInfo>;
+
+    /// Get a PR by number with its head commit's CI, where the forge reports it
+    async fn get_pr_with_checks(&self, pr_number: u64) -> Result<PrInfoWithChecks> {
+        Ok(PrInfoWithChecks {
+            info: self.get_pr(pr_number).await?,
+            mergeable: None,
+            checks: None,
+        })
+    }
+
+    /// Get a PR by number, including head branch name
+    async fn get_pr_with_head(&self, pr_number: u64) -> Result<PrInfoWithHead>;
+
+    /// Find the open PR whose head is `branch`
+    async fn find_pr(&self, branch: &str) -> Result<Option<PrInfo>>;
+
+    /// All open PRs, keyed by head branch
+    async fn list_open_prs_by_head(&self) -> Result<HashMap<String, PrInfoWithHead>>;
+
+    /// PR state as reported by the forge (e.g. "Open", "Closed")
+    async fn pr_state(&self, pr_number: u64) -> Result<String> {
+        Ok(self.get_pr(pr_number).await?.state)
+    }
+
+    /// Retarget a PR at a new base branch
+    async fn update_base(&self, pr_number: u64, new_base: &str) -> Result<()>;
+
+    /// Current PR description (empty if none)
+    async fn get_pr_body(&self, pr_number: u64) -> Result<String>;
+
+    /// Replace the PR description
+    async fn update_pr_body(&self, pr_number: u64, body: &str) -> Result<()>;
+
+    /// Replace the PR description unless it already matches byte for byte,
+    /// keeping no-op edits out of the PR timeline. Returns whether it was written.
+    async fn update_pr_body_if_changed(&self, pr_number: u64, body: &str) -> Result<bool> {
+        if self.get_pr_body(pr_number).await? == body {
+            return Ok(false);
+        }
+        self.update_pr_body(pr_number, body).await?;
+        Ok(true)
+    }
+
+    /// Replace the PR title
+    async fn update_pr_title(&self, pr_number: u64, title: &str) -> Result<()>;
+
+    /// Close a PR without merging
+    async fn close_pr(&self, pr_number: u64) -> Result<()>;
+
+    /// Add or update the stack list in a PR's body
+    async fn update_stack_comment(&self, pr_number: u64, stack_comment: &str) -> Result<()>;
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()>;
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()>;
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()>;
+
+    /// Merge the PR with `method` once its required checks and reviews pass
+    async fn enable_auto_merge(&self, pr_number: u64, method: MergeMethod) -> Result<()>;
+
+    /// Login of the account the token authenticates as
+    async fn current_user(&self) -> Result<String>;
+
+    /// Combined CI state for a commit ("success", "failure", "pending"), if any
+    #[allow(dead_code)] // Commands still ask GitHubClient directly
+    async fn combined_status_state(&self, commit_sha: &str) -> Result<Option<String>>;
+
+    /// State, mergeability, and CI of several PRs; PRs that can't be fetched are left out
+    async fn fetch_prs_batch(&self, numbers: &[u64]) -> Result<HashMap<u64, PrInfoWithChecks>> {
+        let mut results = HashMap::new();
+        for &number in numbers {
+            if let Ok(pr) = self.get_pr_with_checks(number).await {
+                results.insert(number, pr);
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl RemoteProvider for GitHubClient {
+    async fn create_pr(
+        &self,
+        branch: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PrInfo> {
+        GitHubClient::create_pr(self, branch, base, title, body, draft).await
+    }
+
+    async fn get_pr(&self, pr_number: u64) -> Result<PrInfo> {
+        GitHubClient::get_pr(self, pr_number).await
+    }
+
+    async fn get_pr_with_checks(&self, pr_number: u64) -> Result<PrInfoWithChecks> {
+        GitHubClient::get_pr_with_checks(self, pr_number).await
+    }
+
+    async fn get_pr_with_head(&self, pr_number: u64) -> Result<PrInfoWithHead> {
+        GitHubClient::get_pr_with_head(self, pr_number).await
+    }
+
+    async fn find_pr(&self, branch: &str) -> Result<Option<PrInfo>> {
+        GitHubClient::find_pr(self, branch).await
+    }
+
+    async fn list_open_prs_by_head(&self) -> Result<HashMap<String, PrInfoWithHead>> {
+        GitHubClient::list_open_prs_by_head(self).await
+    }
+
+    async fn update_base(&self, pr_number: u64, new_base: &str) -> Result<()> {
+        GitHubClient::update_pr_base(self, pr_number, new_base).await
+    }
+
+    async fn get_pr_body(&self, pr_number: u64) -> Result<String> {
+        GitHubClient::get_pr_body(self, pr_number).await
+    }
+
+    async fn update_pr_body(&self, pr_number: u64, body: &str) -> Result<()> {
+        GitHubClient::update_pr_body(self, pr_number, body).await
+    }
+
+    async fn update_pr_title(&self, pr_number: u64, title: &str) -> Result<()> {
+        GitHubClient::update_pr_title(self, pr_number, title).await
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        GitHubClient::close_pr(self, pr_number).await
+    }
+
+    async fn update_stack_comment(&self, pr_number: u64, stack_comment: &str) -> Result<()> {
+        GitHubClient::update_stack_comment(self, pr_number, stack_comment).await
+    }
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()> {
+        GitHubClient::request_reviewers(self, pr_number, reviewers).await
+    }
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()> {
+        GitHubClient::add_labels(self, pr_number, labels).await
+    }
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()> {
+        GitHubClient::add_assignees(self, pr_number, assignees).await
+    }
+
+    async fn enable_auto_merge(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        GitHubClient::enable_auto_merge(self, pr_number, method).await
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        Ok(GitHubClient::token_identity(self).await?.login)
+    }
+
+    async fn combined_status_state(&self, commit_sha: &str) -> Result<Option<String>> {
+        GitHubClient::combined_status_state(self, commit_sha).await
+    }
+
+    async fn fetch_prs_batch(&self, numbers: &[u64]) -> Result<HashMap<u64, PrInfoWithChecks>> {
+        GitHubClient::fetch_prs_batch(self, numbers).await
+    }
+}
+
+#[async_trait]
+impl RemoteProvider for GitLabClient {
+    async fn create_pr(
+        &self,
+        branch: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PrInfo> {
+        GitLabClient::create_pr(self, branch, base, title, body, draft).await
+    }
+
+    async fn get_pr(&self, pr_number: u64) -> Result<PrInfo> {
+        GitLabClient::get_pr(self, pr_number).await
+    }
+
+    async fn get_pr_with_head(&self, pr_number: u64) -> Result<PrInfoWithHead> {
+        GitLabClient::get_pr_with_head(self, pr_number).await
+    }
+
+    async fn find_pr(&self, branch: &str) -> Result<Option<PrInfo>> {
+        GitLabClient::find_pr(self, branch).await
+    }
+
+    async fn list_open_prs_by_head(&self) -> Result<HashMap<String, PrInfoWithHead>> {
+        GitLabClient::list_open_prs_by_head(self).await
+    }
+
+    async fn update_base(&self, pr_number: u64, new_base: &str) -> Result<()> {
+        GitLabClient::update_pr_base(self, pr_number, new_base).await
+    }
+
+    async fn get_pr_body(&self, pr_number: u64) -> Result<String> {
+        GitLabClient::get_pr_body(self, pr_number).await
+    }
+
+    async fn update_pr_body(&self, pr_number: u64, body: &str) -> Result<()> {
+        GitLabClient::update_pr_body(self, pr_number, body).await
+    }
+
+    async fn update_pr_title(&self, pr_number: u64, title: &str) -> Result<()> {
+        GitLabClient::update_pr_title(self, pr_number, title).await
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        GitLabClient::close_pr(self, pr_number).await
+    }
+
+    async fn update_stack_comment(&self, pr_number: u64, stack_comment: &str) -> Result<()> {
+        GitLabClient::update_stack_comment(self, pr_number, stack_comment).await
+    }
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()> {
+        GitLabClient::request_reviewers(self, pr_number, reviewers).await
+    }
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()> {
+        GitLabClient::add_labels(self, pr_number, labels).await
+    }
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()> {
+        GitLabClient::add_assignees(self, pr_number, assignees).await
+    }
+
+    async fn enable_auto_merge(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        GitLabClient::enable_auto_merge(self, pr_number, method).await
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        GitLabClient::current_user(self).await
+    }
+
+    async fn combined_status_state(&self, commit_sha: &str) -> Result<Option<String>> {
+        GitLabClient::combined_status_state(self, commit_sha).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use octocrab::Octocrab;
+    use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn boxed_client(server: &MockServer) -> Box<dyn RemoteProvider> {
+        let octocrab = Octocrab::builder()
+            .base_uri(server.uri())
+            .unwrap()
+            .personal_token("test-token".to_string())
+            .build()
+            .unwrap();
+
+        Box::new(GitHubClient::with_octocrab(
+            octocrab,
+            "test-owner",
+            "test-repo",
+        ))
+    }
+
+    fn pr_json(number: u64, head: &str, base: &str) -> serde_json::Value {
+        serde_json::json!({
+            "url": format!("https://api.github.com/repos/test-owner/test-repo/pulls/{}", number),
+            "id": number,
+            "number": number,
+            "state": "open",
+            "head": { "ref": head, "sha": "aaaa", "label": format!("test-owner:{}", head) },
+            "base": { "ref": base, "sha": "bbbb" },
+            "draft": false
+        })
+    }
+
+    #[test]
+    fn test_detect_provider_from_host() {
+        assert_eq!(
+            ProviderKind::detect("github.com", None).unwrap(),
+            ProviderKind::GitHub
+        );
+        assert_eq!(
+            ProviderKind::detect("github.company.com", None).unwrap(),
+            ProviderKind::GitHub
+        );
+        assert_eq!(
+            ProviderKind::detect("gitlab.com", None).unwrap(),
+            ProviderKind::GitLab
+        );
+        assert_eq!(
+            ProviderKind::detect("bitbucket.org", None).unwrap(),
+            ProviderKind::Bitbucket
+        );
+    }
+
+    #[test]
+    fn test_configured_provider_overrides_host() {
+        assert_eq!(
+            ProviderKind::detect("git.internal", Some("GitLab")).unwrap(),
+            ProviderKind::GitLab
+        );
+        assert_eq!(
+            ProviderKind::detect("gitlab.com", Some("")).unwrap(),
+            ProviderKind::GitLab
+        );
+        assert!(ProviderKind::detect("github.com", Some("gitea")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_github_provider_get_pr_matches_client() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr_json(7, "feature", "main")))
+            .mount(&server)
+            .await;
+
+        let provider = boxed_client(&server);
+
+        let pr = provider.get_pr_with_head(7).await.unwrap();
+        assert_eq!(pr.head, "feature");
+        assert_eq!(pr.info.base, "main");
+        assert_eq!(provider.pr_state(7).await.unwrap(), "Open");
+    }
+
+    #[tokio::test]
+    async fn test_github_provider_get_pr_body() {
+        let server = MockServer::start().await;
+        let mut pr = pr_json(7, "feature", "main");
+        pr["body"] = serde_json::json!("Existing description");
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr))
+            .mount(&server)
+            .await;
+
+        let provider = boxed_client(&server);
+        assert_eq!(
+            provider.get_pr_body(7).await.unwrap(),
+            "Existing description"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_github_provider_update_base_body_and_title() {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .and(body_partial_json(serde_json::json!({ "base": "parent" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr_json(7, "feature", "parent")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .and(body_partial_json(serde_json::json!({ "body": "new body" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr_json(7, "feature", "parent")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .and(body_partial_json(
+                serde_json::json!({ "title": "feat: new title" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr_json(7, "feature", "parent")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = boxed_client(&server);
+        provider.update_base(7, "parent").await.unwrap();
+        provider.update_pr_body(7, "new body").await.unwrap();
+        provider
+            .update_pr_title(7, "feat: new title")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_github_provider_writes_stack_list_into_body() {
+        let server = MockServer::start().await;
+        let mut pr = pr_json(7, "feature", "main");
+        pr["body"] = serde_json::json!("Body");
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .and(body_string_contains("<!-- stax-stack -->"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/issues/7/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+
+        let provider = boxed_client(&server);
+        provider
+            .update_stack_comment(7, "* **PR #7** 👈")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_github_provider_skips_identical_body_update() {
+        let server = MockServer::start().await;
+        let mut pr = pr_json(7, "feature", "main");
+        pr["body"] = serde_json::json!("Same body");
+        Mock::given(method("GET"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test-owner/test-repo/pulls/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = boxed_client(&server);
+        assert!(!provider
+            .update_pr_body_if_changed(7, "Same body")
+            .await
+            .unwrap());
+        assert!(provider
+            .update_pr_body_if_changed(7, "New body")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_github_provider_current_user() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "login": "octocat" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = boxed_client(&server);
+        assert_eq!(provider.current_user().await.unwrap(), "octocat");
+    }
+}
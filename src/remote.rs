@@ -1,5 +1,8 @@
 use crate::config::Config;
 use crate::git::GitRepo;
+use crate::github::GitHubClient;
+use crate::gitlab::GitLabClient;
+use crate::provider::{ProviderKind, RemoteProvider};
 use anyhow::{Context, Result};
 use git2::{ConfigLevel, Repository};
 use std::path::Path;
@@ -12,6 +15,7 @@ pub struct RemoteInfo {
     pub repo: String,
     pub base_url: String,
     pub api_base_url: Option<String>,
+    pub provider: ProviderKind,
 }
 
 impl RemoteInfo {
@@ -20,6 +24,7 @@ impl RemoteInfo {
         let url = get_remote_url(repo.workdir()?, &name)?;
         let (host, path) = parse_remote_url(&url)?;
         let (namespace, repo_name) = split_namespace_repo(&path)?;
+        let provider = ProviderKind::detect(&host, config.remote.provider.as_deref())?;
 
         let configured_base = config.remote_base_url().trim_end_matches('/');
         let base_url = if configured_base.is_empty()
@@ -32,6 +37,8 @@ impl RemoteInfo {
 
         let api_base_url = if let Some(api) = &config.remote.api_base_url {
             Some(api.clone())
+        } else if provider == ProviderKind::GitLab {
+            Some(format!("{}/api/v4", base_url))
         } else if base_url == "https://github.com" {
             Some("https://api.github.com".to_string())
         } else {
@@ -45,9 +52,36 @@ impl RemoteInfo {
             repo: repo_name,
             base_url,
             api_base_url,
+            provider,
         })
     }
 
+    /// Build an API client for the detected forge. Must run inside a tokio runtime.
+    pub fn provider(&self) -> Result<Box<dyn RemoteProvider>> {
+        match self.provider {
+            ProviderKind::GitHub => Ok(Box::new(GitHubClient::new(
+                self.owner(),
+                &self.repo,
+                self.api_base_url.clone(),
+            )?)),
+            ProviderKind::GitLab => {
+                let api_base_url = self
+                    .api_base_url
+                    .clone()
+                    .unwrap_or_else(|| format!("{}/api/v4", self.base_url));
+                Ok(Box::new(GitLabClient::new(
+                    &self.namespace,
+                    &self.repo,
+                    &api_base_url,
+                )?))
+            }
+            other => anyhow::bail!(
+                "{} remotes are not supported yet. Set remote.provider = \"github\" if this is a GitHub host.",
+                other.display_name()
+            ),
+        }
+    }
+
     pub fn owner(&self) -> &str {
         self.namespace.as_str()
     }
@@ -57,7 +91,10 @@ impl RemoteInfo {
     }
 
     pub fn pr_url(&self, number: u64) -> String {
-        format!("{}/pull/{}", self.repo_url(), number)
+        match self.provider {
+            ProviderKind::GitLab => format!("{}/-/merge_requests/{}", self.repo_url(), number),
+            _ => format!("{}/pull/{}", self.repo_url(), number),
+        }
     }
 }
 
@@ -364,6 +401,7 @@ mod tests {
             repo: "myrepo".to_string(),
             base_url: "https://github.com".to_string(),
             api_base_url: Some("https://api.github.com".to_string()),
+            provider: ProviderKind::GitHub,
         };
         assert_eq!(info.owner(), "myorg");
     }
@@ -376,6 +414,7 @@ mod tests {
             repo: "myrepo".to_string(),
             base_url: "https://github.com".to_string(),
             api_base_url: Some("https://api.github.com".to_string()),
+            provider: ProviderKind::GitHub,
         };
         assert_eq!(info.repo_url(), "https://github.com/myorg/myrepo");
     }
@@ -388,6 +427,7 @@ mod tests {
             repo: "myrepo".to_string(),
             base_url: "https://github.com".to_string(),
             api_base_url: Some("https://api.github.com".to_string()),
+            provider: ProviderKind::GitHub,
         };
         assert_eq!(info.pr_url(42), "https://github.com/myorg/myrepo/pull/42");
     }
@@ -400,8 +440,13 @@ mod tests {
             repo: "project".to_string(),
             base_url: "https://gitlab.com".to_string(),
             api_base_url: None,
+            provider: ProviderKind::GitLab,
         };
         assert_eq!(info.repo_url(), "https://gitlab.com/org/team/project");
+        assert_eq!(
+            info.pr_url(7),
+            "https://gitlab.com/org/team/project/-/merge_requests/7"
+        );
     }
 
     #[test]
@@ -18,30 +18,50 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use std::io::{self, IsTerminal};
 use std::process::Command;
 use std::time::Duration;
 
 /// Run the TUI
 pub fn run() -> Result<()> {
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Raw mode escape codes would garble a pipe or a redirected file
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        anyhow::bail!(
+            "The interactive stack view needs a terminal. Use `stax status` or `stax log` for plain output."
+        );
+    }
 
-    // Create app state
-    let result = App::new().and_then(|mut app| run_app(&mut terminal, &mut app));
+    // Hand the terminal back before a panic message is printed
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    enable_raw_mode()?;
+    let result = run_in_terminal();
+    restore_terminal();
+    let _ = std::panic::take_hook();
 
     result
 }
 
+/// Set up the alternate screen and run the app; the caller restores the terminal
+fn run_in_terminal() -> Result<()> {
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    // Create app state
+    let mut app = App::new()?;
+    run_app(&mut terminal, &mut app)
+}
+
+/// Leave raw mode and the alternate screen; safe to call more than once
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
+}
+
 /// Main event loop
 fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
     loop {
@@ -192,10 +212,8 @@ fn handle_normal_action(app: &mut App, action: KeyAction) -> Result<()> {
                 }
             }
         }
-        KeyAction::ReorderMode => {
-            if app.init_reorder_state() {
-                app.mode = Mode::Reorder;
-            }
+        KeyAction::ReorderMode if app.init_reorder_state() => {
+            app.mode = Mode::Reorder;
         }
         _ => {}
     }
@@ -349,15 +367,11 @@ fn handle_input_action(app: &mut App, action: KeyAction, input_action: &InputAct
                 app.input_cursor = 0;
             }
         }
-        KeyAction::Left => {
-            if app.input_cursor > 0 {
-                app.input_cursor -= 1;
-            }
+        KeyAction::Left if app.input_cursor > 0 => {
+            app.input_cursor -= 1;
         }
-        KeyAction::Right => {
-            if app.input_cursor < app.input_buffer.len() {
-                app.input_cursor += 1;
-            }
+        KeyAction::Right if app.input_cursor < app.input_buffer.len() => {
+            app.input_cursor += 1;
         }
         KeyAction::Home => {
             app.input_cursor = 0;
@@ -369,11 +383,9 @@ fn handle_input_action(app: &mut App, action: KeyAction, input_action: &InputAct
             app.input_buffer.insert(app.input_cursor, c);
             app.input_cursor += 1;
         }
-        KeyAction::Backspace => {
-            if app.input_cursor > 0 {
-                app.input_cursor -= 1;
-                app.input_buffer.remove(app.input_cursor);
-            }
+        KeyAction::Backspace if app.input_cursor > 0 => {
+            app.input_cursor -= 1;
+            app.input_buffer.remove(app.input_cursor);
         }
         _ => {}
     }
@@ -1,8 +1,5 @@
-use crate::config::Config;
-use crate::engine::{BranchMetadata, Stack};
+use crate::engine::Stack;
 use crate::git::GitRepo;
-use crate::ops::receipt::{OpKind, PlanSummary};
-use crate::ops::tx::{self, Transaction};
 use anyhow::{Context, Result};
 
 /// A commit to display in the split UI
@@ -273,83 +270,16 @@ impl SplitApp {
 
     /// Execute the split operation
     pub fn execute_split(&mut self) -> Result<()> {
-        if self.split_points.is_empty() {
-            return Ok(());
-        }
-
-        let config = Config::load()?;
-        let _ = config; // Reserved for future branch name formatting
-
-        // Collect branch names to create
-        let new_branches: Vec<String> = self
+        let tips: Vec<(String, String)> = self
             .split_points
             .iter()
-            .map(|sp| sp.branch_name.clone())
+            .map(|sp| {
+                (
+                    self.commits[sp.after_commit_index].sha.clone(),
+                    sp.branch_name.clone(),
+                )
+            })
             .collect();
-
-        // Begin transaction
-        let mut tx = Transaction::begin(OpKind::Split, &self.repo, false)?;
-        let mut affected = new_branches.clone();
-        affected.push(self.current_branch.clone());
-        tx.plan_branches(&self.repo, &affected)?;
-
-        let summary = PlanSummary {
-            branches_to_rebase: 0,
-            branches_to_push: 0,
-            description: vec![format!("Split into {} new branches", new_branches.len())],
-        };
-        tx::print_plan(tx.kind(), &summary, false);
-        tx.set_plan_summary(summary);
-        tx.snapshot()?;
-
-        // Create branches at split points
-        let mut prev_parent = self.parent_branch.clone();
-        let mut prev_idx = 0;
-
-        for sp in &self.split_points {
-            // The commit at sp.after_commit_index becomes the tip of the new branch
-            let commit_sha = &self.commits[sp.after_commit_index].sha;
-
-            // Create branch at this commit
-            self.repo
-                .create_branch_at_commit(&sp.branch_name, commit_sha)?;
-
-            // Create metadata for the new branch
-            let parent_rev = self.repo.branch_commit(&prev_parent)?;
-            let meta = BranchMetadata::new(&prev_parent, &parent_rev);
-            meta.write(self.repo.inner(), &sp.branch_name)?;
-
-            println!(
-                "Created branch '{}' with {} commits",
-                sp.branch_name,
-                sp.after_commit_index - prev_idx + 1
-            );
-
-            prev_parent = sp.branch_name.clone();
-            prev_idx = sp.after_commit_index + 1;
-        }
-
-        // Update current branch's parent to the last split branch
-        if let Some(last_split) = self.split_points.last() {
-            let new_parent = &last_split.branch_name;
-            let parent_rev = self.repo.branch_commit(new_parent)?;
-
-            // Read and update existing metadata
-            if let Some(mut meta) = BranchMetadata::read(self.repo.inner(), &self.current_branch)? {
-                meta.parent_branch_name = new_parent.clone();
-                meta.parent_branch_revision = parent_rev;
-                meta.write(self.repo.inner(), &self.current_branch)?;
-            }
-
-            println!(
-                "Updated '{}' parent to '{}'",
-                self.current_branch, new_parent
-            );
-        }
-
-        tx.finish_ok()?;
-        println!("\nSplit complete! Use `stax status` to see the new stack structure.");
-
-        Ok(())
+        crate::commands::split::apply(&self.repo, &self.current_branch, &self.parent_branch, &tips)
     }
 }
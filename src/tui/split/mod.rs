@@ -133,21 +133,15 @@ fn handle_naming_key(app: &mut SplitApp, code: KeyCode) -> Result<()> {
             app.input_buffer.insert(app.input_cursor, c);
             app.input_cursor += 1;
         }
-        KeyCode::Backspace => {
-            if app.input_cursor > 0 {
-                app.input_cursor -= 1;
-                app.input_buffer.remove(app.input_cursor);
-            }
+        KeyCode::Backspace if app.input_cursor > 0 => {
+            app.input_cursor -= 1;
+            app.input_buffer.remove(app.input_cursor);
         }
-        KeyCode::Left => {
-            if app.input_cursor > 0 {
-                app.input_cursor -= 1;
-            }
+        KeyCode::Left if app.input_cursor > 0 => {
+            app.input_cursor -= 1;
         }
-        KeyCode::Right => {
-            if app.input_cursor < app.input_buffer.len() {
-                app.input_cursor += 1;
-            }
+        KeyCode::Right if app.input_cursor < app.input_buffer.len() => {
+            app.input_cursor += 1;
         }
         _ => {}
     }
@@ -76,11 +76,9 @@ pub fn render_diff(f: &mut Frame, app: &App, area: Rect) {
 
             let total_changes = stat.additions + stat.deletions;
             let bar_width = 30.min(total_changes);
-            let add_bars = if total_changes > 0 {
-                (stat.additions * bar_width) / total_changes
-            } else {
-                0
-            };
+            let add_bars = (stat.additions * bar_width)
+                .checked_div(total_changes)
+                .unwrap_or(0);
             let del_bars = bar_width.saturating_sub(add_bars);
 
             all_content.push(Line::from(vec![
@@ -1,3 +1,4 @@
+use crate::config::Config;
 use colored::Colorize;
 use std::thread;
 use std::time::Duration;
@@ -30,6 +31,28 @@ impl InstallMethod {
     }
 }
 
+/// Whether update checks may run. Disabled by `--no-update-check`, a non-empty
+/// `STAX_NO_UPDATE_CHECK` (other than "0"), or `ui.update_check = false`.
+pub fn is_enabled(no_update_check: bool) -> bool {
+    if no_update_check {
+        return false;
+    }
+    if std::env::var("STAX_NO_UPDATE_CHECK").is_ok_and(|v| !v.is_empty() && v != "0") {
+        return false;
+    }
+    Config::load().map(|c| c.ui.update_check).unwrap_or(true)
+}
+
+/// Show any cached update notice and refresh the cache for the next run.
+/// Does nothing (and spawns no thread) when checks are disabled.
+pub fn notify(enabled: bool) {
+    if !enabled {
+        return;
+    }
+    show_update_notification();
+    check_in_background();
+}
+
 /// Spawn a background thread to check for updates.
 /// This is non-blocking and won't affect CLI performance.
 /// Results are cached by update-informer for 24 hours.
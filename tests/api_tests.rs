@@ -0,0 +1,74 @@
+//! Library API tests
+//!
+//! Tests for `stax::api`, driving status and restack in-process instead of
+//! through the CLI.
+
+mod common;
+
+use common::TestRepo;
+use stax::api::{self, RestackOutcome, StaxError};
+
+#[test]
+fn test_api_status_reports_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["api-a", "api-b"]);
+    let (a, b) = (&branches[0], &branches[1]);
+
+    let git = api::open(&repo.path()).unwrap();
+    let status = api::status(&git).unwrap();
+
+    assert_eq!(status.trunk, "main");
+    assert_eq!(status.current, *b);
+    let names: Vec<&str> = status.branches.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["main", a.as_str(), b.as_str()]);
+
+    let top = status.branch(b).unwrap();
+    assert!(top.is_current);
+    assert_eq!(top.parent.as_deref(), Some(a.as_str()));
+    assert_eq!(top.ahead, 1);
+    assert!(!top.needs_restack);
+    assert_eq!(status.branch(a).unwrap().children, vec![b.clone()]);
+}
+
+#[test]
+fn test_api_restack_rebases_descendants() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["api-r1", "api-r2"]);
+    let (a, b) = (&branches[0], &branches[1]);
+
+    repo.run_stax(&["checkout", a]);
+    repo.create_file("extra.txt", "extra");
+    repo.commit("Extra commit on bottom");
+
+    let git = api::open(&repo.path()).unwrap();
+    assert!(api::status(&git).unwrap().branch(b).unwrap().needs_restack);
+
+    let outcome = api::restack(&git, a).unwrap();
+    assert_eq!(outcome, RestackOutcome::Restacked(vec![b.clone()]));
+    assert_eq!(repo.current_branch(), *a);
+
+    let status = api::status(&git).unwrap();
+    assert!(!status.branch(b).unwrap().needs_restack);
+    assert_eq!(status.branch(b).unwrap().behind, 0);
+
+    // Like the CLI, the fork point (status diff base) moves to the new parent tip
+    let metadata = repo.git(&["show", &format!("refs/branch-metadata/{}", b)]);
+    let metadata: serde_json::Value = serde_json::from_str(&TestRepo::stdout(&metadata)).unwrap();
+    assert_eq!(metadata["forkPoint"], repo.get_commit_sha(a).as_str());
+
+    assert_eq!(api::restack(&git, b).unwrap(), RestackOutcome::UpToDate);
+}
+
+#[test]
+fn test_api_restack_untracked_branch_is_typed_error() {
+    let repo = TestRepo::new();
+    repo.git(&["checkout", "-b", "plain-git"]);
+
+    let git = api::open(&repo.path()).unwrap();
+    let err = api::restack(&git, "plain-git").unwrap_err();
+
+    assert_eq!(
+        err.downcast_ref::<StaxError>(),
+        Some(&StaxError::NotTracked("plain-git".to_string()))
+    );
+}
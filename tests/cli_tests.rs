@@ -149,6 +149,26 @@ fn test_config_command() {
     assert!(stdout.contains(".config/stax/config.toml"));
 }
 
+#[test]
+fn test_config_path_prints_config_and_credentials() {
+    let output = stax(&["config", "path"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(".config/stax/config.toml"));
+    assert!(stdout.contains(".config/stax/.credentials"));
+}
+
+#[test]
+fn test_submit_comment_only_conflicts_with_no_pr() {
+    let output = stax(&["submit", "--help"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("--comment-only"));
+
+    let output = stax(&["submit", "--comment-only", "--no-pr"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+}
+
 #[test]
 fn test_status_help_flags() {
     let output = stax(&["status", "--help"]);
@@ -549,6 +569,31 @@ fn gt_parity_pr_command() {
     assert!(stdout.contains("PR") || stdout.contains("browser"));
 }
 
+#[test]
+fn test_no_update_check_flag_is_global() {
+    let output = stax(&["status", "--no-update-check", "--help"]);
+    assert!(output.status.success());
+    let output = stax(&["--no-update-check", "--help"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_pr_checks_help() {
+    let output = stax(&["pr", "checks", "--help"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--json"));
+}
+
+#[test]
+fn test_pr_close_and_reopen_listed() {
+    let output = stax(&["pr", "--help"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("close"));
+    assert!(stdout.contains("reopen"));
+}
+
 #[test]
 fn gt_parity_submit_command() {
     // gt submit -> stax submit
@@ -669,3 +714,16 @@ fn test_ci_command_flags() {
         stdout
     );
 }
+
+#[test]
+fn test_completions_bash() {
+    let output = stax(&["completions", "bash"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("_stax()"), "stdout: {}", stdout);
+    assert!(stdout.contains("restack"));
+
+    // Hidden from the main help
+    let help = stax(&["--help"]);
+    assert!(!String::from_utf8_lossy(&help.stdout).contains("completions"));
+}
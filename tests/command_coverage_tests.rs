@@ -37,6 +37,25 @@ fn test_checkout_nonexistent_branch() {
     output.assert_failure();
 }
 
+#[test]
+fn test_checkout_create_makes_missing_branch() {
+    let repo = TestRepo::new();
+
+    repo.run_stax(&["checkout", "fresh-start"]).assert_failure();
+
+    let output = repo.run_stax(&["checkout", "--create", "fresh-start"]);
+    output.assert_success();
+    assert!(repo.current_branch_contains("fresh-start"));
+    assert_eq!(repo.get_current_parent().as_deref(), Some("main"));
+
+    // An existing branch is just switched to
+    let created = repo.current_branch();
+    repo.run_stax(&["t"]).assert_success();
+    repo.run_stax(&["checkout", "--create", &created])
+        .assert_success();
+    assert_eq!(repo.current_branch(), created);
+}
+
 #[test]
 fn test_checkout_to_tracked_branch() {
     let repo = TestRepo::new();
@@ -142,6 +161,32 @@ fn test_restack_quiet_flag() {
     output.assert_success();
 }
 
+#[test]
+fn test_restack_verbose_prints_timing() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["feature-a", "feature-b"]);
+
+    repo.run_stax(&["checkout", &branches[0]]);
+    repo.create_file("extra.txt", "extra content");
+    repo.commit("Extra commit");
+
+    repo.run_stax(&["checkout", &branches[1]]);
+    let output = repo.run_stax(&["restack", "--verbose"]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains(&format!("restacked {} in ", branches[1])));
+    assert!(stdout.contains("Restacked 1 branch in "));
+
+    // Timing lines are only printed with --verbose
+    repo.run_stax(&["checkout", &branches[0]]);
+    repo.create_file("more.txt", "more content");
+    repo.commit("More commit");
+    repo.run_stax(&["checkout", &branches[1]]);
+    let output = repo.run_stax(&["restack"]);
+    output.assert_success();
+    assert!(!TestRepo::stdout(&output).contains(&format!("restacked {} in ", branches[1])));
+}
+
 #[test]
 fn test_restack_on_trunk() {
     let repo = TestRepo::new();
@@ -162,6 +207,137 @@ fn test_restack_continue_no_rebase() {
     let _ = output;
 }
 
+#[test]
+fn test_restack_interactive_applies_edited_todo() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["inter-a", "inter-b"]);
+    repo.create_file("drop.txt", "drop me");
+    repo.commit("Drop me");
+
+    // Move trunk so the whole stack is rebased
+    repo.run_stax(&["checkout", "main"]);
+    repo.create_file("trunk.txt", "trunk");
+    repo.commit("Trunk commit");
+    repo.run_stax(&["checkout", &branches[1]]);
+
+    repo.git(&[
+        "config",
+        "sequence.editor",
+        "sed -i -e '/Drop me/s/^pick/drop/'",
+    ]);
+    let output = repo.run_stax(&["restack", "--interactive"]);
+    output.assert_success();
+
+    // Both branches sit on the new trunk and the dropped commit is gone
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", branches[0])]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", branches[1])]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "2");
+
+    // Metadata was refreshed, so nothing is left to restack
+    let json = repo.get_status_json();
+    let stale = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|b| b["needs_restack"].as_bool() == Some(true));
+    assert!(!stale);
+}
+
+#[test]
+fn test_restack_update_refs_moves_whole_stack_in_one_rebase() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["refs-a", "refs-b", "refs-c"]);
+
+    repo.run_stax(&["checkout", "main"]);
+    repo.create_file("trunk.txt", "trunk");
+    repo.commit("Trunk commit");
+    repo.run_stax(&["checkout", &branches[1]]);
+
+    let output = repo.run_stax(&["restack", "--update-refs", "--quiet"]);
+    output.assert_success();
+    assert_eq!(repo.current_branch(), branches[1]);
+
+    for (i, branch) in branches.iter().enumerate() {
+        let count = repo.git(&["rev-list", "--count", &format!("main..{}", branch)]);
+        assert_eq!(TestRepo::stdout(&count).trim(), (i + 1).to_string());
+    }
+
+    let json = repo.get_status_json();
+    let stale = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|b| b["needs_restack"].as_bool() == Some(true));
+    assert!(!stale);
+}
+
+#[test]
+fn test_restack_interactive_rejects_forked_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["ifork-a", "ifork-b"]);
+    repo.run_stax(&["checkout", &branches[0]]);
+    repo.run_stax(&["bc", "ifork-c"]);
+
+    let output = repo.run_stax(&["restack", "--interactive"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("linear stacks"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_restack_no_hooks_skips_pre_rebase_hook() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["hooks-a"]);
+    repo.run_stax(&["checkout", "main"]);
+    repo.create_file("trunk.txt", "trunk");
+    repo.commit("Trunk commit");
+    repo.run_stax(&["checkout", &branches[0]]);
+
+    let hook = repo.path().join(".git/hooks/pre-rebase");
+    std::fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    std::fs::write(&hook, "#!/bin/sh\nexit 1\n").unwrap();
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    // The hook blocks a normal restack
+    let output = repo.run_stax(&["restack", "--quiet"]);
+    output.assert_failure();
+
+    let output = repo.run_stax(&["restack", "--quiet", "--no-hooks"]);
+    output.assert_success();
+    let merge_base = repo.git(&["merge-base", "main", &branches[0]]);
+    assert_eq!(
+        TestRepo::stdout(&merge_base).trim(),
+        repo.get_commit_sha("main")
+    );
+}
+
+#[test]
+fn test_restack_strategy_option_resolves_conflict() {
+    let repo = TestRepo::new();
+    repo.create_file("shared.txt", "base");
+    repo.commit("Add shared file");
+
+    let branches = repo.create_stack(&["strategy-a"]);
+    repo.create_file("shared.txt", "branch");
+    repo.commit("Branch edits shared file");
+
+    repo.run_stax(&["checkout", "main"]);
+    repo.create_file("shared.txt", "trunk");
+    repo.commit("Trunk edits shared file");
+    repo.run_stax(&["checkout", &branches[0]]);
+
+    // During a rebase "theirs" is the commit being replayed, i.e. the branch
+    let output = repo.run_stax(&["restack", "--quiet", "-X", "theirs"]);
+    output.assert_success();
+    assert!(!repo.has_rebase_in_progress());
+
+    let content = repo.git(&["show", &format!("{}:shared.txt", branches[0])]);
+    assert_eq!(TestRepo::stdout(&content).trim(), "branch");
+}
+
 #[test]
 fn test_upstack_restack() {
     let repo = TestRepo::new();
@@ -189,6 +365,33 @@ fn test_undo_no_operations() {
     output.assert_failure();
 }
 
+#[test]
+fn test_undo_prints_plan_and_returns_to_original_branch() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["feature"]);
+    let original = repo.get_commit_sha(&branches[0]);
+
+    repo.run_stax(&["t"]).assert_success();
+    repo.create_file("main.txt", "main update");
+    repo.commit("Main update");
+    repo.run_stax(&["checkout", &branches[0]]).assert_success();
+    repo.run_stax(&["restack", "--quiet"]).assert_success();
+    assert_ne!(repo.get_commit_sha(&branches[0]), original);
+
+    // Dirty trees are refused rather than stashed
+    repo.create_file("dirty.txt", "uncommitted");
+    repo.git(&["add", "dirty.txt"]);
+    repo.run_stax(&["undo", "--yes"]).assert_failure();
+    repo.git(&["reset", "--hard"]);
+
+    repo.run_stax(&["t"]).assert_success();
+    let output = repo.run_stax(&["undo", "--yes"]);
+    output.assert_success();
+    output.assert_stdout_contains("Plan:");
+    assert_eq!(repo.get_commit_sha(&branches[0]), original);
+    assert_eq!(repo.current_branch(), branches[0]);
+}
+
 #[test]
 fn test_undo_help() {
     let repo = TestRepo::new();
@@ -237,6 +440,49 @@ fn test_doctor_with_stack() {
     output.assert_success();
 }
 
+#[test]
+fn test_bogus_parent_revision_is_reported_and_fixed() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["corrupt"]);
+    let metadata_ref = format!("refs/branch-metadata/{}", branches[0]);
+    let main_sha = TestRepo::stdout(&repo.git(&["rev-parse", "main"]))
+        .trim()
+        .to_string();
+
+    // Corrupt the stored parent revision behind stax's back
+    let json = TestRepo::stdout(&repo.git(&["show", &metadata_ref]));
+    let corrupted = json.replace(&main_sha, "not-a-sha");
+    let blob_path = repo.path().join(".git/corrupt-metadata.json");
+    std::fs::write(&blob_path, corrupted).unwrap();
+    let blob = TestRepo::stdout(&repo.git(&["hash-object", "-w", blob_path.to_str().unwrap()]))
+        .trim()
+        .to_string();
+    repo.git(&["update-ref", &metadata_ref, &blob]);
+
+    // Reading the stack stays graceful and flags the branch
+    let json = repo.get_status_json();
+    let branch = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["name"] == branches[0].as_str())
+        .expect("corrupted branch still listed");
+    assert_eq!(branch["needs_restack"], true);
+
+    let output = repo.run_stax(&["doctor"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("invalid parent revision"));
+
+    let output = repo.run_stax(&["stack", "fix"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("Recomputed parent revision"));
+
+    let json = TestRepo::stdout(&repo.git(&["show", &metadata_ref]));
+    assert!(json.contains(&main_sha), "metadata: {}", json);
+    let output = repo.run_stax(&["doctor"]);
+    assert!(!TestRepo::stdout(&output).contains("invalid parent revision"));
+}
+
 #[test]
 fn test_doctor_help() {
     let repo = TestRepo::new();
@@ -275,6 +521,66 @@ fn test_log_with_stack() {
     output.assert_success();
 }
 
+#[test]
+fn test_log_reverse_puts_trunk_first() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["feature-a", "feature-b"]);
+
+    let names = |args: &[&str]| -> Vec<String> {
+        let output = repo.run_stax(args);
+        output.assert_success();
+        TestRepo::stdout(&output)
+            .lines()
+            .map(|l| l.split('\t').next().unwrap_or("").to_string())
+            .collect()
+    };
+
+    let default = names(&["log", "--compact"]);
+    assert_eq!(
+        default,
+        vec![branches[1].clone(), branches[0].clone(), "main".into()]
+    );
+
+    let reversed = names(&["log", "--compact", "--reverse"]);
+    assert_eq!(
+        reversed,
+        vec!["main".to_string(), branches[0].clone(), branches[1].clone()]
+    );
+}
+
+#[test]
+fn test_log_sort_name_orders_sibling_stacks() {
+    let repo = TestRepo::new();
+    let zed = repo.create_stack(&["zed", "zed-top"]);
+    repo.run_stax(&["checkout", "main"]).assert_success();
+    let alpha = repo.create_stack(&["alpha"]);
+
+    // By default the larger stack comes first; --sort name goes alphabetical
+    let output = repo.run_stax(&["log", "--compact", "--sort", "name"]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    let alpha_pos = stdout.find(&alpha[0]).unwrap();
+    let zed_pos = stdout.find(&zed[1]).unwrap();
+    assert!(alpha_pos < zed_pos, "{}", stdout);
+}
+
+#[test]
+fn test_tui_without_terminal_errors_clearly() {
+    let repo = TestRepo::new();
+    repo.create_stack(&["feature"]);
+
+    for args in [&["tui"][..], &["stack", "tui"][..]] {
+        let output = repo.run_stax(args);
+        output.assert_failure();
+        assert!(
+            TestRepo::stderr(&output).contains("needs a terminal"),
+            "{:?}: {}",
+            args,
+            TestRepo::stderr(&output)
+        );
+    }
+}
+
 #[test]
 fn test_log_alias_l() {
     let repo = TestRepo::new();
@@ -342,6 +648,20 @@ fn test_diff_help() {
     output.assert_success();
 }
 
+#[test]
+fn test_stack_diff_shows_cumulative_changes() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["first", "second"]);
+    repo.git(&["checkout", &branches[0]]);
+
+    let output = repo.run_stax(&["stack", "diff", "--name-only", "--no-pager"]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains("first.txt"), "stdout: {}", stdout);
+    assert!(stdout.contains("second.txt"), "stdout: {}", stdout);
+    assert!(stdout.contains(&format!("main..{}", branches[1])));
+}
+
 // =============================================================================
 // Range-Diff Command Tests
 // =============================================================================
@@ -475,6 +795,46 @@ fn test_branch_create_stacked() {
     assert!(parent.unwrap().contains("parent"));
 }
 
+#[test]
+fn test_branch_create_auto_tracks_untracked_trunk_child() {
+    let repo = TestRepo::new();
+    repo.git(&["checkout", "-b", "plain-bottom"]);
+    repo.create_file("plain.txt", "plain");
+    repo.commit("Plain git commit");
+
+    let output = repo.run_stax(&["bc", "on-plain"]);
+    output.assert_success();
+    assert_eq!(repo.get_current_parent().as_deref(), Some("plain-bottom"));
+
+    let json = repo.get_status_json();
+    let bottom = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["name"] == "plain-bottom")
+        .expect("plain-bottom should be tracked");
+    assert_eq!(bottom["parent"], "main");
+}
+
+#[test]
+fn test_branch_create_no_auto_track_leaves_parent_untracked() {
+    let repo = TestRepo::new();
+    repo.git(&["checkout", "-b", "plain-bottom"]);
+    repo.create_file("plain.txt", "plain");
+    repo.commit("Plain git commit");
+
+    let output = repo.run_stax(&["bc", "on-plain", "--no-auto-track"]);
+    output.assert_success();
+
+    let json = repo.get_status_json();
+    let tracked = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|b| b["name"] == "plain-bottom");
+    assert!(!tracked);
+}
+
 // =============================================================================
 // Branch Delete Tests
 // =============================================================================
@@ -517,6 +877,66 @@ fn test_branch_delete_with_children() {
     output.assert_failure();
 }
 
+#[test]
+fn test_branch_delete_refuses_unmerged_unpushed_work() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["risky"]);
+    repo.run_stax(&["t"]).assert_success();
+
+    let output = repo.run_stax(&["branch", "delete", &branches[0]]);
+    output.assert_failure();
+    let stderr = TestRepo::stderr(&output);
+    assert!(stderr.contains("neither merged"), "stderr: {}", stderr);
+    assert!(stderr.contains("Commit for risky"), "stderr: {}", stderr);
+    assert!(repo.list_branches().contains(&branches[0]));
+
+    repo.run_stax(&["branch", "delete", &branches[0], "--force"])
+        .assert_success();
+    assert!(!repo.list_branches().contains(&branches[0]));
+}
+
+#[test]
+fn test_branch_delete_allows_merged_branch_without_force() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["landed"]);
+    repo.run_stax(&["t"]).assert_success();
+    repo.git(&["merge", "--ff-only", &branches[0]]);
+
+    let output = repo.run_stax(&["branch", "delete", &branches[0]]);
+    output.assert_success();
+    assert!(!repo.list_branches().contains(&branches[0]));
+}
+
+#[test]
+fn test_branch_delete_respects_protected_branches() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["develop"]);
+    repo.run_stax(&["t"]).assert_success();
+
+    let config = format!("[branch]\nprotected = [\"{}\"]\n", branches[0]);
+    let run = |args: &[&str]| repo.run_stax_with_config(&config, args);
+
+    // Interactive selection never offers protected branches
+    let output = run(&["branch", "delete"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("No branches to delete."));
+
+    let output = run(&["branch", "delete", &branches[0], "--force"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("is protected"));
+    assert!(repo.list_branches().contains(&branches[0]));
+
+    run(&[
+        "branch",
+        "delete",
+        &branches[0],
+        "--force",
+        "--i-really-mean-it",
+    ])
+    .assert_success();
+    assert!(!repo.list_branches().contains(&branches[0]));
+}
+
 // =============================================================================
 // Branch Rename Tests
 // =============================================================================
@@ -556,6 +976,78 @@ fn test_branch_track_untracked() {
     output.assert_success();
 }
 
+#[test]
+fn test_branch_track_and_untrack_json() {
+    let repo = TestRepo::new();
+    repo.git(&["checkout", "-b", "adopted"]);
+    repo.create_file("adopted.txt", "content");
+    repo.commit("Adopted commit");
+    let main_sha = TestRepo::stdout(&repo.git(&["rev-parse", "main"]))
+        .trim()
+        .to_string();
+
+    let output = repo.run_stax(&["branch", "track", "--parent", "main", "--json"]);
+    output.assert_success();
+    let json: serde_json::Value =
+        serde_json::from_str(&TestRepo::stdout(&output)).expect("track --json output");
+    assert_eq!(json["branch"], "adopted");
+    assert_eq!(json["parent"], "main");
+    assert_eq!(json["parent_revision"], main_sha.as_str());
+    assert_eq!(json["status"], "tracked");
+
+    let output = repo.run_stax(&["branch", "track", "--parent", "main", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    assert_eq!(json["status"], "already_tracked");
+
+    let output = repo.run_stax(&["branch", "untrack", "--json"]);
+    output.assert_success();
+    let json: serde_json::Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    assert_eq!(json["branch"], "adopted");
+    assert_eq!(json["parent"], "main");
+    assert_eq!(json["status"], "untracked");
+
+    // Failures still print JSON on stdout, with a non-zero exit
+    let output = repo.run_stax(&["branch", "track", "--parent", "missing", "--json"]);
+    output.assert_failure();
+    let json: serde_json::Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    assert_eq!(json["status"], "error");
+    assert!(json["error"].as_str().unwrap().contains("missing"));
+}
+
+#[test]
+fn test_branch_untrack_all_removes_metadata_and_keeps_branches() {
+    let repo = TestRepo::new();
+    repo.create_stack(&["first", "second"]);
+    let metadata_refs = |repo: &TestRepo| {
+        TestRepo::stdout(&repo.git(&[
+            "for-each-ref",
+            "--format=%(refname)",
+            "refs/branch-metadata",
+        ]))
+        .lines()
+        .count()
+    };
+    assert_eq!(metadata_refs(&repo), 2);
+
+    let output = repo.run_stax(&["branch", "untrack", "--all", "--dry-run"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("Would remove stax metadata for"));
+    assert_eq!(metadata_refs(&repo), 2);
+
+    // Without a terminal to confirm on, --yes is required
+    let output = repo.run_stax(&["branch", "untrack", "--all"]);
+    output.assert_failure();
+    assert_eq!(metadata_refs(&repo), 2);
+
+    let output = repo.run_stax(&["branch", "untrack", "--all", "--yes"]);
+    output.assert_success();
+    assert_eq!(metadata_refs(&repo), 0);
+
+    let branches = repo.list_branches();
+    assert!(branches.iter().any(|b| b.contains("first")));
+    assert!(branches.iter().any(|b| b.contains("second")));
+}
+
 #[test]
 fn test_branch_track_help() {
     let repo = TestRepo::new();
@@ -727,6 +1219,70 @@ fn test_create_alias_c() {
     assert!(repo.current_branch_contains("new-feature"));
 }
 
+#[test]
+fn test_create_dry_run_prints_formatted_name_without_creating() {
+    let repo = TestRepo::new();
+    let branches_before = repo.list_branches();
+
+    let output = repo.run_stax(&["create", "Add Login Page", "--dry-run"]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains("Would create branch"), "{}", stdout);
+    let previewed = stdout
+        .split('\'')
+        .nth(1)
+        .expect("dry run should quote the branch name")
+        .to_string();
+
+    assert_eq!(repo.list_branches(), branches_before);
+    assert_eq!(repo.current_branch(), "main");
+
+    repo.run_stax(&["create", "Add Login Page"]).assert_success();
+    assert_eq!(repo.current_branch(), previewed);
+}
+
+#[test]
+fn test_create_track_only_stacks_existing_branch() {
+    let repo = TestRepo::new();
+    // Use whatever name create would produce, so a configured prefix still collides
+    let output = repo.run_stax(&["create", "made-with-git", "--dry-run"]);
+    let name = TestRepo::stdout(&output)
+        .split('\'')
+        .nth(1)
+        .expect("dry run should quote the branch name")
+        .to_string();
+    repo.git(&["branch", &name]);
+
+    let output = repo.run_stax(&["create", "made-with-git"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("--track-only"));
+
+    let output = repo.run_stax(&["create", "made-with-git", "--track-only"]);
+    output.assert_success();
+    assert_eq!(repo.current_branch(), name);
+    assert_eq!(repo.get_current_parent(), Some("main".to_string()));
+}
+
+#[test]
+fn test_create_refuses_trunk_and_reserved_names() {
+    let repo = TestRepo::new();
+    let branches_before = repo.list_branches();
+
+    let output = repo.run_stax(&["create", "main"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("trunk"));
+
+    let output = repo.run_stax(&["create", "main", "--dry-run"]);
+    output.assert_failure();
+
+    let output = repo.run_stax(&["create", "HEAD"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("reserved"));
+
+    assert_eq!(repo.list_branches(), branches_before);
+    assert_eq!(repo.current_branch(), "main");
+}
+
 #[test]
 fn test_create_help() {
     let repo = TestRepo::new();
@@ -776,3 +1332,138 @@ fn test_status_json_basic() {
     assert!(json["trunk"].is_string());
     assert!(json["branches"].is_array());
 }
+
+#[test]
+fn test_status_reports_uncommitted_changes() {
+    let repo = TestRepo::new();
+    repo.create_stack(&["dirty-feature"]);
+
+    let json = repo.get_status_json();
+    assert_eq!(json["dirty"], false);
+
+    repo.create_file("scratch.txt", "not committed yet");
+
+    let json = repo.get_status_json();
+    assert_eq!(json["dirty"], true);
+
+    let output = repo.run_stax(&["status"]);
+    output.assert_success();
+    output.assert_stdout_contains("uncommitted changes");
+}
+
+#[test]
+fn test_status_filter_narrows_branches() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["filter-a", "filter-b"]);
+
+    // Moving filter-a leaves filter-b needing a restack
+    repo.run_stax(&["checkout", &branches[0]]);
+    repo.create_file("extra.txt", "extra");
+    repo.commit("Extra commit");
+
+    let names = |args: &[&str]| -> Vec<String> {
+        let output = repo.run_stax(args);
+        output.assert_success();
+        let json: serde_json::Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+        json["branches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| b["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        names(&["status", "--json", "--filter", "needs-restack"]),
+        vec![branches[1].clone()]
+    );
+    assert_eq!(
+        names(&["status", "--json", "--filter", "no-pr,needs-restack"]),
+        vec![branches[1].clone()]
+    );
+    assert!(names(&["status", "--json", "--filter", "has-pr"]).is_empty());
+
+    repo.create_file("scratch.txt", "not committed yet");
+    assert_eq!(
+        names(&["status", "--json", "--filter", "dirty"]),
+        vec![branches[0].clone()]
+    );
+
+    let output = repo.run_stax(&["status", "--filter", "bogus"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("Invalid filter"));
+}
+
+#[test]
+fn test_status_sort_orders_sibling_stacks() {
+    let repo = TestRepo::new();
+    let alpha = repo.create_stack(&["alpha-sort"]);
+    repo.run_stax(&["t"]).assert_success();
+    let zeta = repo.create_stack(&["zeta-sort"]);
+
+    let order = |args: &[&str]| -> Vec<String> {
+        let output = repo.run_stax(args);
+        output.assert_success();
+        let json: serde_json::Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+        json["branches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|b| b["name"].as_str().map(str::to_string))
+            .filter(|name| name == &alpha[0] || name == &zeta[0])
+            .collect()
+    };
+
+    // Default stack order puts the current branch's stack first
+    assert_eq!(
+        order(&["status", "--json"]),
+        vec![zeta[0].clone(), alpha[0].clone()]
+    );
+    assert_eq!(
+        order(&["status", "--json", "--sort", "name"]),
+        vec![alpha[0].clone(), zeta[0].clone()]
+    );
+
+    repo.run_stax(&["status", "--sort", "size"])
+        .assert_failure();
+}
+
+// =============================================================================
+// Trunk Detection
+// =============================================================================
+
+#[test]
+fn test_trunk_detected_as_master() {
+    let repo = TestRepo::new();
+    repo.git(&["branch", "-m", "main", "master"]);
+
+    let branches = repo.create_stack(&["feature"]);
+    repo.run_stax(&["t"]).assert_success();
+    assert_eq!(repo.current_branch(), "master");
+
+    repo.run_stax(&["checkout", &branches[0]]).assert_success();
+    repo.run_stax(&["down"]).assert_success();
+    assert_eq!(repo.current_branch(), "master");
+}
+
+#[test]
+fn test_trunk_follows_remote_head_then_config() {
+    let repo = TestRepo::new();
+    repo.git(&["branch", "develop"]);
+    repo.git(&["update-ref", "refs/remotes/origin/develop", "develop"]);
+    repo.git(&[
+        "symbolic-ref",
+        "refs/remotes/origin/HEAD",
+        "refs/remotes/origin/develop",
+    ]);
+
+    // origin/HEAD beats the main/master fallback
+    repo.run_stax(&["t"]).assert_success();
+    assert_eq!(repo.current_branch(), "develop");
+
+    // An explicit remote.trunk beats both, even after init stored a trunk
+    repo.git(&["branch", "integration"]);
+    let output = repo.run_stax_with_config("[remote]\ntrunk = \"integration\"\n", &["t"]);
+    output.assert_success();
+    assert_eq!(repo.current_branch(), "integration");
+}
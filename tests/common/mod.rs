@@ -265,6 +265,21 @@ impl TestRepo {
             .expect("Failed to execute stax")
     }
 
+    /// Run a stax command with `config` as its config.toml, via a throwaway XDG_CONFIG_HOME
+    pub fn run_stax_with_config(&self, config: &str, args: &[&str]) -> Output {
+        let config_home = TempDir::new().expect("Failed to create config dir");
+        let config_dir = config_home.path().join("stax");
+        fs::create_dir_all(&config_dir).expect("Failed to create stax config dir");
+        fs::write(config_dir.join("config.toml"), config).expect("Failed to write config");
+
+        Command::new(stax_bin())
+            .args(args)
+            .current_dir(self.path())
+            .env("XDG_CONFIG_HOME", config_home.path())
+            .output()
+            .expect("Failed to execute stax")
+    }
+
     /// Run a stax command in a specific directory
     pub fn run_stax_in(&self, cwd: &Path, args: &[&str]) -> Output {
         Command::new(stax_bin())
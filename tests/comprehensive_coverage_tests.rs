@@ -114,6 +114,30 @@ fn test_branch_create_alias_bc() {
     output.assert_success();
 }
 
+#[test]
+fn test_branch_create_parent_stacks_on_other_branch() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["base-a", "base-b"]);
+
+    // From the top of the stack, branch off the bottom without checking it out
+    let output = repo.run_stax(&["create", "side-c", "--parent", &branches[0]]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(repo.current_branch_contains("side-c"));
+    assert_eq!(
+        repo.get_current_parent().as_deref(),
+        Some(branches[0].as_str())
+    );
+    assert_eq!(repo.head_sha(), repo.get_commit_sha(&branches[0]));
+
+    let output = repo.run_stax(&["create", "orphan", "--parent", "does-not-exist"]);
+    assert!(!output.status.success());
+    assert!(TestRepo::stderr(&output).contains("does not exist"));
+}
+
 #[test]
 fn test_branch_delete_alias_bd() {
     let repo = TestRepo::new();
@@ -219,6 +243,80 @@ fn test_diff_with_multiple_branches() {
     output.assert_success();
 }
 
+#[test]
+fn test_diff_name_only_lists_files() {
+    let repo = TestRepo::new();
+    repo.create_stack(&["names-a", "names-b"]);
+
+    let output = repo.run_stax(&["diff", "--name-only"]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains("names-a.txt"));
+    assert!(stdout.contains("names-b.txt"));
+    assert!(!stdout.contains("insertion"));
+}
+
+#[test]
+fn test_diff_word_diff_marks_changed_words() {
+    let repo = TestRepo::new();
+    repo.run_stax(&["bc", "words"]).assert_success();
+    repo.create_file("notes.md", "the quick brown fox\n");
+    repo.commit("Add notes");
+    repo.run_stax(&["bc", "words-edit"]).assert_success();
+    repo.create_file("notes.md", "the slow brown fox\n");
+    repo.commit("Edit notes");
+
+    let output = repo.run_stax(&["diff", "--word-diff"]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains("[-quick-]{+slow+}"));
+    assert!(!stdout.contains("insertion"));
+
+    let output = repo.run_stax(&["diff", "--word-diff", "--name-only"]);
+    output.assert_failure();
+}
+
+#[test]
+fn test_diff_range_overrides_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["range-a", "range-b"]);
+
+    let range = format!("main..{}", branches[0]);
+    let output = repo.run_stax(&["diff", "--range", &range, "--name-only"]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains("range-a.txt"));
+    assert!(!stdout.contains("range-b.txt"));
+    assert!(!stdout.contains("Aggregate"));
+
+    let output = repo.run_stax(&["diff", "--range", "main"]);
+    output.assert_failure();
+}
+
+#[test]
+fn test_diff_between_compares_branches_directly() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["between-a", "between-b", "between-c"]);
+
+    let output = repo.run_stax(&[
+        "diff",
+        "--between",
+        &branches[0],
+        &branches[2],
+        "--name-only",
+    ]);
+    output.assert_success();
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains("2 ahead, 0 behind"));
+    assert!(!stdout.contains("between-a.txt"));
+    assert!(stdout.contains("between-b.txt"));
+    assert!(stdout.contains("between-c.txt"));
+    assert!(!stdout.contains("Aggregate"));
+
+    let output = repo.run_stax(&["diff", "--between", &branches[0], "no-such-branch"]);
+    output.assert_failure();
+}
+
 // =============================================================================
 // Doctor Command Variations
 // =============================================================================
@@ -233,7 +331,7 @@ fn test_doctor_output_format() {
     // Should contain check symbols
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        stdout.contains("✓") || stdout.contains("✗") || stdout.contains("!") || stdout.len() > 0
+        stdout.contains("✓") || stdout.contains("✗") || stdout.contains("!") || !stdout.is_empty()
     );
 }
 
@@ -306,7 +404,7 @@ fn test_config_output_contains_path() {
     output.assert_success();
     // Should show config path
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("config") || stdout.contains("Config") || stdout.len() > 0);
+    assert!(stdout.contains("config") || stdout.contains("Config") || !stdout.is_empty());
 }
 
 // =============================================================================
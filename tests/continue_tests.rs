@@ -312,3 +312,53 @@ fn test_continue_multiple_times_no_rebase() {
         );
     }
 }
+
+// =============================================================================
+// Rebase Already In Progress Tests
+// =============================================================================
+
+#[test]
+fn test_restack_refuses_while_rebase_in_progress() {
+    let repo = TestRepo::new();
+    repo.create_conflict_scenario();
+
+    repo.run_stax(&["restack", "--quiet"]);
+    assert!(repo.has_rebase_in_progress());
+
+    for args in [
+        &["restack", "--quiet"][..],
+        &["upstack", "restack"][..],
+        &["sync", "--force", "--quiet"][..],
+    ] {
+        let output = repo.run_stax(args);
+        output.assert_failure();
+        let stderr = TestRepo::stderr(&output);
+        assert!(
+            stderr.contains("already in progress") && stderr.contains("stax continue"),
+            "Expected in-progress guidance from {:?}, got: {}",
+            args,
+            stderr
+        );
+    }
+
+    // The original rebase is left untouched for the user to finish
+    assert!(repo.has_rebase_in_progress());
+}
+
+#[test]
+fn test_cascade_fails_with_conflict_error_before_submitting() {
+    let repo = TestRepo::new();
+    let branch = repo.create_conflict_scenario();
+
+    let output = repo.run_stax(&["cascade", "--no-submit"]);
+    output.assert_failure();
+    let stderr = TestRepo::stderr(&output);
+    assert!(
+        stderr.contains(&format!("Rebase of '{}' stopped on conflicts", branch)),
+        "Expected conflict error, got: {}",
+        stderr
+    );
+    assert!(repo.has_rebase_in_progress());
+
+    repo.abort_rebase();
+}
@@ -247,6 +247,70 @@ fn test_squash_yes_flag_no_prompt() {
     output.assert_success();
 }
 
+/// Squash onto a parent that moved on rebases the result, and `stax continue` finishes it
+#[test]
+fn test_squash_continue_after_conflict() {
+    let repo = TestRepo::new();
+
+    repo.run_stax(&["create", "squash-conflict"])
+        .assert_success();
+    let branch = repo.current_branch();
+    repo.create_file("conflict.txt", "branch content");
+    repo.commit("Commit 1");
+    repo.create_file("other.txt", "other");
+    repo.commit("Commit 2");
+
+    repo.git(&["checkout", "main"]);
+    repo.create_file("conflict.txt", "main content");
+    repo.commit("Main commit");
+    repo.git(&["checkout", &branch]);
+
+    let output = repo.run_stax(&["branch", "squash", "--yes", "-m", "Squashed commit"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("stax continue"));
+
+    repo.create_file("conflict.txt", "resolved content");
+    repo.git(&["add", "conflict.txt"]);
+    let output = repo.run_stax(&["continue"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("Squashed 2 commits into one"));
+
+    assert_eq!(repo.current_branch(), branch);
+    let count = repo.git(&["rev-list", "--count", "main..HEAD"]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+    let message = repo.git(&["log", "-1", "--format=%s"]);
+    assert_eq!(TestRepo::stdout(&message).trim(), "Squashed commit");
+    // Main's commit is kept underneath rather than reverted by the squash
+    let merged = repo.git(&["merge-base", "--is-ancestor", "main", "HEAD"]);
+    assert!(merged.status.success());
+
+    let output = repo.run_stax(&["continue"]);
+    assert!(TestRepo::stdout(&output).contains("No rebase in progress"));
+}
+
+/// Squash with --edit should commit the message written in the editor
+#[test]
+fn test_squash_edit_uses_editor_message() {
+    let repo = TestRepo::new();
+
+    repo.run_stax(&["create", "squash-edit"]).assert_success();
+    repo.create_file("file1.txt", "content1");
+    repo.commit("Commit 1");
+    repo.create_file("file2.txt", "content2");
+    repo.commit("Commit 2");
+
+    let output = std::process::Command::new(common::stax_bin())
+        .args(["branch", "squash", "--yes", "--edit"])
+        .current_dir(repo.path())
+        .env("GIT_EDITOR", "printf 'Edited squash message\\n' >")
+        .output()
+        .expect("Failed to execute stax");
+    output.assert_success();
+
+    let message = repo.git(&["log", "-1", "--format=%B"]);
+    assert_eq!(TestRepo::stdout(&message).trim(), "Edited squash message");
+}
+
 /// Undo with --yes flag should work without prompting
 #[test]
 fn test_undo_yes_flag_no_prompt() {
@@ -368,3 +432,33 @@ fn test_restack_reports_stash_pop_failure() {
         "Restack should handle stash pop issues gracefully"
     );
 }
+
+// =============================================================================
+// 8. FRESH REPO WITH ONLY TRUNK
+// =============================================================================
+
+/// Commands that act on tracked branches should explain how to create one
+/// instead of printing empty output when only trunk exists
+#[test]
+fn test_commands_on_trunk_only_repo_point_to_create() {
+    let repo = TestRepo::new();
+
+    for args in [
+        &["status"][..],
+        &["log"],
+        &["restack"],
+        &["upstack", "restack"],
+        &["checkout"],
+    ] {
+        let output = repo.run_stax(args);
+        output.assert_success();
+        let stdout = TestRepo::stdout(&output);
+        assert!(
+            stdout.contains("No tracked branches yet"),
+            "{:?} printed: {}",
+            args,
+            stdout
+        );
+        assert!(stdout.contains("stax create"), "{:?}: {}", args, stdout);
+    }
+}
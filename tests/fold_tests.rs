@@ -2,11 +2,22 @@
 //!
 //! Tests for the `branch fold` command that merges a branch into its parent.
 //! Note: The fold command is interactive (requires confirmation), so we test
-//! error cases that exit before the confirmation prompt.
+//! error cases that exit before the confirmation prompt, or pass `--yes`.
 
 mod common;
 
-use common::{OutputAssertions, TestRepo};
+use common::{stax_bin, OutputAssertions, TestRepo};
+use std::process::{Command, Output};
+
+/// Run stax with an editor that replaces the commit message with `message`
+fn run_stax_with_editor(repo: &TestRepo, args: &[&str], message: &str) -> Output {
+    Command::new(stax_bin())
+        .args(args)
+        .current_dir(repo.path())
+        .env("GIT_EDITOR", format!("printf '{}\\n' >", message))
+        .output()
+        .expect("Failed to execute stax")
+}
 
 // =============================================================================
 // Error Case Tests (these don't require confirmation)
@@ -37,34 +48,6 @@ fn test_fold_into_trunk_not_allowed() {
     );
 }
 
-#[test]
-fn test_fold_with_children_not_allowed() {
-    let repo = TestRepo::new();
-
-    // Create a stack: main -> feature-1 -> feature-2
-    let branches = repo.create_stack(&["feature-1", "feature-2"]);
-
-    // Go to feature-1 (which has feature-2 as child)
-    repo.run_stax(&["checkout", &branches[0]]);
-    assert!(repo.current_branch_contains("feature-1"));
-
-    // Try to fold - should fail because it has children
-    let output = repo.run_stax(&["branch", "fold"]);
-
-    let stdout = TestRepo::stdout(&output);
-    let stderr = TestRepo::stderr(&output);
-    let combined = format!("{}{}", stdout, stderr);
-
-    // Should indicate we can't fold due to children
-    assert!(
-        combined.contains("children")
-            || combined.contains("child")
-            || combined.contains("Cannot fold"),
-        "Expected message about children, got: {}",
-        combined
-    );
-}
-
 #[test]
 fn test_fold_untracked_branch_fails() {
     let repo = TestRepo::new();
@@ -299,3 +282,171 @@ fn test_fold_scenario_parent_is_not_trunk() {
 
     // This means fold would work (parent is not trunk)
 }
+
+#[test]
+fn test_fold_continue_after_conflict_uses_edited_message() {
+    let repo = TestRepo::new();
+
+    repo.run_stax(&["create", "middle"]).assert_success();
+    let middle = repo.current_branch();
+    repo.create_file("conflict.txt", "middle content");
+    repo.commit("Middle commit");
+
+    repo.run_stax(&["create", "feature"]).assert_success();
+    repo.create_file("conflict.txt", "feature content");
+    repo.commit("Feature commit");
+
+    // Rewrite the parent so the squash-merge conflicts
+    repo.git(&["checkout", &middle]);
+    repo.create_file("conflict.txt", "rewritten middle content");
+    repo.git(&["commit", "-a", "--amend", "--no-edit"]);
+    repo.git(&["checkout", "-"]);
+
+    let output = repo.run_stax(&["branch", "fold", "--yes"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("stax continue"));
+    assert_eq!(repo.current_branch(), middle);
+
+    repo.create_file("conflict.txt", "resolved content");
+    repo.git(&["add", "conflict.txt"]);
+
+    let output = run_stax_with_editor(&repo, &["continue"], "Edited fold message");
+    output.assert_success();
+
+    let message = repo.git(&["log", "-1", "--format=%B"]);
+    assert_eq!(TestRepo::stdout(&message).trim(), "Edited fold message");
+    assert!(repo.find_branch_containing("feature").is_none());
+
+    // The fold's operation is complete, so there is nothing left to continue
+    let output = repo.run_stax(&["continue"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("No rebase in progress"));
+}
+
+#[test]
+fn test_fold_no_edit_keeps_default_message_after_conflict() {
+    let repo = TestRepo::new();
+
+    repo.run_stax(&["create", "middle"]).assert_success();
+    let middle = repo.current_branch();
+    repo.create_file("conflict.txt", "middle content");
+    repo.commit("Middle commit");
+
+    repo.run_stax(&["create", "feature"]).assert_success();
+    let feature = repo.current_branch();
+    repo.create_file("conflict.txt", "feature content");
+    repo.commit("Feature commit");
+
+    repo.git(&["checkout", &middle]);
+    repo.create_file("conflict.txt", "rewritten middle content");
+    repo.git(&["commit", "-a", "--amend", "--no-edit"]);
+    repo.git(&["checkout", &feature]);
+
+    repo.run_stax(&["branch", "fold", "--yes", "--no-edit"])
+        .assert_success();
+    repo.create_file("conflict.txt", "resolved content");
+    repo.git(&["add", "conflict.txt"]);
+
+    let output = run_stax_with_editor(&repo, &["continue"], "Should not be used");
+    output.assert_success();
+
+    let message = repo.git(&["log", "-1", "--format=%s"]);
+    assert_eq!(
+        TestRepo::stdout(&message).trim(),
+        format!("Fold {} into {}", feature, middle)
+    );
+}
+
+// =============================================================================
+// Folding Branches With Children
+// =============================================================================
+
+#[test]
+fn test_fold_reparents_child_onto_parent() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["base", "middle", "top"]);
+
+    repo.run_stax(&["checkout", &branches[1]]).assert_success();
+    let output = repo.run_stax(&["branch", "fold", "--yes"]);
+    output.assert_success();
+    assert_eq!(repo.current_branch(), branches[0]);
+    assert!(!repo.list_branches().contains(&branches[1]));
+
+    // The folded commits sit on base as one squash commit
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", branches[0])]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "2");
+
+    // top now stacks on base and carries only its own commit
+    assert_eq!(repo.get_children(&branches[0]), vec![branches[2].clone()]);
+    let count = repo.git(&[
+        "rev-list",
+        "--count",
+        &format!("{}..{}", branches[0], branches[2]),
+    ]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+
+    repo.run_stax(&["checkout", &branches[2]]).assert_success();
+    assert_eq!(repo.get_current_parent(), Some(branches[0].clone()));
+    assert!(repo.path().join("middle.txt").exists());
+    assert!(repo.path().join("top.txt").exists());
+}
+
+#[test]
+fn test_fold_reparents_multiple_children_and_grandchildren() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["base", "middle", "left", "left-top"]);
+    repo.run_stax(&["checkout", &branches[1]]).assert_success();
+    let right = repo.create_stack(&["right"]);
+
+    repo.run_stax(&["checkout", &branches[1]]).assert_success();
+    repo.run_stax(&["branch", "fold", "--yes"]).assert_success();
+
+    let mut children = repo.get_children(&branches[0]);
+    children.sort();
+    let mut expected = vec![branches[2].clone(), right[0].clone()];
+    expected.sort();
+    assert_eq!(children, expected);
+    assert_eq!(repo.get_children(&branches[2]), vec![branches[3].clone()]);
+
+    for branch in [&branches[2], &branches[3], &right[0]] {
+        let ancestor = repo.git(&["merge-base", "--is-ancestor", &branches[0], branch]);
+        assert!(
+            ancestor.status.success(),
+            "{} not on {}",
+            branch,
+            branches[0]
+        );
+    }
+    let json = repo.get_status_json();
+    assert!(json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|b| b["needs_restack"] != true));
+}
+
+#[test]
+fn test_fold_refuses_open_pr_without_force() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["base", "reviewed"]);
+
+    // Record an open PR in the branch metadata
+    let metadata_ref = format!("refs/branch-metadata/{}", branches[1]);
+    let json = TestRepo::stdout(&repo.git(&["show", &metadata_ref]));
+    let with_pr = json.replacen('{', r#"{"prInfo":{"number":42,"state":"OPEN"},"#, 1);
+    let blob_path = repo.path().join(".git/pr-metadata.json");
+    std::fs::write(&blob_path, with_pr).unwrap();
+    let blob = TestRepo::stdout(&repo.git(&["hash-object", "-w", blob_path.to_str().unwrap()]))
+        .trim()
+        .to_string();
+    repo.git(&["update-ref", &metadata_ref, &blob]);
+
+    let output = repo.run_stax(&["branch", "fold", "--yes"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("open PR #42"));
+    assert!(repo.list_branches().contains(&branches[1]));
+
+    repo.run_stax(&["branch", "fold", "--yes", "--force"])
+        .assert_success();
+    assert!(!repo.list_branches().contains(&branches[1]));
+}
@@ -113,6 +113,17 @@ impl TestRepo {
     /// Simulate pushing a commit to the remote main branch (as if another user did it)
     /// This clones the remote, makes a commit, and pushes back
     fn simulate_remote_commit(&self, filename: &str, content: &str, message: &str) {
+        self.simulate_remote_commit_on("main", filename, content, message);
+    }
+
+    /// Like `simulate_remote_commit`, but pushes to `branch` on the remote
+    fn simulate_remote_commit_on(
+        &self,
+        branch: &str,
+        filename: &str,
+        content: &str,
+        message: &str,
+    ) {
         let remote_path = self.remote_path().expect("No remote configured");
 
         // Create a temp clone
@@ -123,12 +134,12 @@ impl TestRepo {
             .output()
             .expect("Failed to clone remote");
 
-        // Ensure we have a local main branch even if remote HEAD isn't set
+        // Ensure we have the local branch even if remote HEAD isn't set
         Command::new("git")
-            .args(["checkout", "-B", "main", "origin/main"])
+            .args(["checkout", "-B", branch, &format!("origin/{}", branch)])
             .current_dir(clone_dir.path())
             .output()
-            .expect("Failed to checkout main");
+            .expect("Failed to checkout branch");
 
         // Configure git user
         Command::new("git")
@@ -157,7 +168,7 @@ impl TestRepo {
 
         // Push back to origin
         Command::new("git")
-            .args(["push", "origin", "main"])
+            .args(["push", "origin", branch])
             .current_dir(clone_dir.path())
             .output()
             .expect("Failed to push to origin");
@@ -362,6 +373,21 @@ fn configure_submit_remote(repo: &TestRepo) {
     repo.git(&["remote", "set-url", "--push", "origin", &remote_path_str]);
 }
 
+/// Run stax with an isolated HOME containing the given stax config.toml
+fn run_stax_with_config(repo: &TestRepo, home: &TempDir, config: &str, args: &[&str]) -> Output {
+    let config_dir = home.path().join(".config").join("stax");
+    fs::create_dir_all(&config_dir).expect("Failed to create config dir");
+    fs::write(config_dir.join("config.toml"), config).expect("Failed to write config");
+
+    Command::new(stax_bin())
+        .args(args)
+        .current_dir(repo.path())
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .output()
+        .expect("Failed to execute stax")
+}
+
 fn list_remote_heads(repo: &TestRepo) -> Vec<String> {
     let remote_path = repo
         .remote_path()
@@ -446,6 +472,91 @@ fn test_branch_create_with_message() {
     );
 }
 
+#[test]
+fn test_branch_create_empty_commit() {
+    let repo = TestRepo::new();
+
+    let output = repo.run_stax(&["bc", "add-login-page", "--empty"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(repo.current_branch_contains("add-login-page"));
+
+    // Branch should be one commit ahead of main with an empty commit
+    let count = repo.git(&["rev-list", "--count", "main..HEAD"]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+    let subject = repo.git(&["log", "-1", "--format=%s"]);
+    assert_eq!(TestRepo::stdout(&subject).trim(), "add login page");
+    let files = repo.git(&["show", "--name-only", "--format=", "HEAD"]);
+    assert!(TestRepo::stdout(&files).trim().is_empty());
+}
+
+#[test]
+fn test_create_empty_derives_message_from_last_name_segment() {
+    let repo = TestRepo::new();
+
+    let output = repo.run_stax(&["create", "auth/add_login-form", "--empty"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    let count = repo.git(&["rev-list", "--count", "main..HEAD"]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+    let subject = repo.git(&["log", "-1", "--format=%s"]);
+    assert_eq!(TestRepo::stdout(&subject).trim(), "add login form");
+    let files = repo.git(&["show", "--name-only", "--format=", "HEAD"]);
+    assert!(TestRepo::stdout(&files).trim().is_empty());
+}
+
+#[test]
+fn test_branch_create_commit_moves_staged_changes() {
+    let repo = TestRepo::new();
+
+    repo.create_file("staged.txt", "staged");
+    repo.create_file("unstaged.txt", "unstaged");
+    repo.git(&["add", "staged.txt"]);
+
+    let output = repo.run_stax(&["bc", "oops-wrong-branch", "--commit", "-m", "Move work"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(repo.current_branch_contains("oops-wrong-branch"));
+
+    // Only the staged file is committed on the new branch
+    let files = repo.git(&["show", "--name-only", "--format=", "HEAD"]);
+    assert_eq!(TestRepo::stdout(&files).trim(), "staged.txt");
+    let subject = repo.git(&["log", "-1", "--format=%s"]);
+    assert_eq!(TestRepo::stdout(&subject).trim(), "Move work");
+
+    // Parent is untouched and the unstaged file is still pending
+    let count = repo.git(&["rev-list", "--count", "main..HEAD"]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+    let status = repo.git(&["status", "--porcelain"]);
+    assert!(TestRepo::stdout(&status).contains("unstaged.txt"));
+}
+
+#[test]
+fn test_branch_create_commit_require_staged_errors_when_index_clean() {
+    let repo = TestRepo::new();
+
+    let output = repo.run_stax(&["bc", "nothing-staged", "--commit", "--require-staged"]);
+    assert!(!output.status.success());
+    assert!(TestRepo::stderr(&output).contains("No staged changes"));
+    assert!(repo.find_branch_containing("nothing-staged").is_none());
+
+    // Without --require-staged it behaves like a plain create
+    let output = repo.run_stax(&["bc", "nothing-staged", "--commit"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+    let count = repo.git(&["rev-list", "--count", "main..HEAD"]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "0");
+}
+
 #[test]
 fn test_branch_create_from_another_branch() {
     let repo = TestRepo::new();
@@ -659,6 +770,69 @@ fn test_status_json_output() {
     );
 }
 
+#[test]
+fn test_status_json_schema_version_and_parents() {
+    let repo = TestRepo::new();
+    let mut created = Vec::new();
+    for name in ["feature-1", "feature-2"] {
+        repo.run_stax(&["bc", name]);
+        created.push(repo.current_branch());
+        repo.create_file(&format!("{}.txt", name), name);
+        repo.commit(&format!("Commit for {}", name));
+    }
+
+    let output = repo.run_stax(&["status", "--json"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+    let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).expect("Invalid JSON");
+
+    assert_eq!(json["schemaVersion"], 1);
+    assert!(json.get("schema_version").is_none());
+    assert_eq!(json["trunk"], "main");
+
+    let branches = json["branches"].as_array().unwrap();
+    let trunk = branches.iter().find(|b| b["name"] == "main").unwrap();
+    assert!(trunk["parent"].is_null());
+
+    for name in &created {
+        let entry = branches
+            .iter()
+            .find(|b| b["name"] == name.as_str())
+            .unwrap();
+        let metadata = repo.git(&["show", &format!("refs/branch-metadata/{}", name)]);
+        let metadata: Value = serde_json::from_str(&TestRepo::stdout(&metadata)).unwrap();
+        assert_eq!(entry["parent"], metadata["parentBranchName"], "{}", name);
+    }
+}
+
+#[test]
+fn test_status_shows_trunk_behind_remote() {
+    let repo = TestRepo::new_with_remote();
+    repo.run_stax(&["bc", "feature-1"]);
+    repo.simulate_remote_commit("remote-file.txt", "from remote", "Remote commit");
+    repo.git(&["fetch", "origin"]);
+
+    let output = repo.run_stax(&["status", "--json"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).expect("Invalid JSON");
+    assert_eq!(json["trunk"], "main");
+    assert_eq!(json["trunk_remote"]["remote_ref"], "origin/main");
+    assert_eq!(json["trunk_remote"]["behind"], 1);
+    assert_eq!(json["trunk_remote"]["ahead"], 0);
+
+    let output = repo.run_stax(&["status"]);
+    let stdout = TestRepo::stdout(&output);
+    assert!(
+        stdout.contains("main is 1 commit behind origin/main"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("stax sync"), "{}", stdout);
+}
+
 #[test]
 fn test_status_compact_output() {
     let repo = TestRepo::new();
@@ -675,6 +849,56 @@ fn test_status_compact_output() {
     assert!(stdout.contains('\t'));
 }
 
+#[test]
+fn test_status_dense_prints_one_line_per_branch() {
+    let repo = TestRepo::new();
+    repo.run_stax(&["bc", "feature-1"]);
+
+    let output = repo.run_stax(&["status", "--dense"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+
+    // Dense lines are "<indent><glyph> <name>", unlike the tab-separated --compact rows
+    let stdout = TestRepo::stdout(&output);
+    let lines: Vec<&str> = stdout.lines().filter(|l| !l.trim().is_empty()).collect();
+    assert_eq!(lines.len(), 2, "{}", stdout);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.trim_start().ends_with("◉ feature-1")),
+        "{}",
+        stdout
+    );
+    assert!(lines.iter().any(|l| l.ends_with("○ main")), "{}", stdout);
+    assert!(!stdout.contains('\t'));
+
+    let output = repo.run_stax(&["status", "--dense", "--compact"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_status_refreshes_stale_ci_cache_without_checks_flag() {
+    let repo = TestRepo::new();
+    repo.run_stax(&["bc", "feature-1"]);
+
+    let cache_path = repo.path().join(".git").join("stax").join("ci-cache.json");
+    fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+    fs::write(
+        &cache_path,
+        r#"{"branches":{"feature-1":{"ci_state":"success","pr_state":null,"updated_at":0}},"last_refresh":0}"#,
+    )
+    .unwrap();
+
+    let output = repo.run_stax(&["status"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+
+    let cache: Value = serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
+    assert!(
+        cache["last_refresh"].as_u64().unwrap() > 0,
+        "stale cache was not refreshed: {}",
+        cache
+    );
+}
+
 #[test]
 fn test_status_alias_s() {
     let repo = TestRepo::new();
@@ -883,6 +1107,136 @@ fn test_branch_track() {
     );
 }
 
+#[test]
+fn test_branch_track_force_overwrites_parent() {
+    let repo = TestRepo::new();
+
+    repo.run_stax(&["bc", "feature-1"]);
+    let feature1_name = repo.current_branch();
+    repo.run_stax(&["t"]);
+    repo.run_stax(&["bc", "feature-2"]);
+    let feature2_name = repo.current_branch();
+
+    // Without --force the existing parent is kept
+    let output = repo.run_stax(&["branch", "track", "--parent", &feature1_name]);
+    assert!(output.status.success());
+    assert!(TestRepo::stdout(&output).contains("already tracked"));
+
+    let output = repo.run_stax(&["branch", "track", "--parent", &feature1_name, "--force"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(TestRepo::stdout(&output).contains("overwriting recorded parent"));
+
+    let output = repo.run_stax(&["status", "--json"]);
+    let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    let branches = json["branches"].as_array().unwrap();
+    let feature2 = branches
+        .iter()
+        .find(|b| b["name"].as_str().unwrap() == feature2_name)
+        .expect("Should find feature-2");
+    assert_eq!(feature2["parent"].as_str().unwrap(), feature1_name);
+}
+
+#[test]
+fn test_branch_track_remote_parent_and_restack_onto_it() {
+    let repo = TestRepo::new_with_remote();
+
+    // A shared branch that only exists on the remote
+    repo.git(&["checkout", "-b", "integration"]);
+    repo.create_file("integration.txt", "v1");
+    repo.commit("Integration v1");
+    repo.git(&["push", "origin", "integration"]);
+    repo.git(&["checkout", "main"]);
+    repo.git(&["branch", "-D", "integration"]);
+
+    repo.git(&["checkout", "-b", "on-integration", "origin/integration"]);
+    repo.create_file("mine.txt", "mine");
+    repo.commit("My work");
+
+    let output = repo.run_stax(&["branch", "track", "--parent", "origin/nope"]);
+    assert!(!output.status.success());
+    assert!(TestRepo::stderr(&output).contains("does not exist"));
+
+    let output = repo.run_stax(&["branch", "track", "--parent", "origin/integration"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    // Someone else moves the shared branch
+    repo.git(&["checkout", "-b", "tmp-integration", "origin/integration"]);
+    repo.create_file("integration.txt", "v2");
+    repo.commit("Integration v2");
+    repo.git(&["push", "origin", "tmp-integration:integration"]);
+    repo.git(&["checkout", "on-integration"]);
+    repo.git(&["branch", "-D", "tmp-integration"]);
+    repo.git(&["fetch", "origin"]);
+
+    let output = repo.run_stax(&["status", "--json"]);
+    let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    let branch = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["name"] == "on-integration")
+        .expect("Should find on-integration")
+        .clone();
+    assert_eq!(branch["parent"], "origin/integration");
+    assert!(branch["needs_restack"].as_bool().unwrap_or(false));
+
+    let output = repo.run_stax(&["restack", "--quiet"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    let output = repo.git(&[
+        "merge-base",
+        "--is-ancestor",
+        "origin/integration",
+        "on-integration",
+    ]);
+    assert!(
+        output.status.success(),
+        "Expected rebase onto origin/integration"
+    );
+}
+
+#[test]
+fn test_status_line_stats_use_fork_point_after_parent_amend() {
+    let repo = TestRepo::new();
+
+    repo.run_stax(&["bc", "feature-1"]);
+    let feature1_name = repo.current_branch();
+    repo.create_file("parent.txt", "one\n");
+    repo.commit("Parent change");
+
+    repo.run_stax(&["bc", "feature-2"]);
+    let feature2_name = repo.current_branch();
+    repo.create_file("child.txt", "child\n");
+    repo.commit("Child change");
+
+    // Rewrite the parent so the live merge-base falls back to main
+    repo.git(&["checkout", &feature1_name]);
+    repo.create_file("parent.txt", "one\ntwo\nthree\n");
+    repo.git(&["commit", "-a", "--amend", "--no-edit"]);
+
+    let output = repo.run_stax(&["status", "--json"]);
+    assert!(output.status.success());
+    let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    let feature2 = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["name"].as_str().unwrap() == feature2_name)
+        .expect("Should find feature-2");
+    assert_eq!(feature2["lines_added"].as_u64(), Some(1));
+}
+
 #[test]
 fn test_branch_reparent() {
     let repo = TestRepo::new();
@@ -939,6 +1293,48 @@ fn test_branch_delete() {
     assert!(repo.find_branch_containing("feature-to-delete").is_none());
 }
 
+#[test]
+fn test_branch_delete_remote_removes_remote_branch() {
+    let repo = TestRepo::new_with_remote();
+
+    repo.run_stax(&["bc", "remote-delete"]);
+    let branch_name = repo.current_branch();
+    repo.create_file("r.txt", "remote");
+    repo.commit("Remote commit");
+    repo.git(&["push", "-u", "origin", &branch_name]);
+    assert!(list_remote_heads(&repo).contains(&branch_name));
+    repo.run_stax(&["t"]);
+
+    let output = repo.run_stax(&["branch", "delete", &branch_name, "--force", "--remote"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    assert!(repo.find_branch_containing("remote-delete").is_none());
+    assert!(
+        !list_remote_heads(&repo).contains(&branch_name),
+        "Remote branch should be deleted"
+    );
+}
+
+#[test]
+fn test_branch_delete_without_remote_flag_keeps_remote_branch() {
+    let repo = TestRepo::new_with_remote();
+
+    repo.run_stax(&["bc", "keep-remote"]);
+    let branch_name = repo.current_branch();
+    repo.create_file("k.txt", "keep");
+    repo.commit("Keep commit");
+    repo.git(&["push", "-u", "origin", &branch_name]);
+    repo.run_stax(&["t"]);
+
+    let output = repo.run_stax(&["branch", "delete", &branch_name, "--force"]);
+    assert!(output.status.success());
+    assert!(list_remote_heads(&repo).contains(&branch_name));
+}
+
 #[test]
 fn test_branch_squash() {
     let repo = TestRepo::new();
@@ -1129,6 +1525,33 @@ fn test_restack_after_parent_change() {
     assert!(!feature1["needs_restack"].as_bool().unwrap_or(true));
 }
 
+#[test]
+fn test_restack_onto_remote_keeps_teammate_commits() {
+    let repo = TestRepo::new_with_remote();
+    repo.run_stax(&["bc", "shared"]);
+    let branch = repo.current_branch();
+    repo.create_file("mine.txt", "local");
+    repo.commit("Local work");
+    repo.git(&["push", "-u", "origin", &branch]);
+
+    repo.create_file("mine2.txt", "more local");
+    repo.commit("More local work");
+    repo.simulate_remote_commit_on(&branch, "theirs.txt", "teammate", "Teammate work");
+
+    let output = repo.run_stax(&["restack", "--onto-remote", "--quiet"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    assert!(repo.path().join("theirs.txt").exists());
+    assert!(repo.path().join("mine2.txt").exists());
+    let remote_sha = repo.get_commit_sha(&format!("origin/{}", branch));
+    let merge_base = TestRepo::stdout(&repo.git(&["merge-base", &remote_sha, "HEAD"]));
+    assert_eq!(merge_base.trim(), remote_sha);
+}
+
 #[test]
 fn test_restack_all_flag() {
     let repo = TestRepo::new();
@@ -1157,6 +1580,83 @@ fn test_restack_all_flag() {
     );
 }
 
+#[test]
+fn test_restack_all_jobs_rebases_independent_stacks() {
+    let repo = TestRepo::new();
+
+    for stack in ["alpha", "beta"] {
+        repo.run_stax(&["t"]);
+        repo.run_stax(&["bc", &format!("{}-1", stack)]);
+        repo.create_file(&format!("{}1.txt", stack), "content");
+        repo.commit(&format!("{} 1", stack));
+        repo.run_stax(&["bc", &format!("{}-2", stack)]);
+        repo.create_file(&format!("{}2.txt", stack), "content");
+        repo.commit(&format!("{} 2", stack));
+    }
+
+    repo.run_stax(&["t"]);
+    repo.create_file("main.txt", "main");
+    repo.commit("Main update");
+
+    let output = repo.run_stax(&["restack", "--all", "--jobs", "2"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(TestRepo::stdout(&output).contains("independent stacks"));
+
+    for branch in ["alpha-1", "alpha-2", "beta-1", "beta-2"] {
+        let ancestor = repo.git(&["merge-base", "--is-ancestor", "main", branch]);
+        assert!(ancestor.status.success(), "{} was not restacked", branch);
+    }
+    assert_eq!(repo.current_branch(), "main");
+
+    // Scratch worktrees are cleaned up
+    let worktrees = TestRepo::stdout(&repo.git(&["worktree", "list"]));
+    assert_eq!(worktrees.lines().count(), 1, "{}", worktrees);
+}
+
+#[test]
+fn test_restack_dry_run_leaves_refs_unchanged() {
+    let repo = TestRepo::new();
+    for name in ["feature-1", "feature-2"] {
+        repo.run_stax(&["bc", name]);
+        repo.create_file(&format!("{}.txt", name), name);
+        repo.commit(&format!("Commit for {}", name));
+    }
+
+    repo.run_stax(&["t"]);
+    repo.create_file("main.txt", "main");
+    repo.commit("Main update");
+    repo.run_stax(&["checkout", "feature-1"]);
+
+    let refs_before = TestRepo::stdout(&repo.git(&["for-each-ref"]));
+
+    let output = repo.run_stax(&["restack", "--all", "--dry-run"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains("Dry run"), "{}", stdout);
+    assert!(
+        stdout.contains("feature-1 onto main (1 commit)"),
+        "{}",
+        stdout
+    );
+    // feature-2 moves too once feature-1 is rebased
+    assert!(
+        stdout.contains("feature-2 onto feature-1 (1 commit)"),
+        "{}",
+        stdout
+    );
+
+    let output = repo.run_stax(&["upstack", "restack", "--dry-run"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+    assert!(TestRepo::stdout(&output).contains("feature-1 onto main"));
+
+    assert_eq!(TestRepo::stdout(&repo.git(&["for-each-ref"])), refs_before);
+    assert_eq!(repo.current_branch(), "feature-1");
+}
+
 // =============================================================================
 // Cascade Tests
 // =============================================================================
@@ -1562,6 +2062,32 @@ fn test_sync_pulls_trunk_updates() {
     );
 }
 
+#[test]
+fn test_sync_no_fetch_uses_last_fetched_refs() {
+    let repo = TestRepo::new_with_remote();
+    repo.simulate_remote_commit("remote-file.txt", "from remote", "Remote commit");
+
+    // Nothing fetched yet, so there is nothing to fast-forward to
+    let output = repo.run_stax(&["sync", "--force", "--no-fetch"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(TestRepo::stdout(&output).contains("Skipping fetch"));
+    assert!(!repo.path().join("remote-file.txt").exists());
+
+    // Once fetched, --no-fetch picks up the cached remote-tracking ref
+    repo.git(&["fetch", "origin"]);
+    let output = repo.run_stax(&["sync", "--force", "--no-fetch"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(repo.path().join("remote-file.txt").exists());
+}
+
 #[test]
 fn test_sync_with_feature_branch() {
     let repo = TestRepo::new_with_remote();
@@ -1805,14 +2331,264 @@ fn test_branch_submit_no_pr_pushes_only_current_branch() {
     assert!(
         remote_branches
             .iter()
-            .any(|b| b == &branch_a || b.contains("scope-a")),
-        "Expected scope-a branch on remote: {:?}",
-        remote_branches
-    );
-    assert!(
-        !remote_branches.iter().any(|b| b.contains("scope-b")),
-        "scope-b should not be submitted by branch submit"
-    );
+            .any(|b| b == &branch_a || b.contains("scope-a")),
+        "Expected scope-a branch on remote: {:?}",
+        remote_branches
+    );
+    assert!(
+        !remote_branches.iter().any(|b| b.contains("scope-b")),
+        "scope-b should not be submitted by branch submit"
+    );
+}
+
+#[test]
+fn test_submit_pre_submit_hook_failure_aborts_push() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+    let home = TempDir::new().unwrap();
+    let config = "[hooks]\npre_submit = \"exit 3\"\n";
+
+    repo.run_stax(&["bc", "hooked"]);
+    let branch = repo.current_branch();
+    repo.create_file("h.txt", "h");
+    repo.commit("Hooked commit");
+
+    let output = run_stax_with_config(&repo, &home, config, &["submit", "--no-pr", "--yes"]);
+    assert!(!output.status.success());
+    assert!(
+        TestRepo::stderr(&output).contains("Pre-submit hook failed"),
+        "Expected hook failure, got: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(!list_remote_heads(&repo).contains(&branch));
+
+    let output = run_stax_with_config(
+        &repo,
+        &home,
+        config,
+        &["submit", "--no-pr", "--yes", "--no-verify"],
+    );
+    assert!(
+        output.status.success(),
+        "--no-verify submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(list_remote_heads(&repo).contains(&branch));
+}
+
+#[test]
+fn test_submit_max_pr_lines_blocks_oversized_branch() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+    let home = TempDir::new().unwrap();
+    let config = "[remote]\nwarn_pr_lines = 10\nmax_pr_lines = 20\n";
+
+    repo.run_stax(&["bc", "huge"]);
+    let branch = repo.current_branch();
+    let generated: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+    repo.create_file("generated.txt", &generated);
+    repo.commit("Add generated file");
+
+    let output = run_stax_with_config(&repo, &home, config, &["submit", "--no-pr", "--yes"]);
+    assert!(!output.status.success());
+    assert!(
+        TestRepo::stderr(&output).contains("max_pr_lines"),
+        "Expected size failure, got: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(TestRepo::stdout(&output).contains("generated.txt (50)"));
+    assert!(!list_remote_heads(&repo).contains(&branch));
+
+    let output = run_stax_with_config(
+        &repo,
+        &home,
+        config,
+        &["submit", "--no-pr", "--yes", "--no-verify"],
+    );
+    assert!(
+        output.status.success(),
+        "--no-verify submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(list_remote_heads(&repo).contains(&branch));
+}
+
+#[test]
+fn test_submit_pre_submit_hook_per_branch_skips_failing_branch() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+    let home = TempDir::new().unwrap();
+    let config = "[hooks]\n\
+                  pre_submit = 'case \"$STAX_BRANCH\" in *hook-bad) exit 1;; esac'\n\
+                  pre_submit_per_branch = true\n\
+                  pre_submit_abort_all = false\n";
+
+    // main -> hook-good -> hook-bad -> hook-after
+    repo.run_stax(&["bc", "hook-good"]);
+    let good = repo.current_branch();
+    repo.create_file("g.txt", "g");
+    repo.commit("Good commit");
+    repo.run_stax(&["bc", "hook-bad"]);
+    let bad = repo.current_branch();
+    repo.create_file("b.txt", "b");
+    repo.commit("Bad commit");
+    repo.run_stax(&["bc", "hook-after"]);
+    let after = repo.current_branch();
+    repo.create_file("a.txt", "a");
+    repo.commit("After commit");
+
+    let output = run_stax_with_config(&repo, &home, config, &["submit", "--no-pr", "--yes"]);
+    assert!(
+        output.status.success(),
+        "submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+    let remote = list_remote_heads(&repo);
+    assert!(remote.contains(&good), "{:?}", remote);
+    assert!(!remote.contains(&bad), "{:?}", remote);
+    assert!(
+        !remote.contains(&after),
+        "descendants of a failing branch are skipped: {:?}",
+        remote
+    );
+}
+
+#[test]
+fn test_submit_pre_submit_hook_per_branch_runs_in_branch_tree() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+    let home = TempDir::new().unwrap();
+    // Only the upper branch has two.txt, and it is what's checked out here
+    let config = "[hooks]\n\
+                  pre_submit = 'test ! -f two.txt'\n\
+                  pre_submit_per_branch = true\n\
+                  pre_submit_abort_all = false\n";
+
+    repo.run_stax(&["bc", "tree-one"]);
+    let one = repo.current_branch();
+    repo.create_file("one.txt", "1");
+    repo.commit("One");
+    repo.run_stax(&["bc", "tree-two"]);
+    let two = repo.current_branch();
+    repo.create_file("two.txt", "2");
+    repo.commit("Two");
+
+    let output = run_stax_with_config(&repo, &home, config, &["submit", "--no-pr", "--yes"]);
+    assert!(
+        output.status.success(),
+        "submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+    let remote = list_remote_heads(&repo);
+    assert!(remote.contains(&one), "{:?}", remote);
+    assert!(!remote.contains(&two), "{:?}", remote);
+    assert_eq!(repo.current_branch(), two);
+}
+
+#[test]
+fn test_submit_no_push_requires_remote_head() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+
+    repo.run_stax(&["bc", "no-push-branch"]);
+    repo.create_file("a.txt", "a");
+    repo.commit("A commit");
+
+    let output = repo.run_stax(&["branch", "submit", "--no-push", "--yes", "--no-prompt"]);
+    assert!(!output.status.success());
+    let stderr = TestRepo::stderr(&output);
+    assert!(
+        stderr.contains("has no remote head"),
+        "Expected missing remote head error, got: {}",
+        stderr
+    );
+    assert!(
+        !list_remote_heads(&repo)
+            .iter()
+            .any(|b| b.contains("no-push-branch")),
+        "--no-push must not push the branch"
+    );
+}
+
+#[test]
+fn test_status_since_last_submit_counts_new_commits() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+
+    repo.run_stax(&["bc", "since-submit"]);
+    let branch = repo.current_branch();
+    repo.create_file("a.txt", "a");
+    repo.commit("First commit");
+
+    let output = repo.run_stax(&["branch", "submit", "--no-pr", "--yes"]);
+    assert!(
+        output.status.success(),
+        "branch submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    repo.create_file("b.txt", "b");
+    repo.commit("Second commit");
+    repo.create_file("c.txt", "c");
+    repo.commit("Third commit");
+
+    let output = repo.run_stax(&["status", "--json", "--since-last-submit"]);
+    assert!(output.status.success());
+    let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    let entry = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["name"] == branch.as_str())
+        .expect("branch in status");
+    assert_eq!(entry["commits_since_submit"], 2);
+    assert_eq!(entry["ahead"], 3);
+}
+
+#[test]
+fn test_status_ahead_of_remote_marks_unpushed_commits() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+
+    repo.run_stax(&["bc", "unpushed-work"]);
+    let branch = repo.current_branch();
+    repo.create_file("a.txt", "a");
+    repo.commit("First commit");
+    repo.git(&["push", "-u", "origin", &branch]);
+
+    let find = |json: &Value| {
+        json["branches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|b| b["name"] == branch.as_str())
+            .cloned()
+            .expect("branch in status")
+    };
+
+    let output = repo.run_stax(&["status", "--json", "--ahead-of-remote"]);
+    assert!(output.status.success());
+    let entry = find(&serde_json::from_str(&TestRepo::stdout(&output)).unwrap());
+    assert_eq!(entry["unpushed"], false);
+    assert_eq!(entry["unpushed_commits"], 0);
+
+    repo.create_file("b.txt", "b");
+    repo.commit("Second commit");
+    repo.create_file("c.txt", "c");
+    repo.commit("Third commit");
+
+    let output = repo.run_stax(&["status", "--json", "--ahead-of-remote"]);
+    let entry = find(&serde_json::from_str(&TestRepo::stdout(&output)).unwrap());
+    assert_eq!(entry["unpushed"], true);
+    assert_eq!(entry["unpushed_commits"], 2);
+
+    let output = repo.run_stax(&["status", "--ahead-of-remote"]);
+    assert!(TestRepo::stdout(&output).contains("unpushed (2)"));
+
+    // Without the flag the fields are omitted
+    let output = repo.run_stax(&["status", "--json"]);
+    let entry = find(&serde_json::from_str(&TestRepo::stdout(&output)).unwrap());
+    assert!(entry.get("unpushed").is_none());
 }
 
 #[test]
@@ -1866,6 +2642,42 @@ fn test_downstack_submit_no_pr_pushes_ancestors_and_current() {
     );
 }
 
+#[test]
+fn test_submit_stack_up_skips_descendants() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+
+    repo.run_stax(&["bc", "up-parent"]);
+    let parent = repo.current_branch();
+    repo.create_file("parent.txt", "parent");
+    repo.commit("Parent commit");
+
+    repo.run_stax(&["bc", "up-leaf"]);
+    let leaf = repo.current_branch();
+    repo.create_file("leaf.txt", "leaf");
+    repo.commit("Leaf commit");
+
+    repo.run_stax(&["checkout", &parent]);
+    let output = repo.run_stax(&["submit", "--up-to-here", "--no-pr", "--yes"]);
+    assert!(
+        output.status.success(),
+        "submit --up-to-here failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    let remote_branches = list_remote_heads(&repo);
+    assert!(
+        remote_branches.contains(&parent),
+        "Expected parent on remote: {:?}",
+        remote_branches
+    );
+    assert!(
+        !remote_branches.contains(&leaf),
+        "Did not expect leaf on remote: {:?}",
+        remote_branches
+    );
+}
+
 #[test]
 fn test_upstack_submit_no_pr_pushes_current_and_descendants() {
     let repo = TestRepo::new_with_remote();
@@ -2522,6 +3334,159 @@ fn test_push_empty_branch_manually() {
     );
 }
 
+#[test]
+fn test_submit_skips_empty_branches_and_continues() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+
+    repo.run_stax(&["bc", "empty-stack-a"]);
+    let branch_a = repo.current_branch();
+    repo.create_file("a.txt", "a");
+    repo.commit("A commit");
+
+    // Middle branch with no commits of its own
+    repo.run_stax(&["bc", "empty-stack-b"]);
+    let branch_b = repo.current_branch();
+
+    repo.run_stax(&["bc", "empty-stack-c"]);
+    let branch_c = repo.current_branch();
+    repo.create_file("c.txt", "c");
+    repo.commit("C commit");
+
+    // Empty leaf
+    repo.run_stax(&["bc", "empty-stack-d"]);
+    let branch_d = repo.current_branch();
+
+    let output = repo.run_stax(&["submit", "--no-pr", "--yes"]);
+    assert!(
+        output.status.success(),
+        "submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+    let stdout = TestRepo::stdout(&output);
+    assert!(stdout.contains(&format!("{} has no commits to submit", branch_b)));
+    assert!(stdout.contains(&format!("{} has no commits to submit", branch_d)));
+
+    let remote_branches = list_remote_heads(&repo);
+    assert!(remote_branches.contains(&branch_a));
+    assert!(remote_branches.contains(&branch_c));
+    // The empty middle branch is still the base of c's PR; the empty leaf is skipped
+    assert!(remote_branches.contains(&branch_b));
+    assert!(
+        !remote_branches.contains(&branch_d),
+        "empty leaf should not be pushed: {:?}",
+        remote_branches
+    );
+}
+
+/// Make the bare remote reject pushes to any ref containing `pattern`
+#[cfg(unix)]
+fn reject_remote_pushes(repo: &TestRepo, pattern: &str) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let hook = repo
+        .remote_path()
+        .expect("Expected remote path for repository with origin")
+        .join("hooks")
+        .join("pre-receive");
+    fs::write(
+        &hook,
+        format!(
+            "#!/bin/sh\nwhile read old new ref; do\n  case \"$ref\" in *{}*) echo rejected >&2; exit 1;; esac\ndone\n",
+            pattern
+        ),
+    )
+    .expect("Failed to write pre-receive hook");
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755))
+        .expect("Failed to make hook executable");
+}
+
+#[cfg(unix)]
+fn allow_remote_pushes(repo: &TestRepo) {
+    let hook = repo
+        .remote_path()
+        .unwrap()
+        .join("hooks")
+        .join("pre-receive");
+    fs::remove_file(hook).expect("Failed to remove pre-receive hook");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_submit_resumes_after_mid_submit_failure() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+
+    let branches: Vec<String> = ["resume-a", "resume-b", "resume-c"]
+        .iter()
+        .map(|name| {
+            repo.run_stax(&["bc", name]);
+            repo.create_file(&format!("{}.txt", name), name);
+            repo.commit(&format!("{} commit", name));
+            repo.current_branch()
+        })
+        .collect();
+
+    // The connection "drops" on the last branch
+    reject_remote_pushes(&repo, "resume-c");
+    let output = repo.run_stax(&["submit", "--no-pr", "--yes"]);
+    assert!(
+        !output.status.success(),
+        "submit should fail on the rejected push"
+    );
+    let remote_branches = list_remote_heads(&repo);
+    assert!(remote_branches.contains(&branches[0]));
+    assert!(remote_branches.contains(&branches[1]));
+    assert!(!remote_branches.contains(&branches[2]));
+
+    allow_remote_pushes(&repo);
+    let output = repo.run_stax(&["submit", "--no-pr", "--yes"]);
+    assert!(
+        output.status.success(),
+        "resumed submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+    let stdout = TestRepo::stdout(&output);
+    assert!(
+        stdout.contains("Resuming interrupted submit") && stdout.contains("2 already pushed"),
+        "expected resume summary, got: {}",
+        stdout
+    );
+    assert!(stdout.contains("Will force-push 1 branch"));
+    assert!(list_remote_heads(&repo).contains(&branches[2]));
+
+    // A finished submit leaves nothing to resume
+    let output = repo.run_stax(&["submit", "--no-pr", "--yes"]);
+    assert!(output.status.success());
+    assert!(!TestRepo::stdout(&output).contains("Resuming"));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_submit_restart_ignores_interrupted_progress() {
+    let repo = TestRepo::new_with_remote();
+    configure_submit_remote(&repo);
+
+    for name in ["restart-a", "restart-b"] {
+        repo.run_stax(&["bc", name]);
+        repo.create_file(&format!("{}.txt", name), name);
+        repo.commit(&format!("{} commit", name));
+    }
+
+    reject_remote_pushes(&repo, "restart-b");
+    let output = repo.run_stax(&["submit", "--no-pr", "--yes"]);
+    assert!(!output.status.success());
+
+    allow_remote_pushes(&repo);
+    let output = repo.run_stax(&["submit", "--no-pr", "--yes", "--restart"]);
+    assert!(
+        output.status.success(),
+        "restarted submit failed: {}",
+        TestRepo::stderr(&output)
+    );
+    assert!(!TestRepo::stdout(&output).contains("Resuming"));
+}
+
 #[test]
 fn test_submit_help_shows_no_pr_flag() {
     let repo = TestRepo::new();
@@ -3185,6 +4150,72 @@ fn test_sync_preserves_branch_with_remote() {
     );
 }
 
+#[test]
+fn test_sync_relinks_branch_renamed_on_remote() {
+    let repo = TestRepo::new_with_remote();
+
+    repo.run_stax(&["bc", "feature-old-name"]);
+    let old_name = repo.current_branch();
+    repo.create_file("feature.txt", "feature content");
+    repo.commit("Feature commit");
+    repo.git(&["push", "-u", "origin", &old_name]);
+
+    // Child stacked on the branch that gets renamed
+    repo.run_stax(&["bc", "feature-child"]);
+    let child_name = repo.current_branch();
+    repo.create_file("child.txt", "child content");
+    repo.commit("Child commit");
+
+    // Rename the branch directly on the remote (as a teammate or the GitHub UI would)
+    let remote_path = repo.remote_path().unwrap();
+    let output = Command::new("git")
+        .args(["branch", "-m", &old_name, "feature-new-name"])
+        .current_dir(&remote_path)
+        .output()
+        .expect("Failed to rename remote branch");
+    assert!(output.status.success());
+
+    repo.run_stax(&["t"]);
+
+    let output = repo.run_stax(&["sync", "--force"]);
+    assert!(
+        output.status.success(),
+        "Sync failed: {}",
+        TestRepo::stderr(&output)
+    );
+    let stdout = TestRepo::stdout(&output);
+    assert!(
+        stdout.contains("renamed local") && stdout.contains("feature-new-name"),
+        "Expected sync to report the local rename, got: {}",
+        stdout
+    );
+
+    // Local branch follows the remote name instead of being deleted as merged
+    let branches = repo.list_branches();
+    assert!(branches.contains(&"feature-new-name".to_string()));
+    assert!(!branches.contains(&old_name));
+
+    let upstream = repo.git(&["rev-parse", "--abbrev-ref", "feature-new-name@{upstream}"]);
+    assert_eq!(
+        TestRepo::stdout(&upstream).trim(),
+        "origin/feature-new-name"
+    );
+
+    // No duplicate remote branch was created under the old name
+    let remote_branches = repo.list_remote_branches();
+    assert!(!remote_branches.contains(&old_name));
+
+    // The child now stacks on the renamed branch
+    let output = repo.run_stax(&["status", "--json"]);
+    let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+    let branches = json["branches"].as_array().unwrap();
+    let child = branches
+        .iter()
+        .find(|b| b["name"].as_str().unwrap() == child_name)
+        .expect("Should find child branch");
+    assert_eq!(child["parent"].as_str().unwrap(), "feature-new-name");
+}
+
 #[test]
 fn test_sync_updates_trunk_after_branch_deletion_checkout() {
     // This test verifies the fix for the issue where trunk update would fail
@@ -3885,6 +4916,7 @@ mod github_mock_tests {
             .args(args)
             .current_dir(repo.path())
             .env("HOME", home)
+            .env_remove("XDG_CONFIG_HOME")
             .env("GIT_CONFIG_GLOBAL", &gitconfig)
             .env("GIT_CONFIG_SYSTEM", &gitconfig)
             .output()
@@ -3953,6 +4985,7 @@ mod github_mock_tests {
             .args(args)
             .current_dir(repo.path())
             .env("HOME", home)
+            .env_remove("XDG_CONFIG_HOME")
             .env("GIT_CONFIG_GLOBAL", &gitconfig)
             .env("GIT_CONFIG_SYSTEM", &gitconfig)
             .env("STAX_GITHUB_TOKEN", "mock-token")
@@ -4077,6 +5110,165 @@ mod github_mock_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_submit_no_track_pr_leaves_metadata_untouched() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"/repos/test/repo/pulls"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "url": "https://api.github.com/repos/test/repo/pulls/42",
+                    "id": 42,
+                    "number": 42,
+                    "state": "open",
+                    "draft": false,
+                    "head": { "ref": "feature-branch", "sha": "aaaa", "label": "test:feature-branch" },
+                    "base": { "ref": "main", "sha": "bbbb" }
+                }
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        let home = TempDir::new().expect("Failed to create temp home");
+        let repo = TestRepo::new();
+        let _remote_root = setup_fake_github_remote(&repo, home.path());
+        write_test_config(home.path(), &mock_server.uri());
+
+        let output = run_stax_with_env(&repo, home.path(), &["bc", "feature-branch"]);
+        assert!(
+            output.status.success(),
+            "Failed to create branch: {}",
+            TestRepo::stderr(&output)
+        );
+
+        repo.create_file("feature.txt", "content");
+        repo.commit("Feature commit");
+
+        let branch = repo.current_branch();
+        let metadata_ref = format!("refs/branch-metadata/{}", branch);
+        let before = TestRepo::stdout(&repo.git(&["rev-parse", &metadata_ref]));
+
+        let output = run_stax_with_env(
+            &repo,
+            home.path(),
+            &["submit", "--no-pr", "--yes", "--no-track-pr"],
+        );
+        assert!(
+            output.status.success(),
+            "Submit failed: {}",
+            TestRepo::stderr(&output)
+        );
+
+        // The branch was pushed, but neither the PR nor the pushed revision was recorded
+        let remote_heads = git_with_env(
+            &repo,
+            home.path(),
+            &["ls-remote", "--heads", "origin", &branch],
+        );
+        assert!(!TestRepo::stdout(&remote_heads).trim().is_empty());
+        let after = TestRepo::stdout(&repo.git(&["rev-parse", &metadata_ref]));
+        assert_eq!(before, after, "metadata ref was rewritten");
+    }
+
+    #[tokio::test]
+    async fn test_status_check_pr_base_flags_mismatch() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/repos/test/repo/pulls$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "url": "https://api.github.com/repos/test/repo/pulls/42",
+                    "id": 42,
+                    "number": 42,
+                    "state": "open",
+                    "draft": false,
+                    "head": { "ref": "stacked-b", "sha": "aaaa", "label": "test:stacked-b" },
+                    "base": { "ref": "main", "sha": "bbbb" }
+                }
+            ])))
+            .mount(&mock_server)
+            .await;
+        // Someone retargeted the PR to main on GitHub
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/repos/test/repo/pulls/42$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "url": "https://api.github.com/repos/test/repo/pulls/42",
+                "id": 42,
+                "number": 42,
+                "state": "open",
+                "draft": false,
+                "head": { "ref": "stacked-b", "sha": "aaaa", "label": "test:stacked-b" },
+                "base": { "ref": "main", "sha": "bbbb" }
+            })))
+            .mount(&mock_server)
+            .await;
+        // status reads PR bases for the whole stack in one GraphQL query
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/graphql$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "repository": {
+                        "pr42": {
+                            "number": 42,
+                            "state": "OPEN",
+                            "isDraft": false,
+                            "baseRefName": "main",
+                            "mergeable": "MERGEABLE",
+                            "commits": { "nodes": [] }
+                        }
+                    }
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let home = TempDir::new().expect("Failed to create temp home");
+        let repo = TestRepo::new();
+        let _remote_root = setup_fake_github_remote(&repo, home.path());
+        write_test_config(home.path(), &mock_server.uri());
+
+        for name in ["stacked-a", "stacked-b"] {
+            let output = run_stax_with_env(&repo, home.path(), &["bc", name]);
+            assert!(
+                output.status.success(),
+                "Failed to create branch: {}",
+                TestRepo::stderr(&output)
+            );
+            repo.create_file(&format!("{}.txt", name), "content");
+            repo.commit(&format!("{} commit", name));
+        }
+
+        let output = run_stax_with_env(&repo, home.path(), &["submit", "--no-pr", "--yes"]);
+        assert!(
+            output.status.success(),
+            "Submit failed: {}",
+            TestRepo::stderr(&output)
+        );
+
+        let output =
+            run_stax_with_env(&repo, home.path(), &["status", "--json", "--check-pr-base"]);
+        assert!(
+            output.status.success(),
+            "Status failed: {}",
+            TestRepo::stderr(&output)
+        );
+        let json: Value = serde_json::from_str(&TestRepo::stdout(&output)).unwrap();
+        let branch = json["branches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|b| b["name"] == "stacked-b")
+            .expect("stacked-b in status");
+        assert_eq!(branch["parent"], "stacked-a");
+        assert_eq!(branch["pr_base"], "main");
+        assert_eq!(branch["pr_base_mismatch"], true);
+
+        let output = run_stax_with_env(&repo, home.path(), &["status", "--check-pr-base"]);
+        assert!(TestRepo::stdout(&output).contains("PR base: main, stax parent: stacked-a"));
+    }
+
     #[tokio::test]
     async fn test_submit_does_not_persist_pr_info_for_fork() {
         let mock_server = MockServer::start().await;
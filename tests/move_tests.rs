@@ -0,0 +1,90 @@
+//! Branch move command integration tests
+//!
+//! Tests for `branch move --to-top` / `--to-bottom`, which reparent a branch to
+//! either end of its stack and restack everything affected.
+
+mod common;
+
+use common::TestRepo;
+use serde_json::Value;
+
+fn parent_of(json: &Value, branch: &str) -> Option<String> {
+    json["branches"]
+        .as_array()?
+        .iter()
+        .find(|b| b["name"].as_str() == Some(branch))?["parent"]
+        .as_str()
+        .map(|s| s.to_string())
+}
+
+#[test]
+fn test_move_to_bottom_reorders_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["move-a", "move-b", "move-c"]);
+    let (a, b, c) = (&branches[0], &branches[1], &branches[2]);
+
+    let output = repo.run_stax(&["branch", "move", "--branch", c, "--to-bottom"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    let json = repo.get_status_json();
+    assert_eq!(parent_of(&json, c).as_deref(), Some("main"));
+    assert_eq!(parent_of(&json, a).as_deref(), Some(c.as_str()));
+    assert_eq!(parent_of(&json, b).as_deref(), Some(a.as_str()));
+
+    // The moved branch now carries only its own commit on top of trunk
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", c)]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", b)]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "3");
+
+    // Should return to the original branch
+    assert_eq!(repo.current_branch(), *c);
+}
+
+#[test]
+fn test_move_to_top_reorders_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["top-a", "top-b", "top-c"]);
+    let (a, b, c) = (&branches[0], &branches[1], &branches[2]);
+
+    let output = repo.run_stax(&["branch", "move", "--branch", a, "--to-top"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    let json = repo.get_status_json();
+    assert_eq!(parent_of(&json, b).as_deref(), Some("main"));
+    assert_eq!(parent_of(&json, c).as_deref(), Some(b.as_str()));
+    assert_eq!(parent_of(&json, a).as_deref(), Some(c.as_str()));
+
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", b)]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "1");
+}
+
+#[test]
+fn test_move_noop_when_already_at_end() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["end-a", "end-b"]);
+    let before = repo.get_commit_sha(&branches[1]);
+
+    let output = repo.run_stax(&["branch", "move", "--to-top"]);
+    assert!(output.status.success());
+    assert!(TestRepo::stdout(&output).contains("already at the top"));
+    assert_eq!(repo.get_commit_sha(&branches[1]), before);
+}
+
+#[test]
+fn test_move_requires_direction() {
+    let repo = TestRepo::new();
+    repo.create_stack(&["dir-a"]);
+
+    let output = repo.run_stax(&["branch", "move"]);
+    assert!(!output.status.success());
+    assert!(TestRepo::stderr(&output).contains("--to-top or --to-bottom"));
+}
@@ -387,6 +387,51 @@ fn test_down_count_exceeds_stack() {
     );
 }
 
+// =============================================================================
+// Go Command Tests
+// =============================================================================
+
+#[test]
+fn test_go_selectors_resolve_within_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["go-1", "go-2", "go-3"]);
+
+    repo.run_stax(&["go", "bottom"]).assert_success();
+    assert_eq!(repo.current_branch(), branches[0]);
+
+    repo.run_stax(&["go", "child"]).assert_success();
+    assert_eq!(repo.current_branch(), branches[1]);
+
+    repo.run_stax(&["go", "top"]).assert_success();
+    assert_eq!(repo.current_branch(), branches[2]);
+
+    repo.run_stax(&["go", "parent"]).assert_success();
+    assert_eq!(repo.current_branch(), branches[1]);
+
+    repo.run_stax(&["go", "trunk"]).assert_success();
+    assert_eq!(repo.current_branch(), "main");
+
+    repo.run_stax(&["navigate", &branches[2]]).assert_success();
+    assert_eq!(repo.current_branch(), branches[2]);
+}
+
+#[test]
+fn test_go_child_with_multiple_children_errors_when_scripted() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["fork-base", "fork-a"]);
+    repo.run_stax(&["checkout", &branches[0]]);
+    repo.run_stax(&["bc", "fork-b"]).assert_success();
+    repo.run_stax(&["checkout", &branches[0]]);
+
+    let output = repo.run_stax(&["go", "child"]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("multiple children"));
+    assert_eq!(repo.current_branch(), branches[0]);
+
+    let output = repo.run_stax(&["go", "no-such-branch"]);
+    output.assert_failure();
+}
+
 // =============================================================================
 // Combined Navigation Tests
 // =============================================================================
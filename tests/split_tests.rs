@@ -195,3 +195,69 @@ fn test_split_from_middle_of_stack_passes_validation() {
         stderr
     );
 }
+
+// =============================================================================
+// Non-interactive Split (--at)
+// =============================================================================
+
+#[test]
+fn test_branch_split_at_creates_stacked_branches() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["feature", "child"]);
+    repo.run_stax(&["checkout", &branches[0]]).assert_success();
+    repo.create_file("second.txt", "content");
+    repo.commit("second commit");
+    repo.create_file("third.txt", "content");
+    repo.commit("third commit");
+
+    // Order of --at doesn't matter; ranges follow commit order
+    let output = repo.run_stax(&[
+        "branch",
+        "split",
+        "--at",
+        "HEAD~1:part-2",
+        "--at",
+        "HEAD~2:part-1",
+    ]);
+    output.assert_success();
+
+    repo.run_stax(&["checkout", "part-1"]).assert_success();
+    assert_eq!(repo.get_current_parent(), Some("main".to_string()));
+    repo.run_stax(&["checkout", "part-2"]).assert_success();
+    assert_eq!(repo.get_current_parent(), Some("part-1".to_string()));
+    repo.run_stax(&["checkout", &branches[0]]).assert_success();
+    assert_eq!(repo.get_current_parent(), Some("part-2".to_string()));
+
+    // Every commit lands in exactly one range, and the child stays on top
+    for (base, tip) in [
+        ("main", "part-1"),
+        ("part-1", "part-2"),
+        ("part-2", branches[0].as_str()),
+    ] {
+        let count = repo.git(&["rev-list", "--count", &format!("{}..{}", base, tip)]);
+        assert_eq!(TestRepo::stdout(&count).trim(), "1", "{}..{}", base, tip);
+    }
+    assert_eq!(repo.get_children(&branches[0]), vec![branches[1].clone()]);
+}
+
+#[test]
+fn test_branch_split_at_rejects_bad_ranges() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["feature"]);
+    repo.create_file("second.txt", "content");
+    repo.commit("second commit");
+    let before = repo.list_branches();
+
+    for args in [
+        vec!["--at", "HEAD~1:one", "--at", "HEAD~1:two"],
+        vec!["--at", "HEAD:one"],
+        vec!["--at", "main:one"],
+        vec!["--at", branches[0].as_str()],
+    ] {
+        let mut full = vec!["branch", "split"];
+        full.extend(args.iter().copied());
+        let output = repo.run_stax(&full);
+        output.assert_failure();
+    }
+    assert_eq!(repo.list_branches(), before);
+}
@@ -0,0 +1,81 @@
+//! Stack squash-all integration tests
+//!
+//! Tests for `stack squash-all`, which collapses a linear stack into its bottom
+//! branch and deletes the branches above it.
+
+mod common;
+
+use common::TestRepo;
+
+#[test]
+fn test_squash_all_collapses_stack_into_bottom() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["sq-a", "sq-b", "sq-c"]);
+    let (a, b, c) = (&branches[0], &branches[1], &branches[2]);
+    let tip = repo.get_commit_sha(c);
+
+    let output = repo.run_stax(&["stack", "squash-all", "--yes"]);
+    assert!(
+        output.status.success(),
+        "Failed: {}",
+        TestRepo::stderr(&output)
+    );
+
+    // Bottom branch now holds every commit, in order
+    assert_eq!(repo.current_branch(), *a);
+    assert_eq!(repo.get_commit_sha(a), tip);
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", a)]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "3");
+
+    let local = repo.list_branches();
+    assert!(!local.contains(b));
+    assert!(!local.contains(c));
+
+    let json = repo.get_status_json();
+    let names: Vec<&str> = json["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|b| b["name"].as_str())
+        .filter(|name| *name != "main")
+        .collect();
+    assert_eq!(names, vec![a.as_str()]);
+}
+
+#[test]
+fn test_squash_all_undo_restores_branches() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["undo-a", "undo-b"]);
+    let (a, b) = (&branches[0], &branches[1]);
+    let a_before = repo.get_commit_sha(a);
+    let b_before = repo.get_commit_sha(b);
+
+    let output = repo.run_stax(&["stack", "squash-all", "--yes"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+
+    let output = repo.run_stax(&["undo", "--yes"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+
+    assert_eq!(repo.get_commit_sha(a), a_before);
+    assert_eq!(repo.get_commit_sha(b), b_before);
+
+    // Metadata comes back too, so the stack is tracked again
+    repo.run_stax(&["checkout", b]);
+    assert_eq!(repo.get_current_parent().as_deref(), Some(a.as_str()));
+}
+
+#[test]
+fn test_squash_all_rejects_forked_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["fork-a", "fork-b"]);
+    repo.run_stax(&["checkout", &branches[0]]);
+    repo.create_file("fork.txt", "fork");
+    repo.commit("fork commit");
+    let output = repo.run_stax(&["create", "fork-c"]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+
+    let output = repo.run_stax(&["stack", "squash-all", "--yes"]);
+    assert!(!output.status.success());
+    assert!(TestRepo::stderr(&output).contains("linear stacks"));
+    assert!(repo.list_branches().contains(&branches[1]));
+}
@@ -0,0 +1,50 @@
+//! Stack onto integration tests
+//!
+//! Tests for `stack onto`, which re-parents the bottom of the current stack
+//! onto the tip of another stack and restacks everything above it.
+
+mod common;
+
+use common::{OutputAssertions, TestRepo};
+
+#[test]
+fn test_stack_onto_chains_stacks() {
+    let repo = TestRepo::new();
+    let first = repo.create_stack(&["base-a", "base-b"]);
+    repo.run_stax(&["t"]).assert_success();
+    let second = repo.create_stack(&["top-a", "top-b"]);
+
+    // Any branch in the target stack resolves to its tip
+    let output = repo.run_stax(&["stack", "onto", &first[0]]);
+    assert!(output.status.success(), "{}", TestRepo::stderr(&output));
+    assert!(TestRepo::stdout(&output).contains("Stacked"));
+
+    assert_eq!(repo.current_branch(), second[1]);
+    let ancestor = repo.git(&["merge-base", "--is-ancestor", &first[1], &second[0]]);
+    assert!(ancestor.status.success());
+    let count = repo.git(&["rev-list", "--count", &format!("main..{}", second[1])]);
+    assert_eq!(TestRepo::stdout(&count).trim(), "4");
+
+    let json = repo.get_status_json();
+    let parent_of = |name: &str| {
+        json["branches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|b| b["name"] == name)
+            .and_then(|b| b["parent"].as_str())
+            .map(str::to_string)
+    };
+    assert_eq!(parent_of(&second[0]).as_deref(), Some(first[1].as_str()));
+    assert_eq!(parent_of(&second[1]).as_deref(), Some(second[0].as_str()));
+}
+
+#[test]
+fn test_stack_onto_rejects_same_stack() {
+    let repo = TestRepo::new();
+    let branches = repo.create_stack(&["cyc-a", "cyc-b"]);
+
+    let output = repo.run_stax(&["stack", "onto", &branches[1]]);
+    output.assert_failure();
+    assert!(TestRepo::stderr(&output).contains("cycle"));
+}
@@ -282,3 +282,30 @@ fn status_and_diff_worktree_smoke_reports_expected_non_empty_changes() {
         "Expected tracked branch diff output"
     );
 }
+
+#[test]
+fn status_reports_branches_checked_out_in_other_worktrees() {
+    let (repo, a, b, wt_a, wt_b) = setup_stack_with_worktrees(false);
+
+    let worktree_of = |status: &Value, branch: &str| -> Option<String> {
+        status["branches"]
+            .as_array()
+            .and_then(|branches| branches.iter().find(|e| e["name"].as_str() == Some(branch)))
+            .and_then(|e| e["worktree"].as_str().map(str::to_string))
+    };
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap().display().to_string();
+
+    let status = status_json(&repo, &repo.path());
+    assert_eq!(worktree_of(&status, &a), Some(canonical(&wt_a)));
+    assert_eq!(worktree_of(&status, &b), Some(canonical(&wt_b)));
+    assert_eq!(worktree_of(&status, "main"), None);
+
+    // From inside wt-b, its own branch is not "elsewhere" but main now is
+    let status = status_json(&repo, &wt_b);
+    assert_eq!(worktree_of(&status, &b), None);
+    assert_eq!(worktree_of(&status, "main"), Some(canonical(&repo.path())));
+
+    let output = repo.run_stax(&["status"]);
+    output.assert_success();
+    assert!(TestRepo::stdout(&output).contains("[worktree: "));
+}